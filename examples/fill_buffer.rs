@@ -2,17 +2,20 @@
  * Spare Buffer
  * This is free and unencumbered software released into the public domain.
  */
+#[cfg(not(feature = "safe-only"))]
 use std::num::NonZeroUsize;
 
+#[cfg(not(feature = "safe-only"))]
 use spare_buffer::SpareBuffer;
 
+#[cfg(not(feature = "safe-only"))]
 fn main() {
     let mut vec: Vec<u8> = Vec::with_capacity(128);
     let mut buffer = SpareBuffer::from(&mut vec, None);
     
     let spare = buffer.allocate_spare(NonZeroUsize::new(100).unwrap());
-    for i in 0..50 {
-        spare[i] = i as u8;
+    for (i, value) in spare[..50].iter_mut().enumerate() {
+        *value = i as u8;
     }
 
     // Whoops: only &spare[0..50] was initialized, but 100 elements are committed!
@@ -24,3 +27,8 @@ fn main() {
     println!("Expect \"unspecified\" garbage:");
     println!("{:?}\n", &vec[50..]);
 }
+
+#[cfg(feature = "safe-only")]
+fn main() {
+    println!("This example requires allocate_spare(), which is not available with the \"safe-only\" feature!");
+}
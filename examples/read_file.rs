@@ -3,7 +3,6 @@
  * This is free and unencumbered software released into the public domain.
  */
 use std::fs::File;
-use std::io::Read;
 use std::num::NonZeroUsize;
 
 use spare_buffer::SpareBuffer;
@@ -15,15 +14,7 @@ fn main() {
     let chunk_size = NonZeroUsize::new(4096).unwrap();
     let mut file = File::open("input.dat").expect("Failed to open input file!");
 
-    loop {
-        let spare = buffer.allocate_spare(chunk_size);
-        let count = file.read(spare).expect("File read error encountered!");
-        if count > 0 {
-            buffer.commit(count).expect("Failed to commit!");
-        } else {
-            break; /* EOF*/
-        }
-    }
+    buffer.fill_from(&mut file, chunk_size).expect("File read error encountered!");
 
     println!("Length: {:?}", vec.len());
 }
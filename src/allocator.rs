@@ -0,0 +1,226 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::{Read, Result as IoResult, Error as IoError, ErrorKind};
+use std::mem::MaybeUninit;
+use std::num::NonZeroUsize;
+
+use allocator_api2::alloc::Allocator;
+use allocator_api2::vec::Vec;
+
+use crate::{Primitive, RetryPolicy};
+#[cfg(not(feature = "safe-only"))]
+use crate::primitive::assume_init_mut;
+use crate::primitive::foreign_mut;
+use crate::sanitizer::{mark_init, mark_uninit};
+
+/// A variant of the [**`SpareBuffer`**](crate::SpareBuffer) that wraps an
+/// *allocator-generic* `allocator_api2::vec::Vec<T, A>`.
+///
+/// This allows vectors that are backed by a custom allocator, such as a bump
+/// allocator, a pool allocator or a DMA-capable allocator, to be filled via
+/// the "spare" buffer, **without** losing the allocator type. The
+/// [`allocator-api2`](https://crates.io/crates/allocator-api2) crate provides
+/// the `Allocator` trait on *stable* Rust, and it is supported by crates like
+/// [bumpalo](https://crates.io/crates/bumpalo).
+///
+/// Provides the *core* allocate/commit API of the **`SpareBuffer`**.
+pub struct SpareBufferIn<'a, T, A>
+where
+    T: Primitive,
+    A: Allocator
+{
+    buffer: &'a mut Vec<T, A>,
+    limit: Option<NonZeroUsize>,
+    allocated: bool,
+    uninit: bool,
+}
+
+impl<'a, T, A> SpareBufferIn<'a, T, A>
+where
+    T: Primitive,
+    A: Allocator
+{
+    /// Creates a new **`SpareBufferIn`** from an existing vector.
+    ///
+    /// An *optional* `limit` for the length of the vector can be specified.
+    /// The [`commit()`](Self::commit) fails, if it would exceed this limit.
+    pub fn from(buffer: &'a mut Vec<T, A>, limit: Option<NonZeroUsize>) -> Self {
+        Self {
+            buffer,
+            limit,
+            allocated: false,
+            uninit: false,
+        }
+    }
+
+    /// Returns the current length of the underlying vector, i.e. the number
+    /// of "committed" elements.
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Returns `true` if the underlying vector contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    /// Returns the length limit, if a limit has been specified. Otherwise
+    /// `None` is returned.
+    pub fn limit(&self) -> Option<NonZeroUsize> {
+        self.limit
+    }
+
+    /// Returns the "committed" elements, as a `&[T]` slice.
+    pub fn data(&self) -> &[T] {
+        self.buffer.as_slice()
+    }
+
+    /// Returns the allocator of the underlying vector.
+    pub fn allocator(&self) -> &A {
+        self.buffer.allocator()
+    }
+
+    /// Allocates a "spare" buffer of the specified `length`, by reserving
+    /// capacity from the allocator of the underlying vector.
+    ///
+    /// Returns a `&mut[T]` slice which allows the caller to access the
+    /// allocated "spare" buffer. No guarantees are provided about the
+    /// *initial* contents of the buffer! The "spare" buffer is **not**
+    /// considered to be a valid part of the underlying vector, until the
+    /// [`commit()`](Self::commit) function is called eventually.
+    ///
+    /// Not available with the **`safe-only`** feature.
+    #[cfg(not(feature = "safe-only"))]
+    pub fn allocate_spare(&mut self, length: NonZeroUsize) -> &mut[T] {
+        unsafe {
+            assume_init_mut(self.spare_uninit(length))
+        }
+    }
+
+    /// Allocates a "spare" buffer of the specified `length`, exactly like
+    /// [`allocate_spare()`](Self::allocate_spare) does, but returns it as a
+    /// `&mut[MaybeUninit<T>]` slice. Available with the **`safe-only`**
+    /// feature.
+    ///
+    /// A "spare" buffer that was allocated this way can **not** be committed
+    /// by [`commit()`](Self::commit). Use
+    /// [`commit_written()`](Self::commit_written) instead.
+    pub fn allocate_spare_uninit(&mut self, length: NonZeroUsize) -> &mut[MaybeUninit<T>] {
+        self.spare_uninit(length);
+        self.uninit = true;
+        self.buffer.spare_capacity_mut()
+    }
+
+    /// Allocates a "spare" buffer of the specified `length`, and lets `f`
+    /// initialize it, as a `&mut[MaybeUninit<T>]` slice. Then commits the
+    /// elements that `f` *returns*, exactly like
+    /// [`SpareBuffer::commit_written()`](crate::SpareBuffer::commit_written)
+    /// does, **without** any `unsafe` code.
+    ///
+    /// Returns the number of elements that have been committed. If `f`
+    /// panics, **nothing** is committed.
+    ///
+    /// # Errors
+    ///
+    /// Fails with the same errors as [`commit()`](Self::commit).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slice returned by `f` is **not** a prefix of the "spare"
+    /// buffer.
+    pub fn commit_written<F>(&mut self, length: NonZeroUsize, f: F) -> IoResult<usize>
+    where
+        F: for<'s> FnOnce(&'s mut [MaybeUninit<T>]) -> &'s [T]
+    {
+        let spare = self.spare_uninit(length);
+        let start = spare.as_ptr() as *const T;
+        let capacity = spare.len();
+        self.allocated = false;
+        let written = f(self.buffer.spare_capacity_mut());
+        assert!(written.as_ptr() == start && written.len() <= capacity, "The written slice is not a prefix of the spare buffer!");
+        let count = written.len();
+        self.allocated = true;
+        self.commit(count).map(|_| count)
+    }
+
+    /// Allocates a "spare" buffer of the specified `length`, for *internal*
+    /// use. See [`allocate_spare()`](Self::allocate_spare) for details.
+    fn spare_uninit(&mut self, length: NonZeroUsize) -> &mut[MaybeUninit<T>] {
+        self.buffer.reserve(length.get());
+        self.allocated = true;
+        self.uninit = false;
+        let spare = self.buffer.spare_capacity_mut();
+        mark_uninit(spare.as_ptr(), spare.len());
+        spare
+    }
+
+    /// Commits the first `additional` elements of the "spare" buffer, i.e.
+    /// *appends* them to the underlying vector **without** copying the data.
+    ///
+    /// The same requirements as for
+    /// [`SpareBuffer::commit()`](crate::SpareBuffer::commit) apply, i.e. all
+    /// elements to be committed **must** have been initialized. This function
+    /// always invalidates the current "spare" buffer.
+    ///
+    /// # Errors
+    ///
+    /// If a length limit has been specified, then this function will fail, if
+    /// adding `additional` more elements would cause the length of the
+    /// underlying vector to exceed the specified limit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `additional` is greater than the available "spare" capacity,
+    /// or if **no** "spare" buffer was allocated before! Also panics, if
+    /// `additional` is **not** zero, but the "spare" buffer was allocated by
+    /// [`allocate_spare_uninit()`](Self::allocate_spare_uninit).
+    pub fn commit(&mut self, additional: usize) -> IoResult<()> {
+        assert!(std::mem::replace(&mut self.allocated, false), "No spare buffer allocated!");
+        assert!(additional == 0 || !self.uninit, "Spare buffer was allocated uninitialized, use commit_written()!");
+        if additional > 0 {
+            let new_length = self.buffer.len().checked_add(additional).expect("Numerical overflow! (new_length)");
+            assert!(new_length <= self.buffer.capacity(), "Commit size exceeds available capacity!");
+            if new_length > self.limit.map_or(usize::MAX, NonZeroUsize::get) {
+                return Err(IoError::new(ErrorKind::OutOfMemory, "The new length exceeds the specified limit!"));
+            }
+            mark_init(self.buffer.spare_capacity_mut().as_ptr(), additional);
+            unsafe {
+                self.buffer.set_len(new_length)
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a, A> SpareBufferIn<'a, u8, A>
+where
+    A: Allocator
+{
+    /// Reads up to `length` bytes from `reader` into the "spare" buffer, and
+    /// commits the data. Reads that fail with
+    /// [`ErrorKind::Interrupted`](std::io::ErrorKind::Interrupted) are
+    /// retried.
+    ///
+    /// Returns the number of bytes that have been committed, which is `0` at
+    /// the end of the stream.
+    ///
+    /// # Errors
+    ///
+    /// Errors returned by the `reader`, as well as errors returned by
+    /// [`commit()`](Self::commit), are passed through.
+    pub fn read_from<R>(&mut self, reader: &mut R, length: NonZeroUsize) -> IoResult<usize>
+    where
+        R: Read
+    {
+        let spare = foreign_mut(&mut self.spare_uninit(length)[..length.get()]);
+        match RetryPolicy::new().retry(|| reader.read(spare)) {
+            Ok(count) => self.commit(count).map(|_| count),
+            Err(error) => {
+                self.allocated = false;
+                Err(error)
+            },
+        }
+    }
+}
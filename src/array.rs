@@ -0,0 +1,205 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::{Read, Result as IoResult};
+use std::mem::MaybeUninit;
+use std::num::NonZeroUsize;
+
+use crate::{Primitive, SpareCapacity};
+use crate::fixed::Fixed;
+#[cfg(not(feature = "safe-only"))]
+use crate::primitive::assume_init_mut;
+
+/// A *fixed-capacity* buffer of `N` elements, which is stored *inline*, e.g.
+/// on the stack, and which **never** allocates.
+///
+/// Provides the same allocate/commit API as the
+/// [**`SpareBuffer`**](crate::SpareBuffer), for hot paths where heap
+/// allocation is forbidden, such as signal handlers, interrupt contexts or
+/// per-packet scratch buffers. The "spare" buffer is simply the unused part
+/// of the array.
+///
+/// The **`SpareArray`** also implements the
+/// [**`SpareCapacity`**](crate::SpareCapacity) trait, so that it can be used
+/// as the backend of a **`SpareBuffer`**. Reserving more than the fixed
+/// capacity *panics*, in that case. Because the elements are stored inline,
+/// an *owned* array can **not** be
+/// [locked into memory](crate::SpareBuffer::lock_memory).
+pub struct SpareArray<T, const N: usize>
+where
+    T: Primitive
+{
+    inner: Fixed<T, [MaybeUninit<T>; N]>,
+}
+
+impl<T, const N: usize> SpareArray<T, N>
+where
+    T: Primitive
+{
+    /// Creates a new, empty **`SpareArray`**.
+    pub const fn new() -> Self {
+        Self {
+            inner: Fixed::new([const { MaybeUninit::uninit() }; N]),
+        }
+    }
+
+    /// Returns the *fixed* capacity, i.e. `N`.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the number of "committed" elements.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if the array contains no "committed" elements.
+    pub fn is_empty(&self) -> bool {
+        self.inner.len() == 0
+    }
+
+    /// Returns `true` if the array is full, i.e. nothing can be committed
+    /// anymore.
+    pub fn is_full(&self) -> bool {
+        self.inner.free() == 0
+    }
+
+    /// Returns the number of elements that can still be committed.
+    pub fn free(&self) -> usize {
+        self.inner.free()
+    }
+
+    /// Returns a `&[T]` slice of all "committed" elements.
+    pub fn data(&self) -> &[T] {
+        self.inner.data()
+    }
+
+    /// Allocates a "spare" buffer of (at least) the specified `length`,
+    /// exactly like [`SpareSlice::allocate_spare()`](crate::SpareSlice::allocate_spare)
+    /// does.
+    ///
+    /// Not available with the **`safe-only`** feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `length` exceeds the free space.
+    #[cfg(not(feature = "safe-only"))]
+    pub fn allocate_spare(&mut self, length: NonZeroUsize) -> &mut[T] {
+        unsafe {
+            assume_init_mut(self.inner.spare_uninit(length))
+        }
+    }
+
+    /// Allocates a "spare" buffer of (at least) the specified `length`,
+    /// exactly like [`allocate_spare()`](Self::allocate_spare) does, but
+    /// returns it as a `&mut[MaybeUninit<T>]` slice. Available with the
+    /// **`safe-only`** feature.
+    ///
+    /// A "spare" buffer that was allocated this way can **not** be committed
+    /// by [`commit()`](Self::commit). Use
+    /// [`commit_written()`](Self::commit_written) instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `length` exceeds the free space.
+    pub fn allocate_spare_uninit(&mut self, length: NonZeroUsize) -> &mut[MaybeUninit<T>] {
+        self.inner.allocate_uninit(length)
+    }
+
+    /// Lets `f` initialize a "spare" buffer of (at least) the specified
+    /// `length`, and commits the elements that `f` *returns*, exactly like
+    /// [`SpareSlice::commit_written()`](crate::SpareSlice::commit_written)
+    /// does.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `length` exceeds the free space, or if the slice returned
+    /// by `f` is **not** a prefix of the "spare" buffer.
+    pub fn commit_written<F>(&mut self, length: NonZeroUsize, f: F) -> usize
+    where
+        F: for<'s> FnOnce(&'s mut [MaybeUninit<T>]) -> &'s [T]
+    {
+        self.inner.commit_written(length, f)
+    }
+
+    /// Commits the first `additional` elements of the "spare" buffer, exactly
+    /// like [`SpareSlice::commit()`](crate::SpareSlice::commit) does.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `additional` is greater than the free space, or if **no**
+    /// "spare" buffer was allocated before! Also panics, if `additional` is
+    /// **not** zero, but the "spare" buffer was allocated by
+    /// [`allocate_spare_uninit()`](Self::allocate_spare_uninit).
+    pub fn commit(&mut self, additional: usize) {
+        self.inner.commit(additional)
+    }
+
+    /// Removes *all* "committed" elements. The current "spare" buffer is
+    /// invalidated.
+    pub fn clear(&mut self) {
+        self.inner.clear()
+    }
+}
+
+impl<const N: usize> SpareArray<u8, N> {
+    /// Reads up to `length` bytes from `reader` into the "spare" buffer, and
+    /// commits the data, exactly like
+    /// [`SpareSlice::read_from()`](crate::SpareSlice::read_from) does.
+    ///
+    /// # Errors
+    ///
+    /// Fails with an error of kind
+    /// [`ErrorKind::OutOfMemory`](std::io::ErrorKind::OutOfMemory), if
+    /// `length` exceeds the free space. Errors returned by the `reader` are
+    /// passed through, and nothing is committed in that case.
+    pub fn read_from<R>(&mut self, reader: &mut R, length: NonZeroUsize) -> IoResult<usize>
+    where
+        R: Read
+    {
+        self.inner.read_from(reader, length, "The array is full!")
+    }
+}
+
+impl<T, const N: usize> Default for SpareArray<T, N>
+where
+    T: Primitive
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+unsafe impl<T, const N: usize> SpareCapacity<T> for SpareArray<T, N>
+where
+    T: Primitive
+{
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    fn capacity(&self) -> usize {
+        N
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.inner.reserve(additional)
+    }
+
+    fn as_ptr(&self) -> *const T {
+        self.inner.as_ptr()
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut T {
+        self.inner.as_mut_ptr()
+    }
+
+    unsafe fn set_len(&mut self, new_len: usize) {
+        self.inner.set_len(new_len)
+    }
+
+    fn is_inline(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,117 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::future::poll_fn;
+use std::io::{Read, Result as IoResult};
+use std::num::NonZeroUsize;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_io::{AsyncBufRead, AsyncRead, AsyncWrite};
+
+use crate::{SpareBuffer, SpareCapacity};
+
+impl<'a, B> SpareBuffer<'a, u8, B>
+where
+    B: SpareCapacity<u8>
+{
+    /// Fills a "spare" buffer of the specified `length` by a *single* read
+    /// from the given `futures_io::AsyncRead`, and commits the data.
+    ///
+    /// This works with any runtime that uses the `futures-io` traits, e.g.
+    /// smol or async-std. Returns the number of bytes that have been
+    /// committed. A return value of `0` indicates that the end of the stream
+    /// was reached.
+    ///
+    /// # Errors
+    ///
+    /// Errors of the underlying reader, as well as errors returned by
+    /// [`commit()`](Self::commit), are passed through. Errors of kind
+    /// [`ErrorKind::Interrupted`](std::io::ErrorKind::Interrupted) are retried
+    /// according to the [retry policy](Self::retry_policy).
+    pub async fn read_async<R>(&mut self, reader: &mut R, length: NonZeroUsize) -> IoResult<usize>
+    where
+        R: AsyncRead + Unpin
+    {
+        poll_fn(|cx| self.poll_fill_spare(cx, length, |cx, spare| Pin::new(&mut *reader).poll_read(cx, spare))).await
+    }
+
+    /// Fills the buffer from the given `futures_io::AsyncRead`, by reading
+    /// chunks of `chunk` bytes into the "spare" buffer and committing them,
+    /// until the end of the stream is reached, *or* the length limit has been
+    /// reached.
+    ///
+    /// This is the asynchronous counterpart of
+    /// [`fill_from()`](Self::fill_from). Returns the number of bytes that
+    /// have been committed.
+    ///
+    /// # Errors
+    ///
+    /// Fails with the same errors as [`read_async()`](Self::read_async). The
+    /// bytes read up to that point remain committed.
+    pub async fn fill_from_async<R>(&mut self, reader: &mut R, chunk: NonZeroUsize) -> IoResult<usize>
+    where
+        R: AsyncRead + Unpin
+    {
+        let max_allocation = self.max_allocation().map_or(usize::MAX, NonZeroUsize::get);
+        let start = self.len();
+        while let Some(length) = NonZeroUsize::new(chunk.get().min(self.headroom()).min(max_allocation)) {
+            if self.read_async(reader, length).await? == 0 {
+                break; /* EOF */
+            }
+        }
+        Ok(self.len() - start)
+    }
+}
+
+/// Writes by copying into the "spare" buffer, which is committed right away,
+/// exactly like the `tokio::io::AsyncWrite` implementation does.
+///
+/// If a length limit has been specified, then a write is *shortened* to the
+/// remaining headroom, so that the limit is never exceeded. Once the limit
+/// has been reached, writing a non-empty slice fails with an error of kind
+/// [`ErrorKind::OutOfMemory`](std::io::ErrorKind::OutOfMemory). A write is
+/// shortened to the maximum allocation size as well.
+impl<'a, B> AsyncWrite for SpareBuffer<'a, u8, B>
+where
+    B: SpareCapacity<u8> + Unpin
+{
+    fn poll_write(self: Pin<&mut Self>, _cx: &mut Context<'_>, buf: &[u8]) -> Poll<IoResult<usize>> {
+        Poll::Ready(self.get_mut().append_partial(buf))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<IoResult<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<IoResult<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Reads the "committed" elements that have **not** been
+/// [consumed](crate::SpareBuffer::consumed) yet, exactly like the
+/// [`BufRead`](std::io::BufRead) implementation does. Never returns
+/// `Poll::Pending`.
+impl<'a, B> AsyncRead for SpareBuffer<'a, u8, B>
+where
+    B: SpareCapacity<u8> + Unpin
+{
+    fn poll_read(self: Pin<&mut Self>, _cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<IoResult<usize>> {
+        Poll::Ready(Read::read(self.get_mut(), buf))
+    }
+}
+
+impl<'a, B> AsyncBufRead for SpareBuffer<'a, u8, B>
+where
+    B: SpareCapacity<u8> + Unpin
+{
+    fn poll_fill_buf(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<IoResult<&[u8]>> {
+        Poll::Ready(Ok(self.get_mut().unconsumed()))
+    }
+
+    fn consume(self: Pin<&mut Self>, amount: usize) {
+        SpareBuffer::consume(self.get_mut(), amount)
+    }
+}
@@ -0,0 +1,60 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::future::poll_fn;
+use std::pin::pin;
+
+use bytes::Buf;
+use http_body::Body;
+
+use crate::{BodyError, SpareBuffer};
+
+impl<'a> SpareBuffer<'a, u8> {
+    /// Accumulates an HTTP body into the underlying vector, with a *hard*
+    /// length limit.
+    ///
+    /// This replaces the common `to_bytes(body, usize::MAX)` pattern. If the
+    /// size hint of the body shows that it will exceed the length limit, the
+    /// function fails *before* reading any data. Otherwise, the capacity is
+    /// reserved up front, according to the size hint, and the data frames are
+    /// appended as they arrive. Trailers are ignored.
+    ///
+    /// Returns the total number of bytes that have been committed.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`BodyError::TooLarge`](crate::BodyError::TooLarge), as soon
+    /// as the body is known to exceed the length limit, or if the capacity
+    /// for its size hint can **not** be reserved. The body is **not** read
+    /// any further in that case. Errors yielded by the body are returned
+    /// as [`BodyError::Body`](crate::BodyError::Body). Use
+    /// [`BodyError::status()`](crate::BodyError::status) to obtain the status
+    /// code for the response, e.g. `413 Payload Too Large`.
+    pub async fn collect_body<B>(&mut self, body: B) -> Result<usize, BodyError<B::Error>>
+    where
+        B: Body
+    {
+        let hint = body.size_hint();
+        if !self.fits_limit(usize::try_from(hint.lower()).unwrap_or(usize::MAX)) {
+            return Err(BodyError::TooLarge);
+        }
+        self.reserve_hint(hint.upper().unwrap_or(hint.lower()).try_into().unwrap_or(usize::MAX)).map_err(|_| BodyError::TooLarge)?;
+
+        let mut body = pin!(body);
+        let mut total = 0usize;
+        while let Some(frame) = poll_fn(|cx| body.as_mut().poll_frame(cx)).await {
+            if let Ok(mut data) = frame.map_err(BodyError::Body)?.into_data() {
+                if !self.fits_limit(data.remaining()) {
+                    return Err(BodyError::TooLarge);
+                }
+                while data.has_remaining() {
+                    let count = self.append_partial(data.chunk()).map_err(|_| BodyError::TooLarge)?;
+                    data.advance(count);
+                    total += count;
+                }
+            }
+        }
+        Ok(total)
+    }
+}
@@ -0,0 +1,131 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::Result as IoResult;
+use std::marker::PhantomData;
+use std::num::NonZeroUsize;
+
+use crate::{Primitive, SpareBuffer, SpareCapacity};
+
+/// An *invariant* lifetime that brands the tokens of one branded scope.
+type Brand<'id> = PhantomData<fn(&'id ()) -> &'id ()>;
+
+/// A [**`SpareBuffer`**](crate::SpareBuffer) within a *branded* scope, as
+/// created by [`SpareBuffer::branded()`](crate::SpareBuffer::branded).
+///
+/// The allocation state is tracked by *linear* tokens at compile time: An
+/// [**`IdleToken`**](crate::IdleToken) is required to allocate a "spare"
+/// buffer, which turns it into an
+/// [**`AllocatedToken`**](crate::AllocatedToken), which in turn is required
+/// to commit. Because the tokens can **not** be copied, and
+/// are branded with the lifetime `'id` of their scope, committing without an
+/// allocation, allocating twice, or committing a stale allocation, are all
+/// compile errors rather than logic bugs.
+pub struct BrandedBuffer<'id, 'b, 'a, T, B = Vec<T>>
+where
+    T: Primitive,
+    B: SpareCapacity<T>
+{
+    buffer: &'b mut SpareBuffer<'a, T, B>,
+    _brand: Brand<'id>,
+}
+
+/// A token proving that **no** "spare" buffer is currently allocated in the
+/// branded scope `'id`.
+pub struct IdleToken<'id> {
+    _brand: Brand<'id>,
+}
+
+/// A token proving that a "spare" buffer is currently allocated in the
+/// branded scope `'id`.
+pub struct AllocatedToken<'id> {
+    length: NonZeroUsize,
+    _brand: Brand<'id>,
+}
+
+impl<'id> AllocatedToken<'id> {
+    /// Returns the length of the allocated "spare" buffer.
+    pub fn len(&self) -> NonZeroUsize {
+        self.length
+    }
+}
+
+impl<'a, T, B> SpareBuffer<'a, T, B>
+where
+    T: Primitive,
+    B: SpareCapacity<T>
+{
+    /// Runs `f` within a new *branded* scope.
+    ///
+    /// `f` receives a [**`BrandedBuffer`**](crate::BrandedBuffer), together
+    /// with the *only* [**`IdleToken`**](crate::IdleToken) of the scope.
+    /// Tokens can **not** escape from the scope, nor be used in any other
+    /// scope.
+    pub fn branded<F, R>(&mut self, f: F) -> R
+    where
+        F: for<'id> FnOnce(BrandedBuffer<'id, '_, 'a, T, B>, IdleToken<'id>) -> R
+    {
+        self.guarded(|buffer| f(BrandedBuffer { buffer, _brand: PhantomData }, IdleToken { _brand: PhantomData }))
+    }
+}
+
+impl<'id, 'b, 'a, T, B> BrandedBuffer<'id, 'b, 'a, T, B>
+where
+    T: Primitive,
+    B: SpareCapacity<T>
+{
+    /// Returns the number of "committed" elements in the underlying vector.
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Returns `true` if the underlying vector contains no "committed"
+    /// elements.
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    /// Returns a `&[T]` slice of all "committed" elements in the underlying
+    /// vector.
+    pub fn data(&self) -> &[T] {
+        self.buffer.data()
+    }
+
+    /// Allocates a "spare" buffer of the specified `length`, consuming the
+    /// [**`IdleToken`**](crate::IdleToken).
+    ///
+    /// Returns an [**`AllocatedToken`**](crate::AllocatedToken), and a
+    /// `&mut[T]` slice of exactly `length` elements. See
+    /// [`SpareBuffer::allocate_spare()`](crate::SpareBuffer::allocate_spare)
+    /// for details.
+    pub fn allocate_spare(&mut self, token: IdleToken<'id>, length: NonZeroUsize) -> (AllocatedToken<'id>, &mut [T]) {
+        let IdleToken { _brand } = token;
+        let spare = &mut self.buffer.allocate_spare(length)[..length.get()];
+        (AllocatedToken { length, _brand }, spare)
+    }
+
+    /// Commits the first `additional` elements of the "spare" buffer,
+    /// consuming the [**`AllocatedToken`**](crate::AllocatedToken).
+    ///
+    /// Returns a new [**`IdleToken`**](crate::IdleToken), regardless of
+    /// whether the commit succeeded, together with the result of the commit. See
+    /// [`SpareBuffer::commit()`](crate::SpareBuffer::commit) for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `additional` is greater than the length of the allocation.
+    pub fn commit(&mut self, token: AllocatedToken<'id>, additional: usize) -> (IdleToken<'id>, IoResult<()>) {
+        let AllocatedToken { length, _brand } = token;
+        assert!(additional <= length.get(), "Commit size exceeds the length of the allocation!");
+        (IdleToken { _brand }, self.buffer.commit(additional))
+    }
+
+    /// Discards the "spare" buffer **without** committing any elements,
+    /// consuming the [**`AllocatedToken`**](crate::AllocatedToken).
+    pub fn discard(&mut self, token: AllocatedToken<'id>) -> IdleToken<'id> {
+        let (idle, result) = self.commit(token, 0);
+        debug_assert!(result.is_ok());
+        idle
+    }
+}
@@ -3,37 +3,412 @@
  * This is free and unencumbered software released into the public domain.
  */
 use std::io::{Result as IoResult, Error as IoError, ErrorKind};
+use std::mem::MaybeUninit;
 use std::num::NonZeroUsize;
+use std::ops::{Deref, DerefMut};
 use std::slice::from_raw_parts_mut;
+use std::sync::Arc;
 
-use crate::Primitive;
+use crate::{AllocationError, ChunkPolicy, DynamicLimit, GrowthPolicy, OverflowPolicy, Primitive, RetryPolicy, ShrinkPolicy, SpareCapacity, Stats};
+#[cfg(feature = "test-util")]
+use crate::FaultInjector;
+#[cfg(feature = "sysinfo")]
+use crate::MemoryLimit;
+#[cfg(feature = "paranoid")]
+use crate::paranoid::{self, Snapshot, State};
+use crate::primitive::zeroed;
+use crate::sanitizer::{mark_init, mark_uninit, poison};
+#[cfg(any(unix, windows))]
+use crate::lock::{self, Range};
+use crate::wipe::wipe;
+
+/// Emits an event with the target `spare_buffer`, via `log` and/or via
+/// `tracing`, if the **`log`** and/or the **`tracing`** feature is enabled.
+macro_rules! log_event {
+    ($level:ident, $($arg:tt)+) => {
+        #[cfg(feature = "log")]
+        log::$level!(target: "spare_buffer", $($arg)+);
+        #[cfg(feature = "tracing")]
+        tracing::$level!(target: "spare_buffer", $($arg)+);
+    };
+}
+pub(crate) use log_event;
+
+/// Enters a `tracing` span with the target `spare_buffer`, until the end of
+/// the enclosing scope, if the **`tracing`** feature is enabled.
+macro_rules! enter_span {
+    ($name:literal, $($field:tt)*) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!(target: "spare_buffer", $name, $($field)*).entered();
+    };
+}
+pub(crate) use enter_span;
+
+/// A callback that is invoked with each slice of newly committed elements.
+type Observer<T> = Box<dyn Fn(&[T]) + Send>;
 
 /// A wrapper around [**`Vec<T>`**](std::vec::Vec) that provides access to the
 /// "spare" capacity of the vector as a `&mut[T]` slice.
+///
+/// The underlying storage can be any [**`SpareCapacity`**](crate::SpareCapacity)
+/// backend `B`, which defaults to `Vec<T>`.
 /// 
 /// See [module level documentation](crate) for more information.
-pub struct SpareBuffer<'a, T>
+pub struct SpareBuffer<'a, T, B = Vec<T>>
 where
-    T: Primitive
+    T: Primitive,
+    B: SpareCapacity<T>
 {
-    buffer: &'a mut Vec<T>,
+    buffer: Storage<'a, B>,
     limit: Option<NonZeroUsize>,
+    dynamic_limit: Option<Box<dyn DynamicLimit + Send>>,
+    observer: Option<Observer<T>>,
+    #[cfg(feature = "crc32fast")]
+    crc: Option<crc32fast::Hasher>,
     allocated: bool,
+    uninit: bool,
+    requested: usize,
+    max_allocation: Option<NonZeroUsize>,
+    retry: RetryPolicy,
+    chunk: ChunkPolicy,
+    growth: GrowthPolicy,
+    overflow: OverflowPolicy,
+    shrink: Option<ShrinkPolicy>,
+    idle_commits: usize,
+    consumed: usize,
+    transaction: Option<usize>,
+    stats: Option<Stats>,
+    #[cfg(any(unix, windows))]
+    locked: Option<Range>,
+    spare_init: Option<T>,
+    initialized: usize,
+    #[cfg(feature = "test-util")]
+    faults: Option<FaultInjector>,
+    #[cfg(feature = "sysinfo")]
+    memory_limit: Option<MemoryLimit>,
+    #[cfg(feature = "paranoid")]
+    snapshot: Option<Snapshot>,
+    #[cfg(feature = "paranoid")]
+    exceeded_limit: bool,
 }
 
-impl<'a, T> SpareBuffer<'a, T>
+impl<'a, T, B> SpareBuffer<'a, T, B>
 where
-    T: Primitive
+    T: Primitive,
+    B: SpareCapacity<T>
 {
     /// Creates a new **`SpareBuffer`** from an existing vector.
     /// 
     /// An *optional* `limit` for the length of the vector can be specified.
     /// The [`commit()`](Self::commit) fails, if it would exceed this limit.
-    pub fn from(buffer: &'a mut Vec<T>, limit: Option<NonZeroUsize>) -> Self {
+    pub fn from(buffer: &'a mut B, limit: Option<NonZeroUsize>) -> Self {
+        Self::with_storage(Storage::Borrowed(buffer), limit)
+    }
+
+    /// Creates a new **`SpareBuffer`** with the given storage.
+    fn with_storage(buffer: Storage<'a, B>, limit: Option<NonZeroUsize>) -> Self {
         Self {
             buffer,
             limit,
+            dynamic_limit: None,
+            observer: None,
+            #[cfg(feature = "crc32fast")]
+            crc: None,
             allocated: false,
+            uninit: false,
+            requested: 0,
+            max_allocation: None,
+            retry: RetryPolicy::new(),
+            chunk: ChunkPolicy::default(),
+            growth: GrowthPolicy::default(),
+            overflow: OverflowPolicy::default(),
+            shrink: None,
+            idle_commits: 0,
+            consumed: 0,
+            transaction: None,
+            stats: None,
+            #[cfg(any(unix, windows))]
+            locked: None,
+            spare_init: None,
+            initialized: 0,
+            #[cfg(feature = "test-util")]
+            faults: None,
+            #[cfg(feature = "sysinfo")]
+            memory_limit: None,
+            #[cfg(feature = "paranoid")]
+            snapshot: None,
+            #[cfg(feature = "paranoid")]
+            exceeded_limit: false,
+        }
+    }
+
+    /// Sets the *optional* maximum size of a *single* "spare" buffer.
+    ///
+    /// This is *independent* of the length limit, and bounds the amount of
+    /// memory that any one allocation may request, e.g. to catch *corrupted*
+    /// length prefixes early. See
+    /// [`try_allocate_spare()`](Self::try_allocate_spare) for details.
+    pub fn with_max_allocation(mut self, max_allocation: Option<NonZeroUsize>) -> Self {
+        self.max_allocation = max_allocation;
+        self
+    }
+
+    /// Returns the maximum size of a single "spare" buffer, if a maximum has
+    /// been specified. Otherwise `None` is returned.
+    pub fn max_allocation(&self) -> Option<NonZeroUsize> {
+        self.max_allocation
+    }
+
+    /// Sets a `value` that the requested `length` of *every* newly allocated
+    /// "spare" buffer is initialized with.
+    ///
+    /// This costs a *memset* for each allocation, but guarantees that
+    /// committing up to `length` elements can **never** expose uninitialized
+    /// memory, and that reading the first `length` elements of the "spare"
+    /// buffer is always well-defined.
+    ///
+    /// Elements of the "spare" capacity that have been initialized before,
+    /// e.g. by a previous allocation, are **not** filled again, unless the
+    /// storage of the underlying vector has been reallocated in the meantime.
+    /// They may therefore contain data that was written, but **not**
+    /// committed, or that was removed from the end of the vector.
+    pub fn with_spare_init(mut self, value: T) -> Self {
+        self.spare_init = Some(value);
+        self.initialized = 0;
+        self
+    }
+
+    /// Initializes *every* newly allocated "spare" buffer with the default
+    /// value of `T`. See [`with_spare_init()`](Self::with_spare_init) for
+    /// details.
+    pub fn with_default_init(self) -> Self
+    where
+        T: Default
+    {
+        self.with_spare_init(T::default())
+    }
+
+    /// *Zero-fills* the requested length of every newly allocated "spare"
+    /// buffer, before it is handed out. See
+    /// [`with_spare_init()`](Self::with_spare_init) for details.
+    ///
+    /// This is the *secure* mode for callers that pass the "spare" buffer to
+    /// untrusted code, such as plugins or FFI: an under-filled commit can
+    /// **never** leak stale heap contents. It may, however, expose data of
+    /// the *same* buffer that was written, but **not** committed, as
+    /// elements that have been initialized before are **not** zeroed again.
+    /// Use [`wipe()`](Self::wipe) to clear such data. The all-zero bit
+    /// pattern is a valid value of every [**`Primitive`**](crate::Primitive)
+    /// type.
+    pub fn with_zeroing(self) -> Self {
+        self.with_spare_init(zeroed())
+    }
+
+    /// Returns the value that newly allocated "spare" buffers are initialized
+    /// with, if a value has been set. Otherwise `None` is returned.
+    pub fn spare_init(&self) -> Option<T> {
+        self.spare_init
+    }
+
+    /// Sets the [**`RetryPolicy`**](crate::RetryPolicy) that is used by the
+    /// fill helpers of this **`SpareBuffer`** to handle *transient* errors.
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    /// Returns the [**`RetryPolicy`**](crate::RetryPolicy) of this
+    /// **`SpareBuffer`**.
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.retry
+    }
+
+    /// Sets the [**`ChunkPolicy`**](crate::ChunkPolicy) that controls the
+    /// sizes of the reads issued by the *bulk* fill helpers of this
+    /// **`SpareBuffer`**.
+    pub fn with_chunk_policy(mut self, policy: ChunkPolicy) -> Self {
+        self.chunk = policy;
+        self
+    }
+
+    /// Returns the [**`ChunkPolicy`**](crate::ChunkPolicy) of this
+    /// **`SpareBuffer`**.
+    pub fn chunk_policy(&self) -> ChunkPolicy {
+        self.chunk
+    }
+
+    /// Sets the [**`GrowthPolicy`**](crate::GrowthPolicy) that controls how
+    /// much capacity is reserved, when a new "spare" buffer does **not** fit
+    /// into the "spare" capacity of the underlying vector.
+    pub fn with_growth_policy(mut self, policy: GrowthPolicy) -> Self {
+        self.growth = policy;
+        self
+    }
+
+    /// Returns the [**`GrowthPolicy`**](crate::GrowthPolicy) of this
+    /// **`SpareBuffer`**.
+    pub fn growth_policy(&self) -> GrowthPolicy {
+        self.growth
+    }
+
+    /// Sets the [**`OverflowPolicy`**](crate::OverflowPolicy) that controls
+    /// what a commit does, if it would exceed the length limit.
+    pub fn with_overflow_policy(mut self, policy: OverflowPolicy) -> Self {
+        self.overflow = policy;
+        self
+    }
+
+    /// Returns the [**`OverflowPolicy`**](crate::OverflowPolicy) of this
+    /// **`SpareBuffer`**.
+    pub fn overflow_policy(&self) -> OverflowPolicy {
+        self.overflow
+    }
+
+    /// Sets the [**`ShrinkPolicy`**](crate::ShrinkPolicy) that is used to
+    /// *automatically* release excess "spare" capacity after a commit.
+    ///
+    /// The commit has already happened, when the capacity is released. So,
+    /// if the memory is [locked](Self::lock_memory), but the shrunk storage
+    /// can **not** be locked again, the commit still succeeds, and the memory
+    /// is no longer locked afterwards, as reported by
+    /// [`is_memory_locked()`](Self::is_memory_locked).
+    pub fn with_shrink_policy(mut self, policy: ShrinkPolicy) -> Self {
+        self.shrink = Some(policy);
+        self.idle_commits = 0;
+        self
+    }
+
+    /// Returns the [**`ShrinkPolicy`**](crate::ShrinkPolicy) of this
+    /// **`SpareBuffer`**, if a policy has been set. Otherwise `None` is
+    /// returned.
+    pub fn shrink_policy(&self) -> Option<ShrinkPolicy> {
+        self.shrink
+    }
+
+    /// Installs a [**`FaultInjector`**](crate::FaultInjector) that makes
+    /// allocations of "spare" buffers fail, as scripted.
+    #[cfg(feature = "test-util")]
+    pub fn with_fault_injector(mut self, injector: FaultInjector) -> Self {
+        self.faults = Some(injector);
+        self
+    }
+
+    /// Returns the [**`FaultInjector`**](crate::FaultInjector) of this
+    /// **`SpareBuffer`**, if one has been installed.
+    #[cfg(feature = "test-util")]
+    pub fn fault_injector(&self) -> Option<&FaultInjector> {
+        self.faults.as_ref()
+    }
+
+    /// Sets a [**`MemoryLimit`**](crate::MemoryLimit), which limits the
+    /// length of the underlying vector *in addition* to the fixed length
+    /// limit, based on the currently available system memory.
+    #[cfg(feature = "sysinfo")]
+    pub fn with_memory_limit(mut self, mut memory_limit: MemoryLimit) -> Self {
+        memory_limit.refresh();
+        self.memory_limit = Some(memory_limit);
+        self
+    }
+
+    /// Returns the [**`MemoryLimit`**](crate::MemoryLimit) of this
+    /// **`SpareBuffer`**, if one has been set.
+    #[cfg(feature = "sysinfo")]
+    pub fn memory_limit(&self) -> Option<&MemoryLimit> {
+        self.memory_limit.as_ref()
+    }
+
+    /// Sets a [**`DynamicLimit`**](crate::DynamicLimit), which is consulted
+    /// on every commit, *in addition* to the fixed length limit, e.g. a
+    /// closure that checks a global memory budget.
+    pub fn with_dynamic_limit<L>(mut self, limit: L) -> Self
+    where
+        L: DynamicLimit + Send + 'static
+    {
+        self.dynamic_limit = Some(Box::new(limit));
+        self
+    }
+
+    /// Returns the [**`DynamicLimit`**](crate::DynamicLimit) of this
+    /// **`SpareBuffer`**, if one has been set.
+    pub fn dynamic_limit(&self) -> Option<&(dyn DynamicLimit + Send)> {
+        self.dynamic_limit.as_deref()
+    }
+
+    /// Sets a *commit observer*, i.e. a callback that is invoked with each
+    /// slice of newly committed elements, e.g. to update metrics or a
+    /// progress bar, or to feed an incremental parser. It is **not** invoked
+    /// for commits of zero elements.
+    ///
+    /// There is **no** built-in digest integration, apart from the CRC-32 of
+    /// the **`crc32fast`** feature. A hasher that is provided by the caller
+    /// can be fed with the committed data, in order to compute a digest while
+    /// accumulating, **without** a second pass. Since the observer is a `Fn`,
+    /// the hasher needs to be shared, e.g. via an `Arc<Mutex<_>>`, so that it
+    /// can be finalized once the data is complete.
+    pub fn with_commit_observer<F>(mut self, observer: F) -> Self
+    where
+        F: Fn(&[T]) + Send + 'static
+    {
+        self.observer = Some(Box::new(observer));
+        self
+    }
+
+    /// Enables a running CRC-32 checksum, which is updated with the bytes of
+    /// each slice of newly committed elements, from now on. Elements that
+    /// have been committed before, or elements that are removed later, do
+    /// **not** affect the checksum.
+    ///
+    /// Only available with the **`crc32fast`** feature.
+    #[cfg(feature = "crc32fast")]
+    pub fn with_crc32(mut self) -> Self {
+        self.crc = Some(crc32fast::Hasher::new());
+        self
+    }
+
+    /// Returns the CRC-32 checksum of all data that has been committed so
+    /// far, if enabled by [`with_crc32()`](Self::with_crc32). Otherwise
+    /// `None` is returned.
+    ///
+    /// Only available with the **`crc32fast`** feature.
+    #[cfg(feature = "crc32fast")]
+    pub fn checksum(&self) -> Option<u32> {
+        self.crc.clone().map(crc32fast::Hasher::finalize)
+    }
+
+    /// Enables the collection of [**`Stats`**](crate::Stats), such as the
+    /// number of allocations and reallocations, the number of committed
+    /// elements, and the peak capacity, from now on.
+    pub fn with_stats(mut self) -> Self {
+        self.stats = Some(Stats::new(self.buffer.capacity()));
+        self
+    }
+
+    /// Returns the [**`Stats`**](crate::Stats) of this **`SpareBuffer`**, if
+    /// enabled by [`with_stats()`](Self::with_stats). Otherwise `None` is
+    /// returned.
+    pub fn stats(&self) -> Option<&Stats> {
+        self.stats.as_ref()
+    }
+
+    /// Invokes the commit observer, if any, with `count` elements of the
+    /// underlying vector, starting at `start`, and updates the checksum and
+    /// the statistics.
+    fn notify(&mut self, start: usize, count: usize) {
+        if count == 0 {
+            return;
+        }
+        if let Some(stats) = self.stats.as_mut() {
+            stats.track_commit(count);
+        }
+        let data = &self.buffer.as_slice()[start..start + count];
+        if let Some(observer) = self.observer.as_deref() {
+            observer(data);
+        }
+        #[cfg(feature = "crc32fast")]
+        if let Some(crc) = self.crc.as_mut() {
+            crc.update(unsafe {
+                std::slice::from_raw_parts(data.as_ptr() as *const u8, std::mem::size_of_val(data))
+            });
         }
     }
 
@@ -50,30 +425,197 @@ where
         self.buffer.is_empty()
     }
 
+    /// Returns the *total* capacity of the underlying vector, including the
+    /// "spare" capacity. This is equivalent to
+    /// [`Vec::capacity()`](std::vec::Vec::capacity).
+    pub fn capacity(&self) -> usize {
+        self.buffer.capacity()
+    }
+
+    /// Returns the number of elements of "spare" capacity that are currently
+    /// available at the end of the underlying vector, i.e. the capacity minus
+    /// the length.
+    ///
+    /// This may exceed the length of the most recently allocated "spare"
+    /// buffer, if more capacity was reserved. Up to this many elements can be
+    /// committed, subject to the length limit, without another reservation.
+    pub fn spare_len(&self) -> usize {
+        self.buffer.capacity() - self.buffer.len()
+    }
+
     /// Returns the length limit, if a limit has been specified. Otherwise
     /// `None` is returned.
     pub fn limit(&self) -> Option<NonZeroUsize> {
         self.limit
     }
 
+    /// Changes the length limit at runtime, e.g. to tighten or relax the cap
+    /// after authentication or negotiation. A limit of `None` removes the
+    /// limit.
+    ///
+    /// The current "spare" buffer, if any, remains valid, but the new limit
+    /// applies to the next commit.
+    ///
+    /// # Errors
+    ///
+    /// Fails with an error of kind [`ErrorKind::InvalidInput`], if the new
+    /// limit is less than the current length of the underlying vector. The
+    /// limit is **not** changed in that case.
+    pub fn set_limit(&mut self, limit: Option<NonZeroUsize>) -> IoResult<()> {
+        if limit.is_some_and(|limit| limit.get() < self.buffer.len()) {
+            return Err(IoError::new(ErrorKind::InvalidInput, "The new limit is less than the current length!"));
+        }
+        self.limit = limit;
+        self.check_invariants("set_limit");
+        Ok(())
+    }
+
+    /// Removes the length limit. This is equivalent to `set_limit(None)`,
+    /// which can **not** fail.
+    pub fn clear_limit(&mut self) {
+        self.limit = None;
+        self.check_invariants("clear_limit");
+    }
+
+    /// Returns the number of elements that can still be committed without
+    /// exceeding the length limit, i.e. the limit minus the current length,
+    /// so that the next read can be sized up front. Returns `None`, if
+    /// neither a length limit nor a memory limit has been specified.
+    ///
+    /// The memory limit, if any, is taken into account, as of its last
+    /// evaluation, so that `Some` is returned, if *only* a memory limit has
+    /// been specified. The dynamic limit, if any, is **not** taken into
+    /// account.
+    pub fn remaining(&self) -> Option<usize> {
+        match self.max_length() {
+            usize::MAX => None,
+            max_length => Some(max_length.saturating_sub(self.buffer.len())),
+        }
+    }
+
+    /// Returns `true` if the length limit has been reached, i.e. nothing can
+    /// be committed anymore. Always returns `false`, if neither a length limit
+    /// nor a memory limit has been specified. See
+    /// [`remaining()`](Self::remaining) for details.
+    pub fn is_full(&self) -> bool {
+        self.remaining() == Some(0)
+    }
+
     /// Returns a `&[T]` slice of all "committed" elements in the underlying
     /// vector. This is equivalent to
     /// [`Vec::as_slice()`](std::vec::Vec::as_slice).
     pub fn data(&self) -> &[T] {
-        &self.buffer[..]
+        self.buffer.as_slice()
+    }
+
+    /// Returns the number of elements that can still be appended to the
+    /// underlying vector without exceeding the length limit.
+    pub(crate) fn headroom(&self) -> usize {
+        self.max_length().saturating_sub(self.buffer.len())
+    }
+
+    /// Returns the *effective* maximum length of the underlying vector, i.e.
+    /// the length limit, but also bounded by the memory limit, if any.
+    fn max_length(&self) -> usize {
+        let limit = self.limit.map_or(usize::MAX, NonZeroUsize::get);
+        #[cfg(feature = "sysinfo")]
+        if let Some(memory_limit) = &self.memory_limit {
+            return limit.min(memory_limit.max_elements::<T>());
+        }
+        limit
+    }
+
+    /// Re-evaluates the memory limit, if any, once its interval has elapsed.
+    #[cfg(feature = "sysinfo")]
+    fn refresh_limit(&mut self) {
+        if let Some(memory_limit) = &mut self.memory_limit {
+            memory_limit.refresh();
+        }
+    }
+
+    #[cfg(not(feature = "sysinfo"))]
+    #[inline(always)]
+    fn refresh_limit(&mut self) {}
+
+    /// Returns the number of "committed" elements that have been *consumed*
+    /// by reading from this **`SpareBuffer`**, e.g. via
+    /// [`BufRead`](std::io::BufRead).
+    pub fn consumed(&self) -> usize {
+        self.consumed
+    }
+
+    /// Returns a `&[T]` slice of all "committed" elements that have **not**
+    /// been consumed yet.
+    pub fn unconsumed(&self) -> &[T] {
+        &self.buffer.as_slice()[self.consumed..]
+    }
+
+    /// Marks the first `amount` unconsumed elements as consumed. The amount
+    /// is clamped to the number of unconsumed elements.
+    pub fn consume(&mut self, amount: usize) {
+        self.consumed = self.consumed.saturating_add(amount).min(self.buffer.len());
+        self.check_invariants("consume");
+    }
+
+    /// Removes all consumed elements from the front of the underlying vector,
+    /// moving the unconsumed elements to the start.
+    pub fn compact(&mut self) {
+        let consumed = std::mem::take(&mut self.consumed);
+        self.remove_front(consumed);
+        self.check_invariants("compact");
+    }
+
+    /// Removes the first `count` "committed" elements from the underlying
+    /// vector, moving the remaining elements to the start. The consumed count
+    /// is reduced accordingly, and the current "spare" buffer is invalidated.
+    pub(crate) fn drain_front(&mut self, count: usize) {
+        self.release();
+        self.remove_front(count);
+        self.consumed = self.consumed.saturating_sub(count);
+        self.check_invariants("drain_front");
+    }
+
+    /// Removes the first `count` "committed" elements from the underlying
+    /// vector, moving the remaining elements to the start.
+    fn remove_front(&mut self, count: usize) {
+        self.transaction = self.transaction.map(|start| start.saturating_sub(count));
+        let remaining = self.buffer.len() - count;
+        unsafe {
+            let ptr = self.buffer.as_mut_ptr();
+            std::ptr::copy(ptr.add(count), ptr, remaining);
+            self.buffer.set_len(remaining);
+        }
+    }
+
+    /// Returns `true` if `additional` more elements can be appended to the
+    /// underlying vector without exceeding the length limit.
+    pub(crate) fn fits_limit(&self, additional: usize) -> bool {
+        additional <= self.headroom() && self.permits(additional)
+    }
+
+    /// Returns `true` if `additional` more elements are permitted by the
+    /// dynamic limit, if any.
+    fn permits(&self, additional: usize) -> bool {
+        self.dynamic_limit.as_ref().is_none_or(|limit| limit.permits(self.buffer.len(), additional))
     }
 
     /// Allocates a "spare" buffer of the specified `length`.
     /// 
     /// Reserves capacity for *at least* `length` additional elements in the
     /// underlying vector. May reserve more space to speculatively avoid
-    /// frequent reallocations. Does nothing, if the unused "spare" capacity of
-    /// the underlying vector is already sufficient.
+    /// frequent reallocations, as controlled by the
+    /// [growth policy](Self::with_growth_policy). Does nothing, if the unused
+    /// "spare" capacity of the underlying vector is already sufficient.
     /// 
     /// Returns a `&mut[T]` slice which allows the caller to access the
     /// allocated "spare" buffer. No guarantees are provided about the
-    /// *initial* contents of the buffer! It is recommended that the caller
-    /// only *writes* data to the slice instead of reading its contents.
+    /// *initial* contents of the buffer, unless a value for initialization
+    /// has been set by [`with_spare_init()`](Self::with_spare_init)! It is
+    /// recommended that the caller only *writes* data to the slice instead of
+    /// reading its contents. In debug builds, or with the **`paranoid`**
+    /// feature, the first `length` elements of the buffer are filled with
+    /// `0xCD` bytes, so that committing elements that have **not** been
+    /// written is easy to spot.
     /// 
     /// The returned `&mut[T]` slice can be passed to
     /// [`Read::read()`](std::io::Read::read) or similar I/O routines.
@@ -81,12 +623,487 @@ where
     /// The "spare" buffer is **not** considered to be a valid part of the
     /// underlying vector, until the [`commit()`](Self::commit) function is
     /// called eventually.
+    ///
+    /// Not available with the **`safe-only`** feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `length` exceeds the maximum allocation size, as specified
+    /// by [`with_max_allocation()`](Self::with_max_allocation), or if the
+    /// capacity can **not** be reserved.
+    #[cfg(not(feature = "safe-only"))]
     pub fn allocate_spare(&mut self, length: NonZeroUsize) -> &mut[T] {
-        self.buffer.reserve(length.get());
-        self.allocated = true;
-        let spare = self.buffer.spare_capacity_mut();
+        self.spare_mut(length)
+    }
+
+    /// Allocates a "spare" buffer of the specified `length`, but clamped to
+    /// the remaining headroom, i.e. the number of elements that can still be
+    /// committed without exceeding the length limit.
+    ///
+    /// Returns a `&mut[T]` slice of *exactly* the clamped length, so that a
+    /// read loop naturally stops at the limit, instead of reading data that
+    /// can **never** be committed. Returns `None`, if the length limit has
+    /// been reached, in which case **no** "spare" buffer is allocated.
+    /// Otherwise, this works like [`allocate_spare()`](Self::allocate_spare).
+    ///
+    /// Not available with the **`safe-only`** feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the clamped length exceeds the maximum allocation size, as
+    /// specified by [`with_max_allocation()`](Self::with_max_allocation), or
+    /// if the capacity can **not** be reserved.
+    #[cfg(not(feature = "safe-only"))]
+    pub fn allocate_spare_clamped(&mut self, length: NonZeroUsize) -> Option<&mut[T]> {
+        self.refresh_limit();
+        let clamped = NonZeroUsize::new(length.get().min(self.headroom()))?;
+        Some(&mut self.spare_mut(clamped)[..clamped.get()])
+    }
+
+    /// Allocates a "spare" buffer of the specified `length`, exactly like
+    /// [`allocate_spare()`](Self::allocate_spare) does, but fails instead of
+    /// panicking, if `length` exceeds the maximum allocation size, or if the
+    /// capacity can **not** be reserved.
+    ///
+    /// The capacity is reserved via
+    /// [`Vec::try_reserve()`](std::vec::Vec::try_reserve), so that servers
+    /// handling *untrusted* sizes can degrade gracefully, instead of aborting
+    /// the process when running out of memory.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`AllocationError::ExceedsMaxAllocation`](crate::AllocationError::ExceedsMaxAllocation),
+    /// if a maximum allocation size has been specified, and `length` exceeds
+    /// it. Nothing is reserved in that case. Fails with
+    /// [`AllocationError::Reserve`](crate::AllocationError::Reserve), if the
+    /// capacity can **not** be reserved.
+    #[cfg(not(feature = "safe-only"))]
+    pub fn try_allocate_spare(&mut self, length: NonZeroUsize) -> Result<&mut[T], AllocationError> {
+        self.try_spare_mut(length)
+    }
+
+    /// Allocates a "spare" buffer of the specified `length`, for *internal*
+    /// use. See [`allocate_spare()`](Self::allocate_spare) for details.
+    pub(crate) fn spare_mut(&mut self, length: NonZeroUsize) -> &mut[T] {
+        match self.try_spare_mut(length) {
+            Ok(spare) => spare,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Allocates a "spare" buffer of the specified `length`, for *internal*
+    /// use. See [`try_allocate_spare()`](Self::try_allocate_spare) for details.
+    pub(crate) fn try_spare_mut(&mut self, length: NonZeroUsize) -> Result<&mut[T], AllocationError> {
+        Ok(self.try_split_at_spare_mut(length)?.1)
+    }
+
+    /// Allocates a "spare" buffer of the specified `length`, exactly like
+    /// [`allocate_spare()`](Self::allocate_spare) does, but returns a
+    /// `&mut[T]` slice of all "committed" elements *and* a `&mut[T]` slice of
+    /// the "spare" buffer at the same time.
+    ///
+    /// The two slices are *disjoint*, so that, for example, data can be
+    /// transformed from the committed part of the vector into the "spare"
+    /// buffer. The "spare" buffer can be [committed](Self::commit), once both
+    /// slices have gone out of scope.
+    ///
+    /// Not available with the **`safe-only`** feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `length` exceeds the maximum allocation size, as specified
+    /// by [`with_max_allocation()`](Self::with_max_allocation), or if the
+    /// capacity can **not** be reserved.
+    #[cfg(not(feature = "safe-only"))]
+    pub fn split_at_spare_mut(&mut self, length: NonZeroUsize) -> (&mut[T], &mut[T]) {
+        match self.try_split_at_spare_mut(length) {
+            Ok(slices) => slices,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// The same as [`split_at_spare_mut()`](Self::split_at_spare_mut), but
+    /// fails if `length` exceeds the maximum allocation size.
+    pub(crate) fn try_split_at_spare_mut(&mut self, length: NonZeroUsize) -> Result<(&mut[T], &mut[T]), AllocationError> {
+        self.reserve_spare(length)?;
+        let committed = self.buffer.len();
+        let spare = self.buffer.capacity() - committed;
+        let ptr = self.buffer.as_mut_ptr();
         unsafe {
-            from_raw_parts_mut(spare.as_mut_ptr() as *mut T, spare.len())
+            Ok((from_raw_parts_mut(ptr, committed), from_raw_parts_mut(ptr.add(committed), spare)))
+        }
+    }
+
+    /// Allocates a "spare" buffer of the specified `length`, exactly like
+    /// [`allocate_spare()`](Self::allocate_spare) does, but returns it as a
+    /// `&mut[MaybeUninit<T>]` slice.
+    ///
+    /// This is the *safe* way to access the "spare" buffer: the elements can
+    /// be written, e.g. via [`MaybeUninit::write()`], but **not** be read,
+    /// before they have been initialized. Available with the **`safe-only`**
+    /// feature.
+    ///
+    /// Because a `MaybeUninit<T>` element can also be *de-initialized* by
+    /// safe code, a "spare" buffer that was allocated this way can **not** be
+    /// committed by [`commit()`](Self::commit). Use
+    /// [`commit_written()`](Self::commit_written) instead, in order to commit
+    /// the written elements without any `unsafe` code.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `length` exceeds the maximum allocation size, as specified
+    /// by [`with_max_allocation()`](Self::with_max_allocation), or if the
+    /// capacity can **not** be reserved.
+    pub fn allocate_spare_uninit(&mut self, length: NonZeroUsize) -> &mut[MaybeUninit<T>] {
+        match self.reserve_spare(length) {
+            Ok(_) => {
+                self.uninit = true;
+                self.buffer.spare_capacity_mut()
+            },
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Allocates a "spare" buffer of the specified `length`, and lets `f`
+    /// initialize it, as a `&mut[MaybeUninit<T>]` slice. Then commits the
+    /// elements that `f` *returns*.
+    ///
+    /// `f` returns the initialized *prefix* of the "spare" buffer, as a
+    /// `&[T]` slice, e.g. as returned by
+    /// [`write_copy_of_slice()`](slice::write_copy_of_slice). Because a
+    /// `&[T]` slice can only be obtained for initialized elements,
+    /// uninitialized memory can **never** be committed this way, and **no**
+    /// `unsafe` code is required.
+    ///
+    /// Returns the number of elements that have been committed. If `f`
+    /// panics, the "spare" buffer is discarded.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`AllocationError::ExceedsMaxAllocation`](crate::AllocationError::ExceedsMaxAllocation),
+    /// converted to an error of kind [`ErrorKind::OutOfMemory`], if `length`
+    /// exceeds the maximum allocation size, or with
+    /// [`AllocationError::Reserve`](crate::AllocationError::Reserve), if the
+    /// capacity can **not** be reserved. Also fails with the same errors as
+    /// [`commit()`](Self::commit).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slice returned by `f` is **not** a prefix of the "spare"
+    /// buffer.
+    pub fn commit_written<F>(&mut self, length: NonZeroUsize, f: F) -> IoResult<usize>
+    where
+        F: for<'s> FnOnce(&'s mut [MaybeUninit<T>]) -> &'s [T]
+    {
+        self.reserve_spare(length)?;
+        let count = self.guarded(|buffer| {
+            let spare = buffer.buffer.spare_capacity_mut();
+            let start = spare.as_ptr() as *const T;
+            let capacity = spare.len();
+            let written = f(spare);
+            assert!(written.as_ptr() == start && written.len() <= capacity, "The written slice is not a prefix of the spare buffer!");
+            written.len()
+        });
+        self.initialized = self.initialized.max(self.buffer.len() + count);
+        self.commit(count).map(|_| count)
+    }
+
+    /// Performs the allocation of a "spare" buffer of the specified `length`,
+    /// i.e. checks the maximum allocation size, reserves the capacity, and
+    /// initializes or annotates the "spare" capacity.
+    fn reserve_spare(&mut self, length: NonZeroUsize) -> Result<(), AllocationError> {
+        self.check_invariants("allocate_spare");
+        self.refresh_limit();
+        if let Some(max_allocation) = self.max_allocation.filter(|max_allocation| length > *max_allocation) {
+            log_event!(debug, "Allocation of {} elements exceeds the maximum of {}", length, max_allocation);
+            return Err(AllocationError::ExceedsMaxAllocation { requested: length, max_allocation });
+        }
+        #[cfg(feature = "test-util")]
+        if self.faults.as_mut().is_some_and(FaultInjector::next_fails) {
+            return Err(AllocationError::Injected);
+        }
+        self.grow(length.get())?;
+        self.set_allocated(true);
+        self.uninit = false;
+        self.requested = length.get();
+        if let Some(stats) = self.stats.as_mut() {
+            stats.track_allocation();
+        }
+        log_event!(trace, "Allocated a spare buffer of {} elements, capacity is {}", length, self.buffer.capacity());
+        #[cfg(feature = "paranoid")]
+        {
+            self.snapshot = Some(Snapshot {
+                address: self.tracked_address(),
+                length: self.buffer.len(),
+                capacity: self.buffer.capacity(),
+                requested: length,
+            });
+        }
+        self.check_invariants("allocate_spare");
+        #[cfg(feature = "safe-only")]
+        let spare_init = self.spare_init.or(Some(zeroed()));
+        #[cfg(not(feature = "safe-only"))]
+        let spare_init = self.spare_init;
+        match spare_init {
+            Some(value) => {
+                let committed = self.buffer.len();
+                let end = committed + length.get();
+                let start = self.initialized.clamp(committed, end);
+                T::fill_spare(&mut self.buffer.spare_capacity_mut()[start - committed..length.get()], value);
+                self.initialized = self.initialized.max(end);
+            },
+            None => {
+                let spare = &mut self.buffer.spare_capacity_mut()[..length.get()];
+                poison(spare.as_mut_ptr(), spare.len());
+                mark_uninit(spare.as_ptr(), spare.len());
+            },
+        }
+        Ok(())
+    }
+
+    /// Reserves capacity for (at least) `additional` more elements, as
+    /// controlled by the growth policy, unless the "spare" capacity is
+    /// already sufficient.
+    fn grow(&mut self, additional: usize) -> Result<(), AllocationError> {
+        let length = self.buffer.len();
+        if self.buffer.capacity() - length >= additional {
+            return Ok(());
+        }
+        let storage = self.storage_range();
+        match self.growth.capacity(length.saturating_add(additional), std::mem::size_of::<T>()) {
+            Some(capacity) => self.buffer.try_reserve_exact(capacity - length),
+            None => self.buffer.try_reserve(additional),
+        }.map_err(AllocationError::Reserve)?;
+        self.track_storage(storage);
+        self.relock().map_err(|error| AllocationError::Lock(error.kind()))
+    }
+
+    /// Returns `true` if the storage is *owned*, and its elements are stored
+    /// [inline](crate::SpareCapacity::is_inline), so that they move together
+    /// with the **`SpareBuffer`** itself.
+    #[cfg(any(unix, windows, feature = "paranoid"))]
+    fn is_movable(&self) -> bool {
+        matches!(self.buffer, Storage::Owned(_)) && self.buffer.is_inline()
+    }
+
+    /// Returns the address of the storage of the underlying vector, as
+    /// tracked by the **`paranoid`** checks, or `0`, if the storage is
+    /// [movable](Self::is_movable).
+    #[cfg(feature = "paranoid")]
+    fn tracked_address(&self) -> usize {
+        if self.is_movable() {
+            return 0;
+        }
+        self.buffer.as_ptr() as usize
+    }
+
+    /// Returns the range of memory of the *whole* storage of the underlying
+    /// vector, as its address and its size in bytes.
+    fn storage_range(&self) -> (usize, usize) {
+        (self.buffer.as_ptr() as usize, self.buffer.capacity() * std::mem::size_of::<T>())
+    }
+
+    /// Updates the statistics, if any, after the storage of the underlying
+    /// vector *may* have been reallocated, given its `previous` range. The
+    /// initialized part of the "spare" capacity is forgotten, if so.
+    fn track_storage(&mut self, previous: (usize, usize)) {
+        let reallocated = self.storage_range() != previous;
+        if reallocated {
+            self.initialized = 0;
+            log_event!(debug, "Reallocated the storage, capacity is {}", self.buffer.capacity());
+        }
+        if let Some(stats) = self.stats.as_mut() {
+            stats.track_storage(reallocated, self.buffer.capacity());
+        }
+    }
+
+    /// Updates the locked range of memory, also in the owned storage, if any,
+    /// so that it is unlocked when the storage is dropped.
+    #[cfg(any(unix, windows))]
+    fn set_locked(&mut self, range: Option<Range>) {
+        self.locked = range;
+        if let Storage::Owned(owned) = &mut self.buffer {
+            owned.locked = range;
+        }
+    }
+
+    /// Renews the lock, if the memory is locked, but the storage of the
+    /// underlying vector has been reallocated.
+    #[cfg(any(unix, windows))]
+    fn relock(&mut self) -> IoResult<()> {
+        if let Some(range) = self.locked {
+            let current = self.storage_range();
+            if current != range {
+                lock::unlock(range);
+                self.set_locked(None);
+                lock::lock(current)?;
+                self.set_locked(Some(current));
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    #[inline(always)]
+    fn relock(&mut self) -> IoResult<()> {
+        Ok(())
+    }
+
+    /// *Locks* the whole storage of the underlying vector into RAM, via
+    /// `mlock(2)` on Unix, or `VirtualLock()` on Windows, so that secrets are
+    /// **not** written to the swap file.
+    ///
+    /// The lock follows the storage: whenever the **`SpareBuffer`** itself
+    /// reallocates the underlying vector, e.g. to allocate a "spare" buffer,
+    /// the new storage is locked, and the old storage is unlocked. Note that
+    /// locking is *page-granular*, and that the operating system may limit
+    /// the amount of memory that can be locked.
+    ///
+    /// The storage of a [**`SpareVec`**](crate::SpareVec) is unlocked, when it
+    /// is dropped. A *borrowed* vector **must** be unlocked explicitly, via
+    /// [`unlock_memory()`](Self::unlock_memory).
+    ///
+    /// Only available on Unix and Windows.
+    ///
+    /// # Errors
+    ///
+    /// Fails with an error of kind [`ErrorKind::Unsupported`], if the storage
+    /// of a **`SpareVec`** is stored [inline](crate::SpareCapacity::is_inline),
+    /// because it moves together with the **`SpareVec`**. Errors returned by
+    /// the operating system are passed through. Once the memory is locked,
+    /// functions that reallocate the underlying vector fail with
+    /// [`AllocationError::Lock`](crate::AllocationError::Lock), if the new
+    /// storage can **not** be locked.
+    #[cfg(any(unix, windows))]
+    pub fn lock_memory(&mut self) -> IoResult<()> {
+        if self.is_movable() {
+            return Err(IoError::new(ErrorKind::Unsupported, "The inline storage of an owned buffer can not be locked!"));
+        }
+        if self.locked.is_none() {
+            let range = self.storage_range();
+            lock::lock(range)?;
+            self.set_locked(Some(range));
+        }
+        Ok(())
+    }
+
+    /// Unlocks the memory that was locked by
+    /// [`lock_memory()`](Self::lock_memory), if any.
+    ///
+    /// Only available on Unix and Windows.
+    #[cfg(any(unix, windows))]
+    pub fn unlock_memory(&mut self) {
+        if let Some(range) = self.locked {
+            lock::unlock(range);
+            self.set_locked(None);
+        }
+    }
+
+    /// Returns `true` if the memory is locked, as requested by
+    /// [`lock_memory()`](Self::lock_memory).
+    ///
+    /// Only available on Unix and Windows.
+    #[cfg(any(unix, windows))]
+    pub fn is_memory_locked(&self) -> bool {
+        self.locked.is_some()
+    }
+
+    /// Runs `f`, and *discards* the current "spare" buffer, if `f` panics.
+    ///
+    /// This ensures that a panic in user code, while a "spare" buffer is
+    /// allocated, can **not** leave a half-filled "spare" buffer behind that
+    /// could be committed later. After a panic has been caught, a new "spare"
+    /// buffer must be allocated, before anything can be committed.
+    pub(crate) fn guarded<F, R>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&mut Self) -> R
+    {
+        let guard = UnwindGuard(self);
+        f(&mut *guard.0)
+    }
+
+    /// Discards the current "spare" buffer, if the thread is panicking.
+    pub(crate) fn discard_if_panicking(&mut self) {
+        if std::thread::panicking() {
+            self.release();
+        }
+    }
+
+    /// Invalidates the current "spare" buffer. Returns `true`, if a "spare"
+    /// buffer was allocated.
+    fn release(&mut self) -> bool {
+        #[cfg(feature = "paranoid")]
+        {
+            self.snapshot = None;
+        }
+        self.set_allocated(false)
+    }
+
+    /// Updates the allocation state, also in the owned storage, if any, so
+    /// that an outstanding "spare" buffer is reported when the storage is
+    /// dropped. Returns the previous state.
+    fn set_allocated(&mut self, allocated: bool) -> bool {
+        #[cfg(any(feature = "log", feature = "tracing"))]
+        if let Storage::Owned(owned) = &mut self.buffer {
+            owned.allocated = allocated;
+        }
+        std::mem::replace(&mut self.allocated, allocated)
+    }
+
+    /// Validates *all* internal invariants, i.e. the allocation state, the
+    /// relations between length, capacity, limit and consumed count, as well
+    /// as the identity of the underlying vector, while a "spare" buffer is
+    /// allocated. On a violation, the process is *aborted* with detailed
+    /// diagnostics.
+    ///
+    /// Only performs any checks with the **`paranoid`** feature.
+    #[cfg(feature = "paranoid")]
+    fn check_invariants(&self, operation: &str) {
+        paranoid::check(operation, &State {
+            address: self.tracked_address(),
+            length: self.buffer.len(),
+            capacity: self.buffer.capacity(),
+            limit: self.limit,
+            max_allocation: self.max_allocation,
+            consumed: self.consumed,
+            allocated: self.allocated,
+            snapshot: self.snapshot,
+            exceeded_limit: self.exceeded_limit,
+        });
+    }
+
+    #[cfg(not(feature = "paranoid"))]
+    #[inline(always)]
+    fn check_invariants(&self, _operation: &str) {}
+
+    /// Returns the *current* "spare" capacity of the underlying vector, as a
+    /// `&mut[MaybeUninit<T>]` slice, **without** reserving any capacity.
+    pub(crate) fn spare_uninit(&mut self) -> &mut [MaybeUninit<T>] {
+        self.buffer.spare_capacity_mut()
+    }
+
+    /// Returns a pointer to the start of the "spare" capacity of the
+    /// underlying vector.
+    pub(crate) fn spare_ptr(&self) -> *const T {
+        unsafe {
+            self.buffer.as_ptr().add(self.buffer.len())
+        }
+    }
+
+    /// Appends as many elements of `data` as fit into the remaining headroom,
+    /// and the maximum allocation size, by copying them into a "spare"
+    /// buffer. Returns the number of elements that have been committed.
+    pub(crate) fn append_partial(&mut self, data: &[T]) -> IoResult<usize> {
+        let count = data.len()
+            .min(self.headroom())
+            .min(self.max_allocation.map_or(usize::MAX, NonZeroUsize::get));
+        match NonZeroUsize::new(count) {
+            Some(length) => {
+                self.spare_mut(length)[..count].copy_from_slice(&data[..count]);
+                self.commit(count).map(|_| count)
+            },
+            None if data.is_empty() => Ok(0),
+            None => Err(IoError::new(ErrorKind::OutOfMemory, "The buffer has reached the specified limit!")),
         }
     }
 
@@ -106,43 +1123,810 @@ where
     /// "spare" buffer must be [allocated](Self::allocate_spare) in order to
     /// append more data!
     ///
+    /// If a [shrink policy](Self::with_shrink_policy) has been set, excess
+    /// "spare" capacity may be released after the commit.
+    ///
     /// # Errors
     /// 
     /// If a length limit has been specified, then this function will fail, if
     /// adding `additional` more elements to the underlying vector would cause
-    /// its total length to exceed the specified limit. Otherwise, the function
-    /// always returns `Ok(())`.
+    /// its total length to exceed the specified limit, unless the
+    /// [overflow policy](Self::with_overflow_policy) permits committing as
+    /// many elements as fit. It also fails, if the commit is rejected by the
+    /// [dynamic limit](Self::with_dynamic_limit), if any, unless the policy
+    /// is `Saturate`. Otherwise, the function always returns `Ok(())`.
     ///
     /// # Panics
     /// 
     /// Panics if `additional` is greater than the available "spare" capacity,
-    /// or if **no** "spare" buffer was allocated before!
+    /// or if **no** "spare" buffer was allocated before! Also panics, if
+    /// `additional` is **not** zero, but the "spare" buffer was allocated by
+    /// [`allocate_spare_uninit()`](Self::allocate_spare_uninit).
     /// 
     /// A panic may also occur, if the new length would overflow `usize::MAX`.
     pub fn commit(&mut self, additional: usize) -> IoResult<()> {
-        assert!(std::mem::replace(&mut self.allocated, false), "No spare buffer allocated!");
+        self.commit_counted(additional).map(|_| ())
+    }
+
+    /// Commits the first `additional` elements of the "spare" buffer, exactly
+    /// like [`commit()`](Self::commit) does, but returns the number of
+    /// elements that have *actually* been committed.
+    ///
+    /// The count is less than `additional`, if the commit was truncated at
+    /// the length limit, as permitted by the
+    /// [overflow policy](Self::with_overflow_policy).
+    ///
+    /// # Errors
+    ///
+    /// Fails with the same errors as [`commit()`](Self::commit).
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`commit()`](Self::commit).
+    pub fn commit_counted(&mut self, additional: usize) -> IoResult<usize> {
+        self.check_invariants("commit");
+        assert!(self.release(), "No spare buffer allocated!");
+        assert!(additional == 0 || !self.uninit, "Spare buffer was allocated uninitialized, use commit_written()!");
+        self.refresh_limit();
+        let mut count = additional;
         if additional > 0 {
-            let new_length = self.buffer.len().checked_add(additional).expect("Numerical overflow! (new_length)");
+            let length = self.buffer.len();
+            let new_length = length.checked_add(additional).expect("Numerical overflow! (new_length)");
             assert!(new_length <= self.buffer.capacity(), "Commit size exceeds available capacity!");
-            if new_length <= self.limit.map_or(usize::MAX, NonZeroUsize::get) {
-                unsafe {
-                    self.buffer.set_len(new_length)
+            #[cfg(feature = "safe-only")]
+            assert!(new_length <= self.initialized, "Commit size exceeds the initialized spare buffer!");
+            let headroom = self.headroom();
+            if additional > headroom {
+                log_event!(debug, "Commit of {} elements exceeds the headroom of {} elements", additional, headroom);
+                count = match self.overflow {
+                    OverflowPolicy::Truncate | OverflowPolicy::Saturate if headroom > 0 => headroom,
+                    OverflowPolicy::Saturate => 0,
+                    _ => return Err(IoError::new(ErrorKind::OutOfMemory, "The new length exceeds the specified limit!")),
+                };
+            }
+            if count > 0 && !self.permits(count) {
+                log_event!(debug, "Commit of {} elements was rejected by the dynamic limit", count);
+                match self.overflow {
+                    OverflowPolicy::Saturate => count = 0,
+                    _ => return Err(IoError::new(ErrorKind::OutOfMemory, "The new length exceeds the dynamic limit!")),
                 }
-            } else {
-                return Err(IoError::new(ErrorKind::OutOfMemory, "The new length exceeds the specified limit!"))
             }
+            mark_init(self.spare_ptr(), count);
+            unsafe {
+                self.buffer.set_len(length + count)
+            }
+            log_event!(trace, "Committed {} elements, length is {}", count, length + count);
+            self.notify(length, count);
         }
-        Ok(())
+        if let Some(policy) = self.shrink {
+            if let Some(capacity) = policy.update(&mut self.idle_commits, count, self.buffer.len(), self.buffer.capacity()) {
+                let storage = self.storage_range();
+                self.buffer.shrink_to(capacity);
+                self.track_storage(storage);
+                if let Err(_error) = self.relock() {
+                    log_event!(warn, "Failed to re-lock the memory after shrinking to {} elements: {}", capacity, _error);
+                }
+            }
+        }
+        self.check_invariants("commit");
+        Ok(count)
+    }
+
+    /// Commits the first `additional` elements of the "spare" buffer, exactly
+    /// like [`commit()`](Self::commit) does, but returns a `&[T]` slice of
+    /// the elements that have *actually* been committed, e.g. to hash, parse
+    /// or forward exactly the new data.
+    ///
+    /// The position of the slice in the underlying vector is the range from
+    /// `len()` before the commit to `len()` after the commit.
+    ///
+    /// # Errors
+    ///
+    /// Fails with the same errors as [`commit()`](Self::commit).
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`commit()`](Self::commit).
+    pub fn commit_slice(&mut self, additional: usize) -> IoResult<&[T]> {
+        let start = self.buffer.len();
+        self.commit_counted(additional)?;
+        Ok(&self.buffer.as_slice()[start..])
+    }
+
+    /// Commits the *whole* "spare" buffer, i.e. exactly the `length` that was
+    /// requested when it was allocated, e.g. after it has been filled
+    /// completely by [`read_exact()`](std::io::Read::read_exact).
+    ///
+    /// This avoids passing the wrong count to [`commit()`](Self::commit).
+    /// Note that the slice returned by the allocation may be *longer* than
+    /// the requested length; the excess elements are **not** committed.
+    ///
+    /// Returns the number of elements that have been committed, which may be
+    /// less than the requested length, if permitted by the
+    /// [overflow policy](Self::with_overflow_policy).
+    ///
+    /// # Errors
+    ///
+    /// Fails with the same errors as [`commit()`](Self::commit).
+    ///
+    /// # Panics
+    ///
+    /// Panics if **no** "spare" buffer was allocated before!
+    pub fn commit_all(&mut self) -> IoResult<usize> {
+        assert!(self.allocated, "No spare buffer allocated!");
+        self.commit_counted(self.requested)
+    }
+
+    /// Commits the first `additional` elements of the "spare" buffer, exactly
+    /// like [`commit()`](Self::commit) does, but keeps the *rest* of the
+    /// "spare" buffer allocated, so that more elements can be written and
+    /// committed **without** allocating a new "spare" buffer, e.g. by a
+    /// parser that writes a few records at a time.
+    ///
+    /// The rest of the "spare" buffer can be accessed via
+    /// [`remaining_spare()`](Self::remaining_spare). Once the requested
+    /// length has been committed completely, or if the commit was truncated
+    /// at the length limit, the "spare" buffer is invalidated.
+    ///
+    /// The [shrink policy](Self::with_shrink_policy) is **not** applied by
+    /// partial commits.
+    ///
+    /// Returns the number of elements that have been committed.
+    ///
+    /// # Errors
+    ///
+    /// Fails with the same errors as [`commit()`](Self::commit). The "spare"
+    /// buffer is invalidated in that case.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `additional` is greater than the rest of the requested
+    /// length, or if **no** "spare" buffer was allocated before!
+    pub fn commit_partial(&mut self, additional: usize) -> IoResult<usize> {
+        assert!(self.allocated, "No spare buffer allocated!");
+        assert!(additional <= self.requested, "Commit size exceeds the remaining spare buffer!");
+        let remaining = self.requested - additional;
+        let shrink = self.shrink.take();
+        let result = self.commit_counted(additional);
+        self.shrink = shrink;
+        let count = result?;
+        if let Some(remaining) = NonZeroUsize::new(remaining).filter(|_| count == additional) {
+            self.rearm(remaining);
+        }
+        Ok(count)
+    }
+
+    /// Returns the *rest* of the "spare" buffer, after a partial commit, as a
+    /// `&mut[T]` slice, **without** reserving any capacity. As with
+    /// [`allocate_spare()`](Self::allocate_spare), the slice covers *all* of
+    /// the "spare" capacity.
+    ///
+    /// Not available with the **`safe-only`** feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if **no** "spare" buffer is allocated!
+    #[cfg(not(feature = "safe-only"))]
+    pub fn remaining_spare(&mut self) -> &mut[T] {
+        assert!(self.allocated, "No spare buffer allocated!");
+        let committed = self.buffer.len();
+        let spare = self.buffer.capacity() - committed;
+        unsafe {
+            from_raw_parts_mut(self.buffer.as_mut_ptr().add(committed), spare)
+        }
+    }
+
+    /// Discards the current "spare" buffer, if any, **without** committing
+    /// anything, e.g. when a read has failed and the caller bails out.
+    ///
+    /// Afterwards, a new "spare" buffer must be allocated before committing.
+    /// The reserved capacity is retained. Returns `true` if a "spare" buffer
+    /// was allocated.
+    pub fn discard_spare(&mut self) -> bool {
+        let discarded = self.release();
+        self.check_invariants("discard_spare");
+        discarded
+    }
+
+    /// *Wipes* the whole storage of the underlying vector, i.e. overwrites
+    /// the committed elements as well as the "spare" capacity with zeros,
+    /// and then removes all elements, e.g. once a secret is **no** longer
+    /// needed. The current "spare" buffer is invalidated.
+    ///
+    /// The writes are *volatile*, so that they can **not** be optimized
+    /// away. Note that copies which were left behind in memory that has been
+    /// *freed* by a reallocation can **not** be wiped. Therefore, reserve
+    /// sufficient capacity up front, when handling secrets.
+    pub fn wipe(&mut self) {
+        self.release();
+        wipe(&mut *self.buffer);
+        unsafe {
+            self.buffer.set_len(0);
+        }
+        self.consumed = 0;
+        self.transaction = self.transaction.map(|_| 0);
+        self.check_invariants("wipe");
+    }
+
+    /// Retracts the last `count` "committed" elements, e.g. speculative data
+    /// that was read past a frame boundary. The retracted elements become
+    /// part of the "spare" capacity again.
+    ///
+    /// The consumed count is clamped to the new length, and the current
+    /// "spare" buffer is invalidated.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` is greater than the length of the underlying vector.
+    pub fn uncommit(&mut self, count: usize) {
+        assert!(count <= self.buffer.len(), "Uncommit size exceeds the length!");
+        self.truncate_committed(self.buffer.len() - count);
+    }
+
+    /// Shortens the "committed" elements to the first `length` elements,
+    /// exactly like [`Vec::truncate()`](std::vec::Vec::truncate) does. Has
+    /// **no** effect, if `length` is greater than or equal to the current
+    /// length.
+    ///
+    /// The consumed count is clamped to the new length, and the current
+    /// "spare" buffer is invalidated.
+    pub fn truncate_committed(&mut self, length: usize) {
+        self.release();
+        if length < self.buffer.len() {
+            unsafe {
+                self.buffer.set_len(length);
+            }
+            self.consumed = self.consumed.min(length);
+        }
+        self.check_invariants("truncate_committed");
+    }
+
+    /// Begins a *transaction*, e.g. in order to assemble one message from
+    /// several reads. All elements that are committed while the transaction
+    /// is active can be discarded *atomically*, by calling
+    /// [`rollback()`](Self::rollback), which restores the length of the
+    /// underlying vector at the beginning of the transaction.
+    ///
+    /// Elements that are removed from, or inserted at, the front, e.g. via
+    /// [`compact()`](Self::compact), while the transaction is active, are
+    /// taken into account.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a transaction is already active!
+    pub fn begin_transaction(&mut self) {
+        assert!(self.transaction.is_none(), "A transaction is already active!");
+        self.transaction = Some(self.buffer.len());
+    }
+
+    /// Returns `true` if a transaction is active.
+    pub fn in_transaction(&self) -> bool {
+        self.transaction.is_some()
+    }
+
+    /// Ends the active transaction, keeping *all* elements that have been
+    /// committed while it was active.
+    ///
+    /// # Panics
+    ///
+    /// Panics if **no** transaction is active!
+    pub fn commit_transaction(&mut self) {
+        assert!(self.transaction.take().is_some(), "No transaction active!");
+    }
+
+    /// Ends the active transaction, discarding *all* elements that have been
+    /// committed while it was active. The current "spare" buffer, if any, is
+    /// invalidated.
+    ///
+    /// Returns the number of elements that have been discarded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if **no** transaction is active!
+    pub fn rollback(&mut self) -> usize {
+        let start = self.transaction.take().expect("No transaction active!");
+        let discarded = self.buffer.len().saturating_sub(start);
+        self.truncate_committed(start);
+        discarded
+    }
+
+    /// Marks the rest of the "spare" buffer, of the given `length`, as still
+    /// allocated, after a partial commit.
+    fn rearm(&mut self, length: NonZeroUsize) {
+        self.set_allocated(true);
+        self.requested = length.get();
+        #[cfg(feature = "paranoid")]
+        {
+            self.snapshot = Some(Snapshot {
+                address: self.tracked_address(),
+                length: self.buffer.len(),
+                capacity: self.buffer.capacity(),
+                requested: length,
+            });
+        }
+        self.check_invariants("commit_partial");
+    }
+
+    /// Commits the first `additional` elements of the "spare" buffer, but
+    /// only if they are accepted by the `check` function.
+    ///
+    /// The `check` function is called with a `&[T]` slice of the first
+    /// `additional` elements of the "spare" buffer, e.g. to verify a checksum
+    /// or some magic bytes. If, and only if, it returns `Ok(())`, the elements
+    /// are committed, exactly like [`commit()`](Self::commit) does. Otherwise,
+    /// the "spare" buffer is discarded and the error is returned.
+    ///
+    /// The same requirements as for [`commit()`](Self::commit) apply, i.e.
+    /// all elements to be committed **must** have been initialized.
+    ///
+    /// Not available with the **`safe-only`** feature.
+    ///
+    /// # Errors
+    ///
+    /// Errors returned by the `check` function, as well as errors returned by
+    /// [`commit()`](Self::commit), are passed through.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `additional` is greater than the available "spare" capacity,
+    /// or if **no** "spare" buffer was allocated before!
+    #[cfg(not(feature = "safe-only"))]
+    pub fn commit_verified<F>(&mut self, additional: usize, check: F) -> IoResult<()>
+    where
+        F: FnOnce(&[T]) -> IoResult<()>
+    {
+        assert!(self.allocated, "No spare buffer allocated!");
+        assert!(additional <= self.buffer.capacity() - self.buffer.len(), "Commit size exceeds available capacity!");
+        let filled = unsafe {
+            std::slice::from_raw_parts(self.spare_ptr(), additional)
+        };
+        if let Err(error) = self.guarded(|_| check(filled)) {
+            self.release();
+            return Err(error);
+        }
+        self.commit(additional)
     }
 
     /// The same as [`commit()`](Self::commit) but **without** any checks.
     /// 
     /// This function is **`unsafe`**, for obvious reasons, and therefore
-    /// should be used with great care!
+    /// should be used with great care! Not available with the **`safe-only`**
+    /// feature.
+    ///
+    /// # Safety
+    ///
+    /// A "spare" buffer **must** have been [allocated](Self::allocate_spare)
+    /// before, and `additional` **must not** exceed the available "spare"
+    /// capacity. Also, the first `additional` elements of the "spare" buffer
+    /// **must** have been initialized. The length limit is **not** enforced!
+    #[cfg(not(feature = "safe-only"))]
     pub unsafe fn commit_unchecked(&mut self, additional: usize) {
-        self.allocated = false;
+        self.check_invariants("commit_unchecked");
+        self.release();
         if additional > 0 {
-            self.buffer.set_len(self.buffer.len() + additional)
+            let length = self.buffer.len();
+            self.buffer.set_len(length + additional);
+            self.notify(length, additional);
         }
+        #[cfg(feature = "paranoid")]
+        {
+            self.exceeded_limit |= self.limit.is_some_and(|limit| self.buffer.len() > limit.get());
+        }
+        self.check_invariants("commit_unchecked");
+    }
+
+    /// Allocates a "spare" buffer of the specified `length` at the *front* of
+    /// the underlying vector, and lets `f` fill it, e.g. with a protocol
+    /// header that is only known after the payload has been accumulated.
+    ///
+    /// The unconsumed elements are moved *once* to make room for the "spare"
+    /// buffer. `f` returns the number of elements, at the *start* of the
+    /// "spare" buffer, that it has initialized, which are then committed, in
+    /// front of the existing elements. If fewer than `length` elements are
+    /// committed, the existing elements are moved back accordingly. Any
+    /// consumed elements are removed, as by [`compact()`](Self::compact).
+    ///
+    /// Returns the number of elements that have been committed. The current
+    /// "spare" buffer, if any, is invalidated. If `f` panics, **nothing** is
+    /// committed and the unconsumed elements are restored, at the *start* of
+    /// the underlying vector.
+    ///
+    /// Not available with the **`safe-only`** feature.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`AllocationError::ExceedsMaxAllocation`](crate::AllocationError::ExceedsMaxAllocation),
+    /// converted to an error of kind [`ErrorKind::OutOfMemory`], if `length`
+    /// exceeds the maximum allocation size. Fails with
+    /// [`AllocationError::Reserve`](crate::AllocationError::Reserve),
+    /// converted to an error of kind `OutOfMemory`, if the capacity can
+    /// **not** be reserved, as controlled by the
+    /// [growth policy](Self::with_growth_policy). Fails with an error of
+    /// kind [`ErrorKind::OutOfMemory`], if `length` additional elements would
+    /// exceed the current length limit, the memory limit, or the
+    /// [dynamic limit](Self::with_dynamic_limit). The elements,
+    /// including the consumed ones, are **not** moved in all of these cases,
+    /// and `f` is **not** called.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `f` returns a count that is greater than `length`.
+    #[cfg(not(feature = "safe-only"))]
+    pub fn prepend_with<F>(&mut self, length: NonZeroUsize, f: F) -> IoResult<usize>
+    where
+        F: FnOnce(&mut [T]) -> usize
+    {
+        self.front_mut(length, f)
+    }
+
+    /// Inserts a copy of `data` at the *front* of the underlying vector. See
+    /// [`prepend_with()`](Self::prepend_with) for details.
+    ///
+    /// # Errors
+    ///
+    /// Fails with the same errors as [`prepend_with()`](Self::prepend_with).
+    pub fn prepend(&mut self, data: &[T]) -> IoResult<()> {
+        match NonZeroUsize::new(data.len()) {
+            Some(length) => self.front_mut(length, |front| {
+                front.copy_from_slice(data);
+                data.len()
+            }).map(|_| ()),
+            None => Ok(()),
+        }
+    }
+
+    /// Allocates a "spare" buffer at the front, for *internal* use. See
+    /// [`prepend_with()`](Self::prepend_with) for details.
+    fn front_mut<F>(&mut self, length: NonZeroUsize, f: F) -> IoResult<usize>
+    where
+        F: FnOnce(&mut [T]) -> usize
+    {
+        self.check_invariants("prepend");
+        self.release();
+        self.refresh_limit();
+        if let Some(max_allocation) = self.max_allocation.filter(|max_allocation| length > *max_allocation) {
+            log_event!(debug, "Allocation of {} elements exceeds the maximum of {}", length, max_allocation);
+            return Err(AllocationError::ExceedsMaxAllocation { requested: length, max_allocation }.into());
+        }
+        let consumed = self.consumed;
+        let unconsumed = self.buffer.len() - consumed;
+        let headroom = self.max_length().saturating_sub(unconsumed);
+        if length.get() > headroom {
+            log_event!(debug, "Prepend of {} elements exceeds the headroom of {} elements", length, headroom);
+            return Err(IoError::new(ErrorKind::OutOfMemory, "The new length exceeds the specified limit!"));
+        }
+        if !self.dynamic_limit.as_ref().is_none_or(|limit| limit.permits(unconsumed, length.get())) {
+            log_event!(debug, "Prepend of {} elements was rejected by the dynamic limit", length);
+            return Err(IoError::new(ErrorKind::OutOfMemory, "The new length exceeds the dynamic limit!"));
+        }
+        let required = unconsumed.checked_add(length.get()).expect("Numerical overflow! (required)");
+        self.grow(required.saturating_sub(self.buffer.len()))?;
+        self.consumed = 0;
+        self.transaction = self.transaction.map(|start| start.saturating_sub(consumed));
+        self.initialized = 0;
+        let mut guard = FrontGuard { buffer: &mut *self.buffer, shift: length.get(), moved: unconsumed, count: 0 };
+        let front = unsafe {
+            let ptr = guard.buffer.as_mut_ptr();
+            guard.buffer.set_len(0);
+            std::ptr::copy(ptr.add(consumed), ptr.add(length.get()), unconsumed);
+            mark_uninit(ptr, length.get());
+            from_raw_parts_mut(ptr, length.get())
+        };
+        let count = f(front);
+        assert!(count <= length.get(), "Commit size exceeds available capacity!");
+        mark_init(guard.buffer.as_ptr(), count);
+        guard.count = count;
+        drop(guard);
+        self.notify(0, count);
+        self.transaction = self.transaction.map(|start| start + count);
+        self.check_invariants("prepend");
+        Ok(count)
+    }
+}
+
+impl<'a, T> SpareBuffer<'a, T>
+where
+    T: Primitive
+{
+    /// Splits off the first `count` "committed" elements, by *taking* the
+    /// underlying vector, truncated to `count` elements, and replacing it
+    /// with a new vector that holds the remaining elements. Only the
+    /// remaining elements are copied. The consumed count is reduced
+    /// accordingly, and the current "spare" buffer is invalidated.
+    ///
+    /// Fails, if the memory is locked, and the new vector can **not** be
+    /// locked. Nothing is changed in that case.
+    #[cfg(feature = "bytes")]
+    pub(crate) fn split_front(&mut self, count: usize) -> IoResult<Vec<T>> {
+        let mut remaining = Vec::with_capacity(self.buffer.capacity() - count);
+        remaining.extend_from_slice(&self.buffer[count..]);
+        let mut front = self.swap_vec(remaining)?;
+        front.truncate(count);
+        self.consumed = self.consumed.saturating_sub(count);
+        self.transaction = self.transaction.map(|start| start.saturating_sub(count));
+        self.check_invariants("split_front");
+        Ok(front)
+    }
+
+    /// Reserves capacity for `hint` additional elements, but clamped to the
+    /// length limit and to the maximum allocation size.
+    #[cfg(any(feature = "futures-core", feature = "http-body"))]
+    pub(crate) fn reserve_hint(&mut self, hint: usize) -> Result<(), AllocationError> {
+        let additional = hint.min(self.headroom()).min(self.max_allocation.map_or(usize::MAX, NonZeroUsize::get));
+        let storage = self.storage_range();
+        self.buffer.try_reserve(additional).map_err(AllocationError::Reserve)?;
+        self.track_storage(storage);
+        self.relock().map_err(|error| AllocationError::Lock(error.kind()))
+    }
+
+    /// Replaces the underlying vector with `vector`, and returns the previous
+    /// vector. The current "spare" buffer is invalidated.
+    ///
+    /// If the memory is locked, then the lock is moved to the new vector. If
+    /// the new vector can **not** be locked, the error is returned, and
+    /// nothing is changed. The lock state is cleared, if even the previous
+    /// vector can **not** be locked again in that case.
+    fn swap_vec(&mut self, vector: Vec<T>) -> IoResult<Vec<T>> {
+        #[cfg(any(unix, windows))]
+        if let Some(range) = self.locked {
+            let current = (vector.as_ptr() as usize, vector.capacity() * std::mem::size_of::<T>());
+            lock::unlock(range);
+            if let Err(error) = lock::lock(current) {
+                if lock::lock(range).is_err() {
+                    self.set_locked(None);
+                }
+                return Err(error);
+            }
+            self.set_locked(Some(current));
+        }
+        self.release();
+        let storage = self.storage_range();
+        let previous = std::mem::replace(&mut *self.buffer, vector);
+        self.track_storage(storage);
+        Ok(previous)
+    }
+}
+
+/// Moves the elements behind a front "spare" buffer back into place, when
+/// dropped, so that they directly follow the `count` committed elements.
+struct FrontGuard<'b, T> {
+    buffer: &'b mut dyn SpareCapacity<T>,
+    shift: usize,
+    moved: usize,
+    count: usize,
+}
+
+impl<'b, T> Drop for FrontGuard<'b, T> {
+    fn drop(&mut self) {
+        unsafe {
+            let ptr = self.buffer.as_mut_ptr();
+            if self.count != self.shift {
+                std::ptr::copy(ptr.add(self.shift), ptr.add(self.count), self.moved);
+            }
+            self.buffer.set_len(self.count + self.moved);
+        }
+    }
+}
+
+/// An *owned* [**`SpareBuffer`**](crate::SpareBuffer), which owns its
+/// underlying vector, instead of borrowing it.
+///
+/// This allows the buffer to live as a long-lived field, e.g. inside readers,
+/// decoders or connection state machines, **without** a borrow-split between
+/// the vector and the buffer. All functions of the
+/// [**`SpareBuffer`**](crate::SpareBuffer) are available.
+///
+/// Use [`SpareBuffer::owned()`](crate::SpareBuffer::owned) to create a new
+/// **`SpareVec`**, and [`into_vec()`](crate::SpareBuffer::into_vec) to get
+/// the vector back.
+pub type SpareVec<T> = SpareBuffer<'static, T>;
+
+impl<T, B> SpareBuffer<'static, T, B>
+where
+    T: Primitive,
+    B: SpareCapacity<T>
+{
+    /// Creates a new [**`SpareVec`**](crate::SpareVec), which takes the
+    /// *ownership* of `buffer`.
+    ///
+    /// An *optional* `limit` for the length of the vector can be specified.
+    /// The [`commit()`](Self::commit) fails, if it would exceed this limit.
+    ///
+    /// If the **`log`** or the **`tracing`** feature is enabled, a warning is
+    /// logged, if the **`SpareVec`** is dropped while a "spare" buffer is
+    /// allocated that has been neither committed nor
+    /// [discarded](Self::discard_spare). This is
+    /// **not** possible for a *borrowed* vector, because the
+    /// **`SpareBuffer`** itself has **no** drop logic.
+    pub fn owned(buffer: B, limit: Option<NonZeroUsize>) -> Self {
+        Self::with_storage(Storage::Owned(Owned {
+            buffer,
+            wipe: None,
+            #[cfg(any(unix, windows))]
+            locked: None,
+            #[cfg(any(feature = "log", feature = "tracing"))]
+            allocated: false,
+        }), limit)
+    }
+
+    /// Makes the [**`SpareVec`**](crate::SpareVec) *wipe* its whole storage,
+    /// i.e. the committed elements as well as the "spare" capacity, when it
+    /// is dropped, e.g. when accumulating keys, passwords or plaintext. See
+    /// [`wipe()`](Self::wipe) for details.
+    ///
+    /// Has **no** effect, if the vector is *borrowed*, because it outlives
+    /// the **`SpareBuffer`** in that case. The vector is **not** wiped, if it
+    /// is taken back via [`into_inner()`](Self::into_inner), or via the
+    /// finalizers, such as [`into_vec()`](Self::into_vec).
+    pub fn with_wipe_on_drop(mut self) -> Self {
+        if let Storage::Owned(owned) = &mut self.buffer {
+            owned.wipe = Some(wipe::<T, B>);
+        }
+        self
+    }
+
+    /// Returns a `&[T]` slice of all "committed" elements in the underlying
+    /// vector. This is equivalent to [`data()`](Self::data).
+    pub fn as_slice(&self) -> &[T] {
+        self.data()
+    }
+
+    /// Returns the underlying storage, which contains all "committed"
+    /// elements, including the consumed ones, for *any* backend.
+    ///
+    /// If the storage was *borrowed* for the `'static` lifetime, rather than
+    /// owned, it is taken, leaving a default storage behind.
+    pub fn into_inner(self) -> B
+    where
+        B: Default
+    {
+        match self.buffer {
+            Storage::Owned(mut owned) => {
+                owned.wipe = None;
+                std::mem::take(&mut owned.buffer)
+            },
+            Storage::Borrowed(buffer) => std::mem::take(buffer),
+        }
+    }
+}
+
+impl<T> SpareBuffer<'static, T>
+where
+    T: Primitive
+{
+    /// Returns the underlying vector, which contains all "committed"
+    /// elements, including the consumed ones.
+    ///
+    /// If the vector was *borrowed* for the `'static` lifetime, rather than
+    /// owned, it is taken, leaving an empty vector behind.
+    pub fn into_vec(self) -> Vec<T> {
+        self.into_inner()
+    }
+
+    /// Returns all "committed" elements, including the consumed ones, as a
+    /// *boxed* slice, e.g. to freeze the accumulated data. The "spare"
+    /// capacity of the underlying vector is released, which may reallocate.
+    pub fn into_boxed_slice(self) -> Box<[T]> {
+        self.into_vec().into_boxed_slice()
+    }
+
+    /// Returns all "committed" elements, including the consumed ones, as a
+    /// *reference-counted* slice, e.g. to share the accumulated data between
+    /// threads. The elements are copied into a new allocation.
+    pub fn into_arc_slice(self) -> Arc<[T]> {
+        Arc::from(self.into_vec())
+    }
+
+    /// *Takes* the underlying vector, which contains all "committed"
+    /// elements, including the consumed ones, and replaces it with a new,
+    /// empty vector, e.g. to hand off a completed batch and to continue
+    /// filling **without** re-constructing the **`SpareBuffer`**.
+    ///
+    /// All settings are retained. The consumed count is reset, and the
+    /// current "spare" buffer is invalidated. The taken vector is **not**
+    /// wiped.
+    pub fn take(&mut self) -> Vec<T> {
+        self.replace(Vec::new()).expect("An empty vector can always be locked!")
+    }
+
+    /// Replaces the underlying vector with `vector`, e.g. a recycled vector,
+    /// and returns the previous vector, exactly like [`take()`](Self::take)
+    /// does. The new vector is *cleared*, but its capacity is retained.
+    ///
+    /// # Errors
+    ///
+    /// If the memory is [locked](Self::lock_memory), then this function will
+    /// fail, if the storage of the new vector can **not** be locked. Errors
+    /// returned by the operating system are passed through. Nothing is
+    /// replaced in that case.
+    pub fn replace(&mut self, mut vector: Vec<T>) -> IoResult<Vec<T>> {
+        vector.clear();
+        let previous = self.swap_vec(vector)?;
+        self.consumed = 0;
+        self.transaction = self.transaction.map(|_| 0);
+        self.check_invariants("replace");
+        Ok(previous)
+    }
+}
+
+/// The underlying vector of a **`SpareBuffer`**, either borrowed or owned.
+enum Storage<'a, B> {
+    Borrowed(&'a mut B),
+    Owned(Owned<B>),
+}
+
+impl<'a, B> Deref for Storage<'a, B> {
+    type Target = B;
+
+    fn deref(&self) -> &B {
+        match self {
+            Self::Borrowed(buffer) => buffer,
+            Self::Owned(owned) => &owned.buffer,
+        }
+    }
+}
+
+impl<'a, B> DerefMut for Storage<'a, B> {
+    fn deref_mut(&mut self) -> &mut B {
+        match self {
+            Self::Borrowed(buffer) => buffer,
+            Self::Owned(owned) => &mut owned.buffer,
+        }
+    }
+}
+
+/// An *owned* vector, which is wiped by the given function, and unlocked,
+/// when dropped. With the **`log`** or the **`tracing`** feature, an
+/// outstanding "spare" buffer, which was neither committed nor discarded, is
+/// reported when dropped.
+struct Owned<B> {
+    buffer: B,
+    wipe: Option<fn(&mut B)>,
+    #[cfg(any(unix, windows))]
+    locked: Option<Range>,
+    #[cfg(any(feature = "log", feature = "tracing"))]
+    allocated: bool,
+}
+
+impl<B> Drop for Owned<B> {
+    fn drop(&mut self) {
+        #[cfg(any(feature = "log", feature = "tracing"))]
+        if self.allocated && !std::thread::panicking() {
+            log_event!(warn, "A `SpareVec` was dropped with a spare buffer that was neither committed nor discarded!");
+        }
+        if let Some(wipe) = self.wipe {
+            wipe(&mut self.buffer);
+        }
+        #[cfg(any(unix, windows))]
+        if let Some(range) = self.locked {
+            lock::unlock(range);
+        }
+    }
+}
+
+/// Discards the "spare" buffer of the wrapped **`SpareBuffer`**, if dropped
+/// while unwinding.
+struct UnwindGuard<'b, 'a, T, B>(&'b mut SpareBuffer<'a, T, B>)
+where
+    T: Primitive,
+    B: SpareCapacity<T>;
+
+impl<'b, 'a, T, B> Drop for UnwindGuard<'b, 'a, T, B>
+where
+    T: Primitive,
+    B: SpareCapacity<T>
+{
+    fn drop(&mut self) {
+        self.0.discard_if_panicking();
+    }
+}
+
+impl<'a, T, B> AsRef<[T]> for SpareBuffer<'a, T, B>
+where
+    T: Primitive,
+    B: SpareCapacity<T>
+{
+    fn as_ref(&self) -> &[T] {
+        self.data()
     }
 }
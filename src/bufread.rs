@@ -0,0 +1,105 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::{BufRead, Read, Result as IoResult, Write};
+#[cfg(feature = "bytes")]
+use std::num::NonZeroUsize;
+
+#[cfg(feature = "bytes")]
+use bytes::buf::{BufMut, UninitSlice};
+
+use crate::{SpareBuffer, SpareCapacity};
+
+/// Reads the "committed" elements that have **not** been
+/// [consumed](crate::SpareBuffer::consumed) yet.
+///
+/// This allows line or frame readers to operate directly on the accumulating
+/// buffer, without copying. Note that reading returns `0`, i.e. signals the
+/// end of the stream, once *all* committed elements have been consumed.
+impl<'a, B> Read for SpareBuffer<'a, u8, B>
+where
+    B: SpareCapacity<u8>
+{
+    fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
+        let count = buf.len().min(self.unconsumed().len());
+        buf[..count].copy_from_slice(&self.unconsumed()[..count]);
+        SpareBuffer::consume(self, count);
+        Ok(count)
+    }
+}
+
+/// Appends the written bytes to the underlying vector, by copying them into
+/// a "spare" buffer and committing it, so that encoders, serializers or
+/// compressors that accept any [`Write`](std::io::Write) can stream into the
+/// buffer directly.
+///
+/// A write is *shortened*, if the length limit or the maximum allocation size
+/// is about to be reached. Once the limit has been reached, writes fail with
+/// an error of kind [`ErrorKind::OutOfMemory`](std::io::ErrorKind::OutOfMemory).
+impl<'a, B> Write for SpareBuffer<'a, u8, B>
+where
+    B: SpareCapacity<u8>
+{
+    fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+        self.append_partial(buf)
+    }
+
+    fn flush(&mut self) -> IoResult<()> {
+        Ok(())
+    }
+}
+
+impl<'a, B> BufRead for SpareBuffer<'a, u8, B>
+where
+    B: SpareCapacity<u8>
+{
+    fn fill_buf(&mut self) -> IoResult<&[u8]> {
+        Ok(self.unconsumed())
+    }
+
+    fn consume(&mut self, amount: usize) {
+        SpareBuffer::consume(self, amount)
+    }
+}
+
+/// Exposes the "spare" buffer to encoders that write into an `impl BufMut`,
+/// such as the codecs of `tokio-util`, `h2` or `quinn`.
+///
+/// [`chunk_mut()`](bytes::BufMut::chunk_mut) allocates a "spare" buffer, if
+/// there is **no** "spare" capacity left, sized according to the
+/// [**`ChunkPolicy`**](crate::ChunkPolicy) of this **`SpareBuffer`**, and
+/// [`advance_mut()`](bytes::BufMut::advance_mut) commits the written bytes.
+/// The remaining capacity is bounded by the length limit, so writing beyond
+/// the limit *panics*, as documented for `BufMut`.
+///
+/// Only available with the **`bytes`** feature.
+#[cfg(feature = "bytes")]
+unsafe impl<'a, B> BufMut for SpareBuffer<'a, u8, B>
+where
+    B: SpareCapacity<u8>
+{
+    fn remaining_mut(&self) -> usize {
+        self.headroom()
+    }
+
+    unsafe fn advance_mut(&mut self, cnt: usize) {
+        if cnt > 0 {
+            if let Err(error) = self.commit(cnt) {
+                panic!("{}", error);
+            }
+        }
+    }
+
+    fn chunk_mut(&mut self) -> &mut UninitSlice {
+        let headroom = self.headroom().min(self.max_allocation().map_or(usize::MAX, NonZeroUsize::get));
+        let available = self.spare_uninit().len();
+        let length = if available > 0 { available } else { self.chunk_policy().chunk().get() }.min(headroom);
+        if let Some(length) = NonZeroUsize::new(length) {
+            if let Err(error) = self.try_spare_mut(length) {
+                panic!("{}", error);
+            }
+        }
+        UninitSlice::uninit(&mut self.spare_uninit()[..length])
+    }
+}
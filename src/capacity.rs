@@ -0,0 +1,204 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::collections::TryReserveError;
+use std::mem::MaybeUninit;
+use std::slice::{from_raw_parts, from_raw_parts_mut};
+
+/// A *backend* for the [**`SpareBuffer`**](crate::SpareBuffer), i.e. a
+/// contiguous, growable storage whose "spare" capacity can be accessed.
+///
+/// The allocate/commit state machine of the **`SpareBuffer`** only relies on
+/// this trait, so that it is **not** hard-wired to
+/// [**`Vec<T>`**](std::vec::Vec), which is the default backend. Alternative
+/// backends, such as inline or memory-mapped storages, can be plugged in by
+/// implementing this trait.
+///
+/// # Safety
+///
+/// Implementations **must** guarantee that:
+///
+/// - [`as_ptr()`](Self::as_ptr) and [`as_mut_ptr()`](Self::as_mut_ptr) point
+///   to a *contiguous* allocation of [`capacity()`](Self::capacity)
+///   elements, the first [`len()`](Self::len) of which are initialized.
+/// - After [`reserve(additional)`](Self::reserve), the "spare" capacity, i.e.
+///   `capacity() - len()`, is *at least* `additional` elements.
+/// - The storage is **not** moved, and its length and capacity are **not**
+///   changed, except by `reserve()`, `set_len()` or
+///   [`shrink_to()`](Self::shrink_to). *Inline* elements, as reported by
+///   [`is_inline()`](Self::is_inline), may only move together with the
+///   value that implements this trait.
+pub unsafe trait SpareCapacity<T> {
+    /// Returns the number of initialized elements.
+    fn len(&self) -> usize;
+
+    /// Returns the *total* number of elements that the storage can hold,
+    /// without reallocating.
+    fn capacity(&self) -> usize;
+
+    /// Reserves capacity for *at least* `additional` more elements.
+    fn reserve(&mut self, additional: usize);
+
+    /// Tries to reserve capacity for *at least* `additional` more elements,
+    /// returning an error instead of aborting the process, if the allocation
+    /// fails. Falls back to [`reserve()`](Self::reserve), by default.
+    fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.reserve(additional);
+        Ok(())
+    }
+
+    /// Tries to reserve capacity for *exactly* `additional` more elements,
+    /// if the storage supports it. Falls back to
+    /// [`try_reserve()`](Self::try_reserve), by default.
+    fn try_reserve_exact(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.try_reserve(additional)
+    }
+
+    /// Returns a raw pointer to the start of the storage.
+    fn as_ptr(&self) -> *const T;
+
+    /// Returns a raw mutable pointer to the start of the storage.
+    fn as_mut_ptr(&mut self) -> *mut T;
+
+    /// Sets the number of initialized elements.
+    ///
+    /// # Safety
+    ///
+    /// `new_len` **must not** exceed the capacity, and the first `new_len`
+    /// elements **must** have been initialized.
+    unsafe fn set_len(&mut self, new_len: usize);
+
+    /// Shrinks the capacity, with a lower bound of `min_capacity`, if the
+    /// storage supports it. Does nothing, by default.
+    fn shrink_to(&mut self, _min_capacity: usize) {}
+
+    /// Returns `true` if the elements are currently stored *inline*, i.e.
+    /// they move whenever the value that implements this trait is moved.
+    /// Returns `false`, by default.
+    fn is_inline(&self) -> bool {
+        false
+    }
+
+    /// Returns `true` if the storage contains no initialized elements.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the initialized elements, as a `&[T]` slice.
+    fn as_slice(&self) -> &[T] {
+        unsafe {
+            from_raw_parts(self.as_ptr(), self.len())
+        }
+    }
+
+    /// Returns the "spare" capacity, as a `&mut[MaybeUninit<T>]` slice.
+    fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        let length = self.len();
+        let spare = self.capacity() - length;
+        unsafe {
+            from_raw_parts_mut(self.as_mut_ptr().add(length) as *mut MaybeUninit<T>, spare)
+        }
+    }
+}
+
+unsafe impl<T> SpareCapacity<T> for Vec<T> {
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn capacity(&self) -> usize {
+        Vec::capacity(self)
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        Vec::reserve(self, additional)
+    }
+
+    fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        Vec::try_reserve(self, additional)
+    }
+
+    fn try_reserve_exact(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        Vec::try_reserve_exact(self, additional)
+    }
+
+    fn as_ptr(&self) -> *const T {
+        Vec::as_ptr(self)
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut T {
+        Vec::as_mut_ptr(self)
+    }
+
+    unsafe fn set_len(&mut self, new_len: usize) {
+        Vec::set_len(self, new_len)
+    }
+
+    fn shrink_to(&mut self, min_capacity: usize) {
+        Vec::shrink_to(self, min_capacity)
+    }
+}
+
+/// Keeps short data *inline*, e.g. on the stack, and only spills to the heap,
+/// once the inline capacity is exceeded.
+///
+/// Note that the inline elements move together with the **`SmallVec`**. If
+/// it is *owned* by a [**`SpareVec`**](crate::SpareVec), they move whenever
+/// the **`SpareVec`** is moved. Therefore,
+/// [`lock_memory()`](crate::SpareBuffer::lock_memory) fails for inline
+/// elements in that case, and the address of the storage is **not** tracked
+/// by the **`paranoid`** checks. The capacity is reserved via
+/// `SmallVec::try_reserve()`, but its errors are reported as a
+/// *capacity overflow*, because they can **not** be converted.
+///
+/// Only available with the **`smallvec`** feature.
+#[cfg(feature = "smallvec")]
+unsafe impl<A> SpareCapacity<A::Item> for smallvec::SmallVec<A>
+where
+    A: smallvec::Array
+{
+    fn len(&self) -> usize {
+        smallvec::SmallVec::len(self)
+    }
+
+    fn capacity(&self) -> usize {
+        smallvec::SmallVec::capacity(self)
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        smallvec::SmallVec::reserve(self, additional)
+    }
+
+    fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        smallvec::SmallVec::try_reserve(self, additional).map_err(|_| capacity_overflow())
+    }
+
+    fn try_reserve_exact(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        smallvec::SmallVec::try_reserve_exact(self, additional).map_err(|_| capacity_overflow())
+    }
+
+    fn as_ptr(&self) -> *const A::Item {
+        smallvec::SmallVec::as_ptr(self)
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut A::Item {
+        smallvec::SmallVec::as_mut_ptr(self)
+    }
+
+    unsafe fn set_len(&mut self, new_len: usize) {
+        smallvec::SmallVec::set_len(self, new_len)
+    }
+
+    fn is_inline(&self) -> bool {
+        !smallvec::SmallVec::spilled(self)
+    }
+}
+
+/// Returns a [**`TryReserveError`**](std::collections::TryReserveError) that
+/// reports a *capacity overflow*, for backends whose own errors can **not**
+/// be converted.
+#[cfg(feature = "smallvec")]
+fn capacity_overflow() -> TryReserveError {
+    Vec::<u8>::new().try_reserve(usize::MAX).expect_err("Reserving `usize::MAX` bytes must fail!")
+}
@@ -0,0 +1,128 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::fmt::Debug;
+use std::io::Result as IoResult;
+use std::num::NonZeroUsize;
+
+use crate::{Primitive, SpareBuffer, SpareWriter};
+
+/// A wrapper around a [**`SpareBuffer`**](crate::SpareBuffer) that checks
+/// its invariants, intended for *fuzzing* code that uses this crate.
+///
+/// All data is written through the *safe* API, i.e. via a
+/// [**`SpareWriter`**](crate::SpareWriter), while a *model* of the expected
+/// contents is recorded. After every operation, the contents of the
+/// underlying vector are compared against the model, and the length limit is
+/// verified. Any mismatch causes a panic, which the fuzzer reports.
+pub struct CheckedSpareBuffer<'a, T>
+where
+    T: Primitive + PartialEq + Debug
+{
+    inner: SpareBuffer<'a, T>,
+    model: Vec<T>,
+}
+
+impl<'a, T> CheckedSpareBuffer<'a, T>
+where
+    T: Primitive + PartialEq + Debug
+{
+    /// Creates a new **`CheckedSpareBuffer`** that wraps the given
+    /// [**`SpareBuffer`**](crate::SpareBuffer). The model is initialized
+    /// from the elements that are already committed.
+    pub fn new(inner: SpareBuffer<'a, T>) -> Self {
+        let model = inner.data().to_vec();
+        Self {
+            inner,
+            model,
+        }
+    }
+
+    /// Returns a reference to the wrapped **`SpareBuffer`**.
+    pub fn get_ref(&self) -> &SpareBuffer<'a, T> {
+        &self.inner
+    }
+
+    /// Returns the expected contents of the underlying vector.
+    pub fn model(&self) -> &[T] {
+        &self.model
+    }
+
+    /// Consumes the **`CheckedSpareBuffer`**, returning the wrapped
+    /// **`SpareBuffer`**.
+    pub fn into_inner(self) -> SpareBuffer<'a, T> {
+        self.inner
+    }
+
+    /// Allocates a "spare" buffer of the specified `length`, lets `f` fill it
+    /// through a [**`SpareWriter`**](crate::SpareWriter), and commits the
+    /// data that has been written, unless `f` returns `false`, in which case
+    /// the "spare" buffer is discarded.
+    ///
+    /// Returns the number of elements that have been committed.
+    ///
+    /// # Errors
+    ///
+    /// Errors returned by [`SpareWriter::commit()`](crate::SpareWriter::commit)
+    /// are passed through. The model is **not** updated in that case.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the contents of the underlying vector do **not** match the
+    /// model afterwards, or if the length limit has been exceeded.
+    pub fn write_with<F>(&mut self, length: NonZeroUsize, f: F) -> IoResult<usize>
+    where
+        F: FnOnce(&mut SpareWriter<'_, 'a, T>) -> bool
+    {
+        let mut writer = self.inner.writer(length);
+        let keep = f(&mut writer);
+        assert_eq!(writer.written().len(), writer.filled(), "Written data does not match the filled count!");
+        assert!(writer.filled() <= writer.capacity(), "Filled count exceeds the capacity!");
+        let written = writer.written().to_vec();
+        let result = if keep {
+            writer.commit()
+        } else {
+            writer.discard();
+            Ok(0)
+        };
+        if let Ok(count) = result {
+            assert_eq!(count, if keep { written.len() } else { 0 }, "Commit count does not match the written data!");
+            self.model.extend_from_slice(&written[..count]);
+        }
+        self.check();
+        result
+    }
+
+    /// Writes as many elements of `values` as fit into a "spare" buffer of
+    /// the specified `length`, and commits them. See
+    /// [`write_with()`](Self::write_with) for details.
+    pub fn write(&mut self, length: NonZeroUsize, values: &[T]) -> IoResult<usize> {
+        self.write_with(length, |writer| {
+            writer.write(values);
+            true
+        })
+    }
+
+    /// Removes all consumed elements from the front of the underlying vector.
+    /// See [`SpareBuffer::compact()`](crate::SpareBuffer::compact).
+    pub fn compact(&mut self) {
+        self.model.drain(..self.inner.consumed());
+        self.inner.compact();
+        self.check();
+    }
+
+    /// Checks the contents of the underlying vector against the model, and
+    /// verifies the length limit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the checks fails!
+    pub fn check(&self) {
+        assert_eq!(self.inner.data(), &self.model[..], "The contents of the vector do not match the model!");
+        assert!(self.inner.consumed() <= self.inner.len(), "Consumed count exceeds the length!");
+        if let Some(limit) = self.inner.limit() {
+            assert!(self.inner.len() <= limit.get(), "The length exceeds the specified limit!");
+        }
+    }
+}
@@ -0,0 +1,328 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::{BufRead, Read, Result as IoResult, Error as IoError, ErrorKind};
+use std::num::NonZeroUsize;
+
+use crate::{SpareBuffer, SpareCapacity};
+use crate::buffer::enter_span;
+
+/// Default size of a single read, in bytes.
+const DEFAULT_CHUNK: usize = 8192;
+
+/// Default minimum size of a single read, in bytes.
+const DEFAULT_MIN_CHUNK: usize = 512;
+
+/// Default maximum size of a single read, in bytes.
+const DEFAULT_MAX_CHUNK: usize = 1048576;
+
+/// Controls the sizes of the reads that are issued by the *bulk* fill
+/// helpers, such as [`read_all_from()`](crate::SpareBuffer::read_all_from).
+///
+/// The first read requests `initial_chunk` bytes. While the source keeps
+/// delivering *full* reads, the size of the reads is doubled, up to
+/// `max_chunk` bytes, and after a *short* read, it is halved again, down to
+/// `min_chunk` bytes. This is similar to the probing of
+/// [`Read::read_to_end()`](std::io::Read::read_to_end), but the reads are
+/// **never** larger than the length limit permits. With a
+/// `read_ahead` factor greater than one, the "spare" buffer is allocated for
+/// `read_ahead` chunks at once, so that *several* reads hit the same
+/// allocation, which reduces the number of calls to `reserve()` on fast
+/// sources.
+///
+/// The policy of a [**`SpareBuffer`**](crate::SpareBuffer) can be set with
+/// [`with_chunk_policy()`](crate::SpareBuffer::with_chunk_policy).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ChunkPolicy {
+    min_chunk: NonZeroUsize,
+    initial_chunk: NonZeroUsize,
+    max_chunk: NonZeroUsize,
+    read_ahead: NonZeroUsize,
+}
+
+impl ChunkPolicy {
+    /// Creates a new *adaptive* **`ChunkPolicy`** with the given size of the
+    /// first read, in elements, and **no** read-ahead.
+    ///
+    /// The minimum chunk size defaults to `min(initial_chunk, 512)`, and the
+    /// maximum chunk size defaults to `max(initial_chunk, 1048576)`.
+    pub fn new(initial_chunk: NonZeroUsize) -> Self {
+        Self {
+            min_chunk: initial_chunk.min(NonZeroUsize::new(DEFAULT_MIN_CHUNK).unwrap()),
+            initial_chunk,
+            max_chunk: initial_chunk.max(NonZeroUsize::new(DEFAULT_MAX_CHUNK).unwrap()),
+            read_ahead: NonZeroUsize::MIN,
+        }
+    }
+
+    /// Creates a new **`ChunkPolicy`** that always reads `chunk` elements at
+    /// a time, i.e. **without** adapting the size of the reads.
+    pub fn fixed(chunk: NonZeroUsize) -> Self {
+        Self {
+            min_chunk: chunk,
+            initial_chunk: chunk,
+            max_chunk: chunk,
+            read_ahead: NonZeroUsize::MIN,
+        }
+    }
+
+    /// Sets the minimum size of a single read, in elements. The size of a
+    /// single read is raised to the minimum, if it is smaller.
+    pub fn with_min_chunk(mut self, min_chunk: NonZeroUsize) -> Self {
+        self.min_chunk = min_chunk;
+        self
+    }
+
+    /// Sets the maximum size of a single read, in elements.
+    pub fn with_max_chunk(mut self, max_chunk: NonZeroUsize) -> Self {
+        self.max_chunk = max_chunk;
+        self
+    }
+
+    /// Sets the read-ahead factor, i.e. the number of chunks that a *single*
+    /// allocation of a "spare" buffer covers.
+    pub fn with_read_ahead(mut self, read_ahead: NonZeroUsize) -> Self {
+        self.read_ahead = read_ahead;
+        self
+    }
+
+    /// Returns the minimum size of a single read, in elements.
+    pub fn min_chunk(&self) -> NonZeroUsize {
+        self.min_chunk
+    }
+
+    /// Returns the size of a single read, in elements.
+    pub fn initial_chunk(&self) -> NonZeroUsize {
+        self.initial_chunk
+    }
+
+    /// Returns the maximum size of a single read, in elements.
+    pub fn max_chunk(&self) -> NonZeroUsize {
+        self.max_chunk
+    }
+
+    /// Returns the read-ahead factor.
+    pub fn read_ahead(&self) -> NonZeroUsize {
+        self.read_ahead
+    }
+
+    /// Returns the size of the first read, in elements.
+    pub(crate) fn chunk(&self) -> NonZeroUsize {
+        self.clamp(self.initial_chunk)
+    }
+
+    /// Returns the size of the next read, in elements, after a read of
+    /// `chunk` elements returned `count` elements.
+    pub(crate) fn adapt(&self, chunk: NonZeroUsize, count: usize) -> NonZeroUsize {
+        if count >= chunk.get() {
+            self.clamp(chunk.saturating_mul(NonZeroUsize::new(2).unwrap()))
+        } else {
+            self.clamp(NonZeroUsize::new(chunk.get() / 2).unwrap_or(NonZeroUsize::MIN))
+        }
+    }
+
+    /// Clamps `chunk` to the range from `min_chunk` to `max_chunk`.
+    fn clamp(&self, chunk: NonZeroUsize) -> NonZeroUsize {
+        chunk.min(self.max_chunk).max(self.min_chunk)
+    }
+
+    /// Returns the length of the "spare" buffer to allocate for a read of
+    /// `chunk` elements.
+    pub(crate) fn allocation(&self, chunk: NonZeroUsize) -> NonZeroUsize {
+        chunk.saturating_mul(self.read_ahead)
+    }
+}
+
+impl Default for ChunkPolicy {
+    fn default() -> Self {
+        Self::new(NonZeroUsize::new(DEFAULT_CHUNK).unwrap())
+    }
+}
+
+impl<'a, B> SpareBuffer<'a, u8, B>
+where
+    B: SpareCapacity<u8>
+{
+    /// Reads *all* bytes from `reader`, until the end of the stream, into the
+    /// "spare" buffer, and commits them.
+    ///
+    /// The sizes of the reads are controlled by the
+    /// [**`ChunkPolicy`**](crate::ChunkPolicy) of this **`SpareBuffer`**, and
+    /// transient errors are handled according to its
+    /// [**`RetryPolicy`**](crate::RetryPolicy). Returns the number of bytes
+    /// that have been committed.
+    ///
+    /// # Errors
+    ///
+    /// Fails with an error of kind [`ErrorKind::OutOfMemory`], if the stream
+    /// is longer than the length limit permits. All bytes up to the limit
+    /// have been committed in that case. Errors returned by the `reader` are
+    /// passed through, and the "spare" buffer is discarded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `reader` reports more bytes than it was asked for.
+    pub fn read_all_from<R>(&mut self, reader: &mut R) -> IoResult<usize>
+    where
+        R: Read
+    {
+        self.read_chunks(reader, self.chunk_policy(), true)
+    }
+
+    /// Fills the buffer from `reader`, by reading chunks of `chunk` bytes
+    /// into the "spare" buffer and committing them, until the end of the
+    /// stream is reached, *or* the length limit has been reached.
+    ///
+    /// This is the usual allocate/read/commit loop. Reads that fail with
+    /// [`ErrorKind::Interrupted`](std::io::ErrorKind::Interrupted) are
+    /// retried, according to the [**`RetryPolicy`**](crate::RetryPolicy) of
+    /// this **`SpareBuffer`**. Unlike
+    /// [`read_all_from()`](Self::read_all_from), reaching the length limit is
+    /// **not** an error; the remainder of the stream is simply **not** read.
+    ///
+    /// Returns the number of bytes that have been committed.
+    ///
+    /// # Errors
+    ///
+    /// Errors returned by the `reader` are passed through. The bytes read up
+    /// to that point remain committed, and the "spare" buffer is discarded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `reader` reports more bytes than it was asked for.
+    pub fn fill_from<R>(&mut self, reader: &mut R, chunk: NonZeroUsize) -> IoResult<usize>
+    where
+        R: Read
+    {
+        self.read_chunks(reader, ChunkPolicy::fixed(chunk), false)
+    }
+
+    /// Reads *exactly* `length` bytes from `reader` into the "spare" buffer,
+    /// and commits them, e.g. the payload of a length-prefixed frame.
+    ///
+    /// Short reads are continued, and reads that fail with
+    /// [`ErrorKind::Interrupted`](std::io::ErrorKind::Interrupted) are
+    /// retried, as by [`Read::read_exact()`]. The bytes are committed
+    /// *atomically*, i.e. either all `length` bytes, or **nothing**.
+    ///
+    /// # Errors
+    ///
+    /// Fails with an error of kind [`ErrorKind::OutOfMemory`], if `length`
+    /// exceeds the length limit, *before* anything is read. Fails with an
+    /// error of kind [`ErrorKind::UnexpectedEof`], if the stream ends before
+    /// `length` bytes have been read. Nothing is committed in that case, i.e.
+    /// the bytes that have been read are *lost*. Other errors returned by
+    /// the `reader`, as well as errors returned by [`commit()`](Self::commit),
+    /// are passed through.
+    pub fn read_exact_into<R>(&mut self, reader: &mut R, length: NonZeroUsize) -> IoResult<()>
+    where
+        R: Read
+    {
+        if !self.fits_limit(length.get()) {
+            return Err(IoError::new(ErrorKind::OutOfMemory, "The new length exceeds the specified limit!"));
+        }
+        let spare = &mut self.try_spare_mut(length)?[..length.get()];
+        if let Err(error) = reader.read_exact(spare) {
+            self.discard_spare();
+            return Err(error);
+        }
+        self.commit(length.get())
+    }
+
+    /// Reads bytes from `reader` into the "spare" buffer, and commits them,
+    /// until the `delimiter` has been read, *or* until the end of the stream
+    /// or the length limit has been reached, exactly like
+    /// [`BufRead::read_until()`] does, but **without** an extra scratch
+    /// buffer. The delimiter, if found, is committed too.
+    ///
+    /// Reads that fail with
+    /// [`ErrorKind::Interrupted`](std::io::ErrorKind::Interrupted) are
+    /// retried. Returns `true` if the delimiter has been found. Otherwise,
+    /// the end of the stream, or the length limit, has been reached, which
+    /// can be distinguished via [`is_full()`](Self::is_full).
+    ///
+    /// # Errors
+    ///
+    /// Errors returned by the `reader`, as well as errors returned by
+    /// [`commit()`](Self::commit), are passed through. The bytes read up to
+    /// that point remain committed.
+    pub fn fill_until<R>(&mut self, reader: &mut R, delimiter: u8) -> IoResult<bool>
+    where
+        R: BufRead
+    {
+        let max_allocation = self.max_allocation().map_or(usize::MAX, NonZeroUsize::get);
+        loop {
+            let available = match reader.fill_buf() {
+                Ok(available) => available,
+                Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+                Err(error) => return Err(error),
+            };
+            let (found, length) = match available.iter().position(|byte| *byte == delimiter) {
+                Some(position) => (true, position + 1),
+                None => (false, available.len()),
+            };
+            let count = match NonZeroUsize::new(length.min(self.headroom()).min(max_allocation)) {
+                Some(count) => count,
+                None => return Ok(false), /* EOF or limit */
+            };
+            self.try_spare_mut(count)?[..count.get()].copy_from_slice(&available[..count.get()]);
+            self.commit(count.get())?;
+            reader.consume(count.get());
+            if found && count.get() == length {
+                return Ok(true);
+            }
+        }
+    }
+
+    /// Reads chunks from `reader`, as controlled by `policy`, until the end
+    /// of the stream, or until the length limit has been reached. In the
+    /// latter case, fails if `exact` is `true` and the stream has **not**
+    /// ended yet.
+    fn read_chunks<R>(&mut self, reader: &mut R, policy: ChunkPolicy, exact: bool) -> IoResult<usize>
+    where
+        R: Read
+    {
+        enter_span!("read_chunks", exact, start = self.len());
+        let retry = self.retry_policy();
+        let max_allocation = self.max_allocation().map_or(usize::MAX, NonZeroUsize::get);
+        let start = self.len();
+        let mut next_chunk = policy.chunk();
+        loop {
+            let headroom = self.headroom().min(max_allocation);
+            let count = match NonZeroUsize::new(next_chunk.get().min(headroom)) {
+                Some(chunk) => {
+                    let allocation = policy.allocation(chunk).get().min(headroom);
+                    let length = if self.spare_uninit().len() >= chunk.get() { chunk.get() } else { allocation };
+                    let spare = &mut self.try_spare_mut(NonZeroUsize::new(length).unwrap())?[..chunk.get()];
+                    let count = match retry.retry(|| reader.read(spare)) {
+                        Ok(count) if count <= chunk.get() => count,
+                        Ok(_) => {
+                            self.discard_spare();
+                            panic!("Read size exceeds the spare buffer!")
+                        },
+                        Err(error) => {
+                            self.discard_spare();
+                            return Err(error);
+                        },
+                    };
+                    self.commit(count)?;
+                    next_chunk = policy.adapt(chunk, count);
+                    count
+                },
+                None if !exact => 0,
+                None => {
+                    let mut probe = [0u8; 1];
+                    if retry.retry(|| reader.read(&mut probe))? > 0 {
+                        return Err(IoError::new(ErrorKind::OutOfMemory, "The stream length exceeds the specified limit!"));
+                    }
+                    0
+                },
+            };
+            if count == 0 {
+                break; /* EOF */
+            }
+        }
+        Ok(self.len() - start)
+    }
+}
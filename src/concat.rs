@@ -0,0 +1,61 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::{Result as IoResult, Error as IoError, ErrorKind};
+use std::num::NonZeroUsize;
+
+use crate::{Primitive, SpareBuffer};
+
+impl<'a, T> SpareBuffer<'a, T>
+where
+    T: Primitive
+{
+    /// Appends the contents of *all* `sources`, in order, to the underlying
+    /// vector.
+    ///
+    /// The total length is computed, and checked against the length limit,
+    /// *once* up front. Then a "spare" buffer of the total length is
+    /// allocated, all sources are copied into it, and it is committed. The
+    /// sources can be anything that can be borrowed as a `&[T]` slice, e.g.
+    /// other vectors or [**`SpareBuffer`**](crate::SpareBuffer)s.
+    ///
+    /// Returns the number of elements that have been committed.
+    ///
+    /// # Errors
+    ///
+    /// If a length limit has been specified, then this function will fail,
+    /// *before* copying any data, if the total length of the sources would
+    /// cause the length of the underlying vector to exceed the specified
+    /// limit.
+    pub fn concat_into<S>(&mut self, sources: &[S]) -> IoResult<usize>
+    where
+        S: AsRef<[T]>
+    {
+        let total = sources.iter().try_fold(0usize, |total, source| total.checked_add(source.as_ref().len()));
+        let total = match total {
+            Some(total) if self.fits_limit(total) => total,
+            _ => return Err(IoError::new(ErrorKind::OutOfMemory, "The total length exceeds the specified limit!")),
+        };
+        if let Some(length) = NonZeroUsize::new(total) {
+            let mut spare = &mut self.try_spare_mut(length)?[..total];
+            for source in sources {
+                let (head, tail) = spare.split_at_mut(source.as_ref().len());
+                head.copy_from_slice(source.as_ref());
+                spare = tail;
+            }
+            self.commit(total)?;
+        }
+        Ok(total)
+    }
+
+    /// Same as [`concat_into()`](Self::concat_into), but *consumes* the given
+    /// source vectors.
+    pub fn merge<I>(&mut self, sources: I) -> IoResult<usize>
+    where
+        I: IntoIterator<Item = Vec<T>>
+    {
+        let sources: Vec<Vec<T>> = sources.into_iter().collect();
+        self.concat_into(&sources)
+    }
+}
@@ -0,0 +1,216 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::collections::VecDeque;
+use std::io::{BufRead, Read, Result as IoResult, Error as IoError, ErrorKind};
+use std::mem::MaybeUninit;
+use std::num::NonZeroUsize;
+
+use crate::{Primitive, RetryPolicy};
+
+/// A wrapper around [**`VecDeque<T>`**](std::collections::VecDeque), for
+/// streaming consumers that read data at the tail, while consuming complete
+/// frames at the front, and keep the leftover elements.
+///
+/// A "spare" buffer is allocated at the *tail* of the deque, and committed
+/// elements can be [consumed](Self::consume) at the *front*, **without**
+/// ever moving the remaining elements. Because the storage of the deque may
+/// wrap around, the "spare" buffer is handed out as *two* slices, the second
+/// of which may be empty.
+///
+/// Note that `VecDeque` does **not** provide access to its *uninitialized*
+/// capacity. Therefore, the "spare" buffer is *zero-initialized* on
+/// allocation, which is always valid for a [**`Primitive`**](crate::Primitive)
+/// type, and the caller needs **no** `unsafe` code. Uncommitted elements are
+/// removed again, once the **`SpareDeque`** is dropped.
+pub struct SpareDeque<'a, T>
+where
+    T: Primitive
+{
+    deque: &'a mut VecDeque<T>,
+    limit: Option<NonZeroUsize>,
+    committed: usize,
+    allocated: bool,
+}
+
+impl<'a, T> SpareDeque<'a, T>
+where
+    T: Primitive
+{
+    /// Creates a new **`SpareDeque`** from an existing deque.
+    ///
+    /// An *optional* `limit` for the length of the deque can be specified.
+    /// The [`commit()`](Self::commit) fails, if it would exceed this limit.
+    pub fn from(deque: &'a mut VecDeque<T>, limit: Option<NonZeroUsize>) -> Self {
+        let committed = deque.len();
+        Self {
+            deque,
+            limit,
+            committed,
+            allocated: false,
+        }
+    }
+
+    /// Returns the number of "committed" elements that have **not** been
+    /// consumed yet.
+    pub fn len(&self) -> usize {
+        self.committed
+    }
+
+    /// Returns `true` if the deque contains no "committed" elements.
+    pub fn is_empty(&self) -> bool {
+        self.committed == 0
+    }
+
+    /// Returns the length limit, if a limit has been specified. Otherwise
+    /// `None` is returned.
+    pub fn limit(&self) -> Option<NonZeroUsize> {
+        self.limit
+    }
+
+    /// Returns the "committed" elements, in order, as two `&[T]` slices. The
+    /// second slice is empty, unless the data wraps around the end of the
+    /// storage.
+    pub fn data(&self) -> (&[T], &[T]) {
+        let (first, second) = self.deque.as_slices();
+        let split = self.committed.min(first.len());
+        (&first[..split], &second[..self.committed - split])
+    }
+
+    /// Allocates a "spare" buffer of the specified `length` at the tail of
+    /// the deque, as two `&mut[T]` slices. The second slice is empty, unless
+    /// the "spare" buffer wraps around the end of the storage.
+    ///
+    /// The "spare" buffer is *zero-initialized*. It is **not** considered to
+    /// be a valid part of the deque, until the [`commit()`](Self::commit)
+    /// function is called eventually. A previously allocated "spare" buffer
+    /// is discarded.
+    pub fn allocate_spare(&mut self, length: NonZeroUsize) -> (&mut[T], &mut[T]) {
+        self.discard();
+        let new_length = self.committed.checked_add(length.get()).expect("Numerical overflow! (new_length)");
+        self.deque.resize(new_length, unsafe { MaybeUninit::zeroed().assume_init() });
+        self.allocated = true;
+        let (first, second) = self.deque.as_mut_slices();
+        if second.len() >= length.get() {
+            let start = second.len() - length.get();
+            (&mut second[start..], &mut [])
+        } else {
+            let start = first.len() - (length.get() - second.len());
+            (&mut first[start..], second)
+        }
+    }
+
+    /// Commits the first `additional` elements of the "spare" buffer, i.e.
+    /// *appends* them at the tail of the deque **without** copying the data.
+    /// The remaining elements of the "spare" buffer are removed again. If the
+    /// "spare" buffer consists of two slices, the elements of the first
+    /// slice are committed first.
+    ///
+    /// This function always invalidates the current "spare" buffer.
+    ///
+    /// # Errors
+    ///
+    /// If a length limit has been specified, then this function will fail, if
+    /// adding `additional` more elements would cause the length of the deque
+    /// to exceed the specified limit. Nothing is committed in that case.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `additional` is greater than the length of the "spare"
+    /// buffer, or if **no** "spare" buffer was allocated before!
+    pub fn commit(&mut self, additional: usize) -> IoResult<()> {
+        assert!(std::mem::replace(&mut self.allocated, false), "No spare buffer allocated!");
+        assert!(additional <= self.deque.len() - self.committed, "Commit size exceeds available capacity!");
+        let new_length = self.committed + additional;
+        if new_length > self.limit.map_or(usize::MAX, NonZeroUsize::get) {
+            self.deque.truncate(self.committed);
+            return Err(IoError::new(ErrorKind::OutOfMemory, "The new length exceeds the specified limit!"));
+        }
+        self.deque.truncate(new_length);
+        self.committed = new_length;
+        Ok(())
+    }
+
+    /// Removes the first `amount` "committed" elements from the front of the
+    /// deque, e.g. once a complete frame has been parsed. The amount is
+    /// clamped to the number of committed elements.
+    ///
+    /// The current "spare" buffer is invalidated.
+    pub fn consume(&mut self, amount: usize) {
+        self.discard();
+        let amount = amount.min(self.committed);
+        self.deque.drain(..amount);
+        self.committed -= amount;
+    }
+
+    /// Removes the elements of the current "spare" buffer, if any.
+    fn discard(&mut self) {
+        if std::mem::replace(&mut self.allocated, false) {
+            self.deque.truncate(self.committed);
+        }
+    }
+}
+
+impl<'a> SpareDeque<'a, u8> {
+    /// Reads up to `length` bytes from `reader` into the "spare" buffer, and
+    /// commits the data. Reads that fail with
+    /// [`ErrorKind::Interrupted`](std::io::ErrorKind::Interrupted) are
+    /// retried.
+    ///
+    /// Only the *first* slice of the "spare" buffer is read into, so that a
+    /// single call to [`read()`](std::io::Read::read) is issued. Returns the
+    /// number of bytes that have been committed, which is `0` at the end of
+    /// the stream.
+    ///
+    /// # Errors
+    ///
+    /// Errors returned by the `reader`, as well as errors returned by
+    /// [`commit()`](Self::commit), are passed through.
+    pub fn read_from<R>(&mut self, reader: &mut R, length: NonZeroUsize) -> IoResult<usize>
+    where
+        R: Read
+    {
+        let (spare, _) = self.allocate_spare(length);
+        match RetryPolicy::new().retry(|| reader.read(spare)) {
+            Ok(count) => self.commit(count).map(|_| count),
+            Err(error) => {
+                self.discard();
+                Err(error)
+            },
+        }
+    }
+}
+
+impl<'a, T> Drop for SpareDeque<'a, T>
+where
+    T: Primitive
+{
+    fn drop(&mut self) {
+        self.discard();
+    }
+}
+
+/// Reads and consumes the "committed" bytes, at the front of the deque.
+impl<'a> Read for SpareDeque<'a, u8> {
+    fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
+        let (first, second) = self.data();
+        let count = buf.len().min(first.len() + second.len());
+        let split = count.min(first.len());
+        buf[..split].copy_from_slice(&first[..split]);
+        buf[split..count].copy_from_slice(&second[..count - split]);
+        self.consume(count);
+        Ok(count)
+    }
+}
+
+/// Returns the *contiguous* "committed" bytes at the front of the deque.
+impl<'a> BufRead for SpareDeque<'a, u8> {
+    fn fill_buf(&mut self) -> IoResult<&[u8]> {
+        Ok(self.data().0)
+    }
+
+    fn consume(&mut self, amount: usize) {
+        SpareDeque::consume(self, amount)
+    }
+}
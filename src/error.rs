@@ -0,0 +1,155 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::collections::TryReserveError;
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::io::{Error as IoError, ErrorKind};
+use std::num::NonZeroUsize;
+
+/// The error type that is returned, if a "spare" buffer can **not** be
+/// allocated.
+///
+/// Can be converted into an [`std::io::Error`] of kind
+/// [`ErrorKind::OutOfMemory`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AllocationError {
+    /// The requested length exceeds the maximum allocation size, as specified
+    /// by [`with_max_allocation()`](crate::SpareBuffer::with_max_allocation).
+    ExceedsMaxAllocation {
+        requested: NonZeroUsize,
+        max_allocation: NonZeroUsize,
+    },
+    /// The capacity could **not** be reserved, e.g. because the allocator
+    /// ran out of memory, or because the capacity would overflow.
+    Reserve(TryReserveError),
+    /// The reallocated storage could **not** be locked into RAM, as requested
+    /// by [`lock_memory()`](crate::SpareBuffer::lock_memory).
+    Lock(ErrorKind),
+    /// The allocation failed, because a failure was injected by a
+    /// `FaultInjector`.
+    #[cfg(feature = "test-util")]
+    Injected,
+}
+
+impl Display for AllocationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::ExceedsMaxAllocation { requested, max_allocation } =>
+                write!(f, "The allocation size {} exceeds the specified maximum of {}!", requested, max_allocation),
+            Self::Reserve(error) => write!(f, "Failed to reserve the capacity: {}", error),
+            Self::Lock(kind) => write!(f, "Failed to lock the memory: {}", kind),
+            #[cfg(feature = "test-util")]
+            Self::Injected => f.write_str("Injected allocation failure!"),
+        }
+    }
+}
+
+impl Error for AllocationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Reserve(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<AllocationError> for IoError {
+    fn from(error: AllocationError) -> Self {
+        IoError::new(ErrorKind::OutOfMemory, error)
+    }
+}
+
+/// The error type that is returned by `SpareBuffer::collect_body()`.
+#[cfg(feature = "http-body")]
+#[derive(Debug)]
+pub enum BodyError<E> {
+    /// The body exceeds the length limit. The body was **not** read to the
+    /// end.
+    TooLarge,
+    /// The body yielded an error.
+    Body(E),
+}
+
+#[cfg(feature = "http-body")]
+impl<E> BodyError<E> {
+    /// Returns the HTTP status code that is appropriate for responding to
+    /// this error, i.e. `413 Payload Too Large` or `400 Bad Request`.
+    pub fn status(&self) -> http::StatusCode {
+        match self {
+            Self::TooLarge => http::StatusCode::PAYLOAD_TOO_LARGE,
+            Self::Body(_) => http::StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+#[cfg(feature = "http-body")]
+impl<E> Display for BodyError<E>
+where
+    E: Display
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::TooLarge => f.write_str("The body length exceeds the specified limit!"),
+            Self::Body(error) => write!(f, "Failed to read the body: {}", error),
+        }
+    }
+}
+
+#[cfg(feature = "http-body")]
+impl<E> Error for BodyError<E>
+where
+    E: Error + 'static
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::TooLarge => None,
+            Self::Body(error) => Some(error),
+        }
+    }
+}
+
+/// The error type that is returned by `SpareBuffer::finish_verified()`, if
+/// the accumulated data does **not** match what was declared, e.g. because a
+/// transfer was truncated or corrupted.
+///
+/// Can be converted into an [`std::io::Error`] of kind
+/// [`ErrorKind::InvalidData`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum VerifyError {
+    /// The number of committed elements differs from the expected length.
+    LengthMismatch {
+        expected: usize,
+        actual: usize,
+    },
+    /// The CRC-32 checksum of the committed data differs from the expected
+    /// checksum.
+    #[cfg(feature = "crc32fast")]
+    ChecksumMismatch {
+        expected: u32,
+        actual: u32,
+    },
+}
+
+impl Display for VerifyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::LengthMismatch { expected, actual } =>
+                write!(f, "The length {} differs from the expected length {}!", actual, expected),
+            #[cfg(feature = "crc32fast")]
+            Self::ChecksumMismatch { expected, actual } =>
+                write!(f, "The checksum {:#010x} differs from the expected checksum {:#010x}!", actual, expected),
+        }
+    }
+}
+
+impl Error for VerifyError {}
+
+impl From<VerifyError> for IoError {
+    fn from(error: VerifyError) -> Self {
+        IoError::new(ErrorKind::InvalidData, error)
+    }
+}
@@ -0,0 +1,57 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::num::NonZeroUsize;
+
+/// Scripts *injected* allocation failures, in order to test the handling of
+/// out-of-memory conditions deterministically.
+///
+/// Every attempt of a [**`SpareBuffer`**](crate::SpareBuffer) to reserve a
+/// "spare" buffer counts as one call. An injected failure is reported as
+/// [`AllocationError::Injected`](crate::AllocationError::Injected), exactly
+/// like a real allocation failure would be reported. Use
+/// [`with_fault_injector()`](crate::SpareBuffer::with_fault_injector) to
+/// install a **`FaultInjector`**.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FaultInjector {
+    fail_on: NonZeroUsize,
+    persistent: bool,
+    calls: usize,
+}
+
+impl FaultInjector {
+    /// Creates a new **`FaultInjector`** that fails the `n`-th call *only*.
+    pub fn fail_nth(n: NonZeroUsize) -> Self {
+        Self {
+            fail_on: n,
+            persistent: false,
+            calls: 0,
+        }
+    }
+
+    /// Creates a new **`FaultInjector`** that fails the `n`-th call, and all
+    /// subsequent calls.
+    pub fn fail_from(n: NonZeroUsize) -> Self {
+        Self {
+            fail_on: n,
+            persistent: true,
+            calls: 0,
+        }
+    }
+
+    /// Returns the number of calls so far, including the failed ones.
+    pub fn calls(&self) -> usize {
+        self.calls
+    }
+
+    /// Counts a call, and returns `true` if it shall fail.
+    pub(crate) fn next_fails(&mut self) -> bool {
+        self.calls = self.calls.saturating_add(1);
+        if self.persistent {
+            self.calls >= self.fail_on.get()
+        } else {
+            self.calls == self.fail_on.get()
+        }
+    }
+}
@@ -0,0 +1,92 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::{Result as IoResult, Error as IoError, ErrorKind};
+use std::num::NonZeroUsize;
+use std::os::fd::{AsFd, AsRawFd};
+
+use crate::{SpareBuffer, SpareCapacity};
+
+impl<'a, B> SpareBuffer<'a, u8, B>
+where
+    B: SpareCapacity<u8>
+{
+    /// Reads up to `length` bytes from the file descriptor `fd` into the
+    /// "spare" buffer, by issuing [`read(2)`](https://man7.org/linux/man-pages/man2/read.2.html)
+    /// *directly*, and commits the data, e.g. for character devices that are
+    /// **not** accessed via [`File`](std::fs::File).
+    ///
+    /// Reads that fail with `EINTR`, i.e. with an error of kind
+    /// [`ErrorKind::Interrupted`], are handled according to the
+    /// [**`RetryPolicy`**](crate::RetryPolicy) of this **`SpareBuffer`**.
+    /// Returns the number of bytes that have been committed, which is `0` at
+    /// the end of the file. Note that the "spare" buffer is **not** aligned,
+    /// as required e.g. by `O_DIRECT`.
+    ///
+    /// Only available on Unix platforms.
+    ///
+    /// # Errors
+    ///
+    /// Fails with
+    /// [`AllocationError::ExceedsMaxAllocation`](crate::AllocationError::ExceedsMaxAllocation),
+    /// converted to an error of kind [`ErrorKind::OutOfMemory`], if `length`
+    /// exceeds the maximum allocation size. Errors returned by `read(2)`, as
+    /// well as errors returned by [`commit()`](Self::commit), are passed
+    /// through.
+    pub fn read_fd<F>(&mut self, fd: &F, length: NonZeroUsize) -> IoResult<usize>
+    where
+        F: AsFd
+    {
+        let fd = fd.as_fd().as_raw_fd();
+        self.fill_fd(length, |spare| unsafe {
+            libc::read(fd, spare.as_mut_ptr().cast(), spare.len())
+        })
+    }
+
+    /// Reads up to `length` bytes from the file descriptor `fd`, at the given
+    /// `offset`, into the "spare" buffer, by issuing
+    /// [`pread(2)`](https://man7.org/linux/man-pages/man2/pread.2.html)
+    /// *directly*, and commits the data. The file offset of `fd` is **not**
+    /// changed.
+    ///
+    /// See [`read_fd()`](Self::read_fd) for details. Only available on Unix
+    /// platforms.
+    ///
+    /// # Errors
+    ///
+    /// Fails with an error of kind [`ErrorKind::InvalidInput`], if `offset`
+    /// is out of range. Otherwise, fails with the same errors as
+    /// [`read_fd()`](Self::read_fd).
+    pub fn pread_fd<F>(&mut self, fd: &F, length: NonZeroUsize, offset: u64) -> IoResult<usize>
+    where
+        F: AsFd
+    {
+        let fd = fd.as_fd().as_raw_fd();
+        let offset = libc::off_t::try_from(offset).map_err(|_| IoError::new(ErrorKind::InvalidInput, "The offset is out of range!"))?;
+        self.fill_fd(length, |spare| unsafe {
+            libc::pread(fd, spare.as_mut_ptr().cast(), spare.len(), offset)
+        })
+    }
+
+    /// Allocates a "spare" buffer of the specified `length`, fills it by
+    /// calling the system call `f`, and commits the data, for *internal* use.
+    fn fill_fd<F>(&mut self, length: NonZeroUsize, mut f: F) -> IoResult<usize>
+    where
+        F: FnMut(&mut [u8]) -> libc::ssize_t
+    {
+        let retry = self.retry_policy();
+        let spare = &mut self.try_spare_mut(length)?[..length.get()];
+        let result = retry.retry(|| match f(spare) {
+            count if count < 0 => Err(IoError::last_os_error()),
+            count => Ok(count as usize),
+        });
+        match result {
+            Ok(count) => self.commit(count).map(|_| count),
+            Err(error) => {
+                self.discard_spare();
+                Err(error)
+            },
+        }
+    }
+}
@@ -0,0 +1,73 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::fs::File;
+use std::io::{Read, Result as IoResult, Error as IoError, ErrorKind};
+use std::num::NonZeroUsize;
+use std::path::Path;
+
+use crate::SpareBuffer;
+
+/// Size of the probe buffer that is used to detect the end of the file.
+const PROBE_SIZE: usize = 32;
+
+/// Minimum number of bytes to reserve, if the file *grows* while being read.
+const MIN_GROWTH: usize = 8192;
+
+/// Reads the entire contents of a file into a `Vec<u8>`.
+///
+/// This is similar to [`std::fs::read()`], except that an *optional* `limit`
+/// for the file size can be specified. The vector is pre-allocated *exactly*
+/// to the size of the file, as reported by the file system, but clamped to
+/// the `limit`. The file is then read into the "spare" capacity of the vector
+/// in large reads, so that **no** reallocation happens, unless the file grows
+/// while being read. Files that shrink while being read are handled as well.
+///
+/// # Errors
+///
+/// Fails with an error of kind [`ErrorKind::OutOfMemory`], if the size of the
+/// file exceeds the specified `limit`, or if the vector can **not** be
+/// allocated. Errors that occur while opening or reading the file are passed
+/// through.
+pub fn read_file<P>(path: P, limit: Option<NonZeroUsize>) -> IoResult<Vec<u8>>
+where
+    P: AsRef<Path>
+{
+    let mut file = File::open(path)?;
+    let max_length = limit.map_or(usize::MAX, NonZeroUsize::get);
+    let mut expected = file.metadata().ok()
+        .and_then(|metadata| usize::try_from(metadata.len()).ok())
+        .map_or(0, |length| length.min(max_length));
+
+    let mut vec = Vec::new();
+    vec.try_reserve_exact(expected).map_err(|_| IoError::new(ErrorKind::OutOfMemory, "Failed to allocate the buffer!"))?;
+    let mut buffer = SpareBuffer::from(&mut vec, limit);
+
+    let policy = buffer.retry_policy();
+    let mut probe = [0u8; PROBE_SIZE];
+    loop {
+        let count = match NonZeroUsize::new(expected.saturating_sub(buffer.len())) {
+            Some(remaining) => {
+                let spare = buffer.try_spare_mut(remaining)?;
+                let count = policy.retry(|| file.read(&mut spare[..remaining.get()]))?;
+                buffer.commit(count)?;
+                count
+            },
+            None => {
+                let count = policy.retry(|| file.read(&mut probe))?;
+                if let Some(length) = NonZeroUsize::new(count) {
+                    buffer.try_spare_mut(length)?[..count].copy_from_slice(&probe[..count]);
+                    buffer.commit(count)?;
+                    expected = buffer.len().saturating_mul(2).max(MIN_GROWTH).min(max_length);
+                }
+                count
+            },
+        };
+        if count == 0 {
+            break; /* EOF */
+        }
+    }
+
+    Ok(vec)
+}
@@ -0,0 +1,176 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::{Read, Result as IoResult, Error as IoError, ErrorKind};
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+use std::num::NonZeroUsize;
+
+use crate::{Primitive, RetryPolicy};
+use crate::primitive::{assume_init, foreign_mut};
+use crate::sanitizer::{mark_init, mark_uninit};
+
+/// The *shared* state machine of the fixed-capacity buffers, i.e. of the
+/// [**`SpareArray`**](crate::SpareArray) and the
+/// [**`SpareSlice`**](crate::SpareSlice), over a storage `S` of
+/// `MaybeUninit<T>` elements that **never** grows.
+pub(crate) struct Fixed<T, S> {
+    storage: S,
+    length: usize,
+    allocated: bool,
+    uninit: bool,
+    marker: PhantomData<T>,
+}
+
+impl<T, S> Fixed<T, S> {
+    /// Creates a new, empty **`Fixed`** buffer over the given `storage`.
+    pub(crate) const fn new(storage: S) -> Self {
+        Self {
+            storage,
+            length: 0,
+            allocated: false,
+            uninit: false,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the storage, e.g. in order to borrow it for a longer lifetime.
+    pub(crate) fn into_parts(self) -> (S, usize) {
+        (self.storage, self.length)
+    }
+}
+
+impl<T, S> Fixed<T, S>
+where
+    T: Primitive,
+    S: AsRef<[MaybeUninit<T>]> + AsMut<[MaybeUninit<T>]>
+{
+    pub(crate) fn capacity(&self) -> usize {
+        self.storage.as_ref().len()
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.length
+    }
+
+    pub(crate) fn free(&self) -> usize {
+        self.capacity() - self.length
+    }
+
+    pub(crate) fn data(&self) -> &[T] {
+        unsafe {
+            assume_init(&self.storage.as_ref()[..self.length])
+        }
+    }
+
+    /// Allocates a "spare" buffer of (at least) the specified `length`, i.e.
+    /// *all* of the free space.
+    pub(crate) fn spare_uninit(&mut self, length: NonZeroUsize) -> &mut[MaybeUninit<T>] {
+        self.allocate(length, false)
+    }
+
+    /// Allocates a "spare" buffer, exactly like
+    /// [`spare_uninit()`](Self::spare_uninit) does, but for the *public*
+    /// `allocate_spare_uninit()`, so that it can **not** be committed by
+    /// [`commit()`](Self::commit).
+    pub(crate) fn allocate_uninit(&mut self, length: NonZeroUsize) -> &mut[MaybeUninit<T>] {
+        self.allocate(length, true)
+    }
+
+    fn allocate(&mut self, length: NonZeroUsize, uninit: bool) -> &mut[MaybeUninit<T>] {
+        assert!(length.get() <= self.free(), "Spare buffer exceeds the fixed capacity!");
+        self.allocated = true;
+        self.uninit = uninit;
+        let spare = &mut self.storage.as_mut()[self.length..];
+        mark_uninit(spare.as_ptr(), spare.len());
+        spare
+    }
+
+    pub(crate) fn commit(&mut self, additional: usize) {
+        assert!(std::mem::replace(&mut self.allocated, false), "No spare buffer allocated!");
+        assert!(additional == 0 || !self.uninit, "Spare buffer was allocated uninitialized, use commit_written()!");
+        assert!(additional <= self.free(), "Commit size exceeds available capacity!");
+        mark_init(self.storage.as_ref()[self.length..].as_ptr(), additional);
+        self.length += additional;
+    }
+
+    /// Lets `f` initialize a "spare" buffer of (at least) the specified
+    /// `length`, and commits the *prefix* that `f` returns. Nothing is left
+    /// allocated, if `f` panics.
+    pub(crate) fn commit_written<F>(&mut self, length: NonZeroUsize, f: F) -> usize
+    where
+        F: for<'s> FnOnce(&'s mut [MaybeUninit<T>]) -> &'s [T]
+    {
+        assert!(length.get() <= self.free(), "Spare buffer exceeds the fixed capacity!");
+        self.allocated = false;
+        let spare = &mut self.storage.as_mut()[self.length..];
+        mark_uninit(spare.as_ptr(), spare.len());
+        let start = spare.as_ptr() as *const T;
+        let capacity = spare.len();
+        let written = f(spare);
+        assert!(written.as_ptr() == start && written.len() <= capacity, "The written slice is not a prefix of the spare buffer!");
+        let count = written.len();
+        mark_init(start, count);
+        self.length += count;
+        count
+    }
+
+    /// Discards the current "spare" buffer, if any, **without** committing
+    /// anything.
+    pub(crate) fn discard(&mut self) {
+        self.allocated = false;
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.allocated = false;
+        self.length = 0;
+    }
+
+    pub(crate) fn reserve(&mut self, additional: usize) {
+        assert!(additional <= self.free(), "Spare buffer exceeds the fixed capacity!");
+    }
+
+    pub(crate) fn as_ptr(&self) -> *const T {
+        self.storage.as_ref().as_ptr() as *const T
+    }
+
+    pub(crate) fn as_mut_ptr(&mut self) -> *mut T {
+        self.storage.as_mut().as_mut_ptr() as *mut T
+    }
+
+    pub(crate) unsafe fn set_len(&mut self, new_len: usize) {
+        self.length = new_len;
+    }
+}
+
+impl<S> Fixed<u8, S>
+where
+    S: AsRef<[MaybeUninit<u8>]> + AsMut<[MaybeUninit<u8>]>
+{
+    /// Reads up to `length` bytes from `reader` into the "spare" buffer, and
+    /// commits the data. The "spare" buffer is discarded, if the read fails.
+    pub(crate) fn read_from<R>(&mut self, reader: &mut R, length: NonZeroUsize, message: &'static str) -> IoResult<usize>
+    where
+        R: Read
+    {
+        if length.get() > self.free() {
+            return Err(IoError::new(ErrorKind::OutOfMemory, message));
+        }
+        let spare = foreign_mut(&mut self.spare_uninit(length)[..length.get()]);
+        match RetryPolicy::new().retry(|| reader.read(spare)) {
+            Ok(count) if count <= length.get() => {
+                self.commit(count);
+                Ok(count)
+            },
+            Ok(_) => {
+                self.discard();
+                panic!("Read size exceeds the spare buffer!")
+            },
+            Err(error) => {
+                self.discard();
+                Err(error)
+            },
+        }
+    }
+}
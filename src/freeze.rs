@@ -0,0 +1,76 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+#[cfg(feature = "bytes")]
+use std::io::Result as IoResult;
+use std::sync::Arc;
+
+use crate::{Primitive, SpareBuffer};
+
+impl<'a, T> SpareBuffer<'a, T>
+where
+    T: Primitive
+{
+    /// Splits off the first `count` "committed" elements as a shareable
+    /// `Arc<[T]>`, e.g. to ship a completed frame downstream, while the
+    /// buffer keeps accumulating.
+    ///
+    /// The frozen elements are copied into the `Arc<[T]>` *once*, and the
+    /// remaining elements are moved to the start of the underlying vector
+    /// *once*. Elements that have been consumed are counted as part of the
+    /// frozen prefix. The current "spare" buffer is invalidated.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` is greater than the number of "committed" elements.
+    pub fn freeze(&mut self, count: usize) -> Arc<[T]> {
+        assert!(count <= self.len(), "Freeze size exceeds the length!");
+        let frozen = Arc::from(&self.data()[..count]);
+        self.drain_front(count);
+        frozen
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl<'a> SpareBuffer<'a, u8> {
+    /// Splits off the first `count` "committed" bytes as a shareable
+    /// [`Bytes`](bytes::Bytes) handle. See [`freeze()`](Self::freeze) for
+    /// details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` is greater than the number of "committed" elements.
+    pub fn freeze_bytes(&mut self, count: usize) -> bytes::Bytes {
+        assert!(count <= self.len(), "Freeze size exceeds the length!");
+        let frozen = bytes::Bytes::copy_from_slice(&self.data()[..count]);
+        self.drain_front(count);
+        frozen
+    }
+
+    /// Splits off the first `count` "committed" bytes as a shareable
+    /// [`Bytes`](bytes::Bytes) handle, **without** copying them.
+    ///
+    /// Unlike [`freeze_bytes()`](Self::freeze_bytes), the underlying vector
+    /// itself is converted into the `Bytes`, and only the *remaining* bytes
+    /// are copied into a new vector, which then continues to be filled. This
+    /// is cheap, if the frozen prefix is large and the remainder is small,
+    /// e.g. when a network pipeline hands off completed frames. Elements that
+    /// have been consumed are counted as part of the frozen prefix. The
+    /// current "spare" buffer is invalidated.
+    ///
+    /// # Errors
+    ///
+    /// If the memory is [locked](Self::lock_memory), then this function will
+    /// fail, if the new vector can **not** be locked. Errors returned by the
+    /// operating system are passed through. Nothing is split off in that
+    /// case.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` is greater than the number of "committed" elements.
+    pub fn split_to(&mut self, count: usize) -> IoResult<bytes::Bytes> {
+        assert!(count <= self.len(), "Split size exceeds the length!");
+        self.split_front(count).map(bytes::Bytes::from)
+    }
+}
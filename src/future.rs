@@ -0,0 +1,138 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+#[cfg(not(feature = "safe-only"))]
+use std::future::Future;
+use std::io::Result as IoResult;
+use std::num::NonZeroUsize;
+#[cfg(not(feature = "safe-only"))]
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use crate::{AllocationError, Primitive, SpareBuffer, SpareCapacity};
+
+/// A future that fills the "spare" buffer of a
+/// [**`SpareBuffer`**](crate::SpareBuffer) *asynchronously*, and commits the
+/// data once it is ready.
+///
+/// The future owns the whole allocate/fill/commit interaction. It does **not**
+/// hold on to the "spare" buffer between calls to `poll()`. Instead, the
+/// "spare" buffer is *re-derived* on every call, so the future does **not**
+/// contain any self-references and can be moved around freely.
+///
+/// Use [`SpareBuffer::fill_with()`](crate::SpareBuffer::fill_with) to create
+/// a new **`FillFuture`**.
+#[cfg(not(feature = "safe-only"))]
+pub struct FillFuture<'b, 'a, T, F, B = Vec<T>>
+where
+    T: Primitive,
+    F: FnMut(&mut Context<'_>, &mut [T]) -> Poll<IoResult<usize>>,
+    B: SpareCapacity<T>
+{
+    buffer: &'b mut SpareBuffer<'a, T, B>,
+    length: NonZeroUsize,
+    poll_fn: F,
+}
+
+impl<'a, T, B> SpareBuffer<'a, T, B>
+where
+    T: Primitive,
+    B: SpareCapacity<T>
+{
+    /// Attempts to fill a "spare" buffer of the specified `length`, by calling
+    /// `poll_fn` with the given [`Context`] and a `&mut[T]` slice of exactly
+    /// `length` elements.
+    ///
+    /// The `poll_fn` is expected to behave like
+    /// [`AsyncRead::poll_read()`](https://docs.rs/futures/latest/futures/io/trait.AsyncRead.html#tymethod.poll_read),
+    /// i.e. return the number of elements that have been written to the start
+    /// of the slice, once the data is ready. These elements are then
+    /// committed. If `Poll::Pending` is returned, nothing is committed and the
+    /// function should be called again, when the task is woken up.
+    ///
+    /// This is the building block for implementing *hand-written* futures
+    /// or `AsyncRead` wrappers. It does **not** require any state to be kept
+    /// between two calls, as the "spare" buffer is allocated anew each time.
+    ///
+    /// # Errors
+    ///
+    /// Errors returned by `poll_fn`, as well as errors returned by
+    /// [`commit()`](Self::commit), are passed through. Errors of kind
+    /// [`ErrorKind::Interrupted`](std::io::ErrorKind::Interrupted) are retried
+    /// according to the [retry policy](Self::retry_policy).
+    ///
+    /// Not available with the **`safe-only`** feature.
+    #[cfg(not(feature = "safe-only"))]
+    pub fn poll_fill<F>(&mut self, cx: &mut Context<'_>, length: NonZeroUsize, poll_fn: F) -> Poll<IoResult<usize>>
+    where
+        F: FnMut(&mut Context<'_>, &mut [T]) -> Poll<IoResult<usize>>
+    {
+        self.poll_fill_spare(cx, length, poll_fn)
+    }
+
+    /// Attempts to fill a "spare" buffer, for *internal* use. See
+    /// [`poll_fill()`](Self::poll_fill) for details.
+    #[cfg_attr(all(feature = "safe-only", not(feature = "futures-io")), allow(dead_code))]
+    pub(crate) fn poll_fill_spare<F>(&mut self, cx: &mut Context<'_>, length: NonZeroUsize, mut poll_fn: F) -> Poll<IoResult<usize>>
+    where
+        F: FnMut(&mut Context<'_>, &mut [T]) -> Poll<IoResult<usize>>
+    {
+        let policy = self.retry_policy();
+        let mut attempts = 0usize;
+        loop {
+            attempts += 1;
+            let poll = self.guarded(|buffer| {
+                let spare = &mut buffer.try_spare_mut(length)?[..length.get()];
+                Ok::<_, AllocationError>(poll_fn(cx, spare))
+            })?;
+            return match poll {
+                Poll::Ready(Ok(count)) => Poll::Ready(self.commit(count).map(|_| count)),
+                Poll::Ready(Err(error)) if policy.should_retry(&error, attempts, false) => {
+                    self.discard_spare();
+                    continue;
+                },
+                Poll::Ready(Err(error)) => {
+                    self.discard_spare();
+                    Poll::Ready(Err(error))
+                },
+                Poll::Pending => Poll::Pending,
+            };
+        }
+    }
+
+    /// Returns a [**`FillFuture`**](crate::FillFuture) that fills a "spare"
+    /// buffer of the specified `length` by calling
+    /// [`poll_fill()`](Self::poll_fill) until the data is ready.
+    ///
+    /// The future resolves to the number of elements that have been
+    /// committed.
+    ///
+    /// Not available with the **`safe-only`** feature.
+    #[cfg(not(feature = "safe-only"))]
+    pub fn fill_with<F>(&mut self, length: NonZeroUsize, poll_fn: F) -> FillFuture<'_, 'a, T, F, B>
+    where
+        F: FnMut(&mut Context<'_>, &mut [T]) -> Poll<IoResult<usize>>
+    {
+        FillFuture {
+            buffer: self,
+            length,
+            poll_fn,
+        }
+    }
+}
+
+#[cfg(not(feature = "safe-only"))]
+impl<'b, 'a, T, F, B> Future for FillFuture<'b, 'a, T, F, B>
+where
+    T: Primitive,
+    F: FnMut(&mut Context<'_>, &mut [T]) -> Poll<IoResult<usize>> + Unpin,
+    B: SpareCapacity<T>
+{
+    type Output = IoResult<usize>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        this.buffer.poll_fill(cx, this.length, &mut this.poll_fn)
+    }
+}
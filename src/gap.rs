@@ -0,0 +1,259 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::{Read, Result as IoResult};
+use std::mem::MaybeUninit;
+use std::num::NonZeroUsize;
+
+use crate::{Primitive, RetryPolicy};
+use crate::primitive::{assume_init, assume_init_mut, foreign_mut};
+use crate::sanitizer::{mark_init, mark_uninit};
+
+/// Minimum capacity of the storage, once it has to grow.
+const MIN_CAPACITY: usize = 64;
+
+/// A *gap buffer*, for editor-like workloads that insert at a *cursor* rather
+/// than at the end.
+///
+/// The unused capacity is kept as a *gap* at the cursor position, which is
+/// handed out as the "spare" buffer. Insertions can therefore be filled
+/// *directly*, e.g. by reading from a file or stream, and are then committed
+/// at the cursor, **without** moving the data after the cursor. Moving the
+/// cursor moves the gap, by copying the elements in between.
+pub struct SpareGap<T>
+where
+    T: Primitive
+{
+    storage: Box<[MaybeUninit<T>]>,
+    gap_start: usize,
+    gap_end: usize,
+    allocated: bool,
+}
+
+impl<T> SpareGap<T>
+where
+    T: Primitive
+{
+    /// Creates a new, empty **`SpareGap`** with the given initial
+    /// `capacity`.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            storage: Box::new_uninit_slice(capacity),
+            gap_start: 0,
+            gap_end: capacity,
+            allocated: false,
+        }
+    }
+
+    /// Creates a new **`SpareGap`** that contains the elements of `data`,
+    /// with the cursor at the *end*.
+    pub fn from_slice(data: &[T]) -> Self {
+        let mut gap = Self::with_capacity(data.len());
+        gap.insert(data);
+        gap
+    }
+
+    /// Returns the number of "committed" elements, before *and* after the
+    /// cursor.
+    pub fn len(&self) -> usize {
+        self.storage.len() - self.gap_len()
+    }
+
+    /// Returns `true` if the **`SpareGap`** contains no "committed"
+    /// elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the *total* capacity, i.e. the number of "committed" elements
+    /// plus the length of the gap.
+    pub fn capacity(&self) -> usize {
+        self.storage.len()
+    }
+
+    /// Returns the current cursor position, i.e. the number of "committed"
+    /// elements *before* the gap.
+    pub fn cursor(&self) -> usize {
+        self.gap_start
+    }
+
+    /// Moves the cursor, and thus the gap, to `position`. The elements
+    /// between the old and the new position are copied.
+    ///
+    /// The current "spare" buffer is invalidated.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `position` is greater than the number of "committed"
+    /// elements.
+    pub fn set_cursor(&mut self, position: usize) {
+        assert!(position <= self.len(), "Cursor position exceeds the length!");
+        self.allocated = false;
+        let gap_len = self.gap_len();
+        if position < self.gap_start {
+            self.storage.copy_within(position..self.gap_start, position + gap_len);
+        } else if position > self.gap_start {
+            self.storage.copy_within(self.gap_end..position + gap_len, self.gap_start);
+        }
+        self.gap_start = position;
+        self.gap_end = position + gap_len;
+    }
+
+    /// Returns the "committed" elements *before* and *after* the cursor, as
+    /// two `&[T]` slices.
+    pub fn data(&self) -> (&[T], &[T]) {
+        unsafe {
+            (assume_init(&self.storage[..self.gap_start]), assume_init(&self.storage[self.gap_end..]))
+        }
+    }
+
+    /// Copies *all* "committed" elements into a new vector.
+    pub fn to_vec(&self) -> Vec<T> {
+        let (before, after) = self.data();
+        let mut vec = Vec::with_capacity(self.len());
+        vec.extend_from_slice(before);
+        vec.extend_from_slice(after);
+        vec
+    }
+
+    /// Allocates a "spare" buffer of (at least) the specified `length` at the
+    /// cursor position, by *growing* the gap, if it is too small.
+    ///
+    /// Returns a `&mut[T]` slice of the *whole* gap. No guarantees are
+    /// provided about the *initial* contents of the buffer! The "spare" buffer
+    /// is **not** considered to be a valid part of the **`SpareGap`**, until
+    /// the [`commit()`](Self::commit) function is called eventually.
+    ///
+    /// Not available with the **`safe-only`** feature.
+    #[cfg(not(feature = "safe-only"))]
+    pub fn allocate_spare(&mut self, length: NonZeroUsize) -> &mut[T] {
+        self.spare_mut(length)
+    }
+
+    /// Allocates a "spare" buffer of the specified `length`, for *internal*
+    /// use. See [`allocate_spare()`](Self::allocate_spare) for details.
+    fn spare_mut(&mut self, length: NonZeroUsize) -> &mut[T] {
+        unsafe {
+            assume_init_mut(self.spare_uninit(length))
+        }
+    }
+
+    /// Allocates a "spare" buffer of the specified `length`, as a
+    /// `&mut[MaybeUninit<T>]` slice, for *internal* use.
+    fn spare_uninit(&mut self, length: NonZeroUsize) -> &mut[MaybeUninit<T>] {
+        if self.gap_len() < length.get() {
+            self.grow(length.get());
+        }
+        self.allocated = true;
+        let spare = &mut self.storage[self.gap_start..self.gap_end];
+        mark_uninit(spare.as_ptr(), spare.len());
+        spare
+    }
+
+    /// Commits the first `additional` elements of the "spare" buffer, i.e.
+    /// *inserts* them at the cursor, which is then advanced past the inserted
+    /// elements. The data after the cursor is **not** moved.
+    ///
+    /// All elements to be committed **must** have been initialized, exactly
+    /// like for [`SpareBuffer::commit()`](crate::SpareBuffer::commit). This
+    /// function always invalidates the current "spare" buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `additional` is greater than the length of the gap, or if
+    /// **no** "spare" buffer was allocated before!
+    pub fn commit(&mut self, additional: usize) {
+        assert!(std::mem::replace(&mut self.allocated, false), "No spare buffer allocated!");
+        assert!(additional <= self.gap_len(), "Commit size exceeds available capacity!");
+        mark_init(self.storage[self.gap_start..].as_ptr(), additional);
+        self.gap_start += additional;
+    }
+
+    /// Discards the current "spare" buffer, if any, **without** committing
+    /// anything.
+    fn discard(&mut self) {
+        self.allocated = false;
+    }
+
+    /// Inserts a copy of `data` at the cursor, which is then advanced past
+    /// the inserted elements.
+    pub fn insert(&mut self, data: &[T]) {
+        if let Some(length) = NonZeroUsize::new(data.len()) {
+            self.spare_mut(length)[..data.len()].copy_from_slice(data);
+            self.commit(data.len());
+        }
+    }
+
+    /// Removes up to `count` elements *before* the cursor, i.e. like the
+    /// backspace key does. Returns the number of elements that were removed.
+    ///
+    /// The current "spare" buffer is invalidated.
+    pub fn remove_before(&mut self, count: usize) -> usize {
+        self.allocated = false;
+        let count = count.min(self.gap_start);
+        self.gap_start -= count;
+        count
+    }
+
+    /// Removes up to `count` elements *after* the cursor, i.e. like the
+    /// delete key does. Returns the number of elements that were removed.
+    ///
+    /// The current "spare" buffer is invalidated.
+    pub fn remove_after(&mut self, count: usize) -> usize {
+        self.allocated = false;
+        let count = count.min(self.storage.len() - self.gap_end);
+        self.gap_end += count;
+        count
+    }
+
+    /// Returns the length of the gap.
+    fn gap_len(&self) -> usize {
+        self.gap_end - self.gap_start
+    }
+
+    /// Reallocates the storage, so that the gap can hold *at least*
+    /// `required` elements.
+    fn grow(&mut self, required: usize) {
+        let length = self.len();
+        let capacity = length.checked_add(required).expect("Numerical overflow! (capacity)")
+            .max(self.storage.len().saturating_mul(2))
+            .max(MIN_CAPACITY);
+        let mut storage = Box::new_uninit_slice(capacity);
+        let after = self.storage.len() - self.gap_end;
+        storage[..self.gap_start].copy_from_slice(&self.storage[..self.gap_start]);
+        storage[capacity - after..].copy_from_slice(&self.storage[self.gap_end..]);
+        self.storage = storage;
+        self.gap_end = capacity - after;
+    }
+}
+
+impl SpareGap<u8> {
+    /// Reads up to `length` bytes from `reader` *directly* into the gap, and
+    /// inserts them at the cursor. Reads that fail with
+    /// [`ErrorKind::Interrupted`](std::io::ErrorKind::Interrupted) are
+    /// retried.
+    ///
+    /// Returns the number of bytes that have been inserted, which is `0` at
+    /// the end of the stream.
+    ///
+    /// # Errors
+    ///
+    /// Errors returned by the `reader` are passed through.
+    pub fn read_from<R>(&mut self, reader: &mut R, length: NonZeroUsize) -> IoResult<usize>
+    where
+        R: Read
+    {
+        let spare = foreign_mut(&mut self.spare_uninit(length)[..length.get()]);
+        match RetryPolicy::new().retry(|| reader.read(spare)) {
+            Ok(count) => {
+                self.commit(count);
+                Ok(count)
+            },
+            Err(error) => {
+                self.discard();
+                Err(error)
+            },
+        }
+    }
+}
@@ -0,0 +1,53 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::Result as IoResult;
+use std::mem::size_of_val;
+use std::num::NonZeroUsize;
+use std::slice::from_raw_parts;
+
+use crate::{Primitive, SpareBuffer};
+
+impl<'a, T> SpareBuffer<'a, T>
+where
+    T: Primitive
+{
+    /// Uploads all "committed" elements to the given [`wgpu::Buffer`],
+    /// starting at `offset`, by calling
+    /// [`Queue::write_buffer()`](wgpu::Queue::write_buffer).
+    ///
+    /// The usual requirements of `write_buffer()` apply, i.e. `offset` and
+    /// the size of the committed data, in bytes, must be multiples of
+    /// [`wgpu::COPY_BUFFER_ALIGNMENT`], and the destination buffer must have
+    /// been created with [`wgpu::BufferUsages::COPY_DST`].
+    pub fn upload_to_wgpu(&self, queue: &wgpu::Queue, buffer: &wgpu::Buffer, offset: wgpu::BufferAddress) {
+        let data = self.data();
+        let bytes = unsafe {
+            from_raw_parts(data.as_ptr() as *const u8, size_of_val(data))
+        };
+        queue.write_buffer(buffer, offset, bytes);
+    }
+}
+
+impl<'a> SpareBuffer<'a, u8> {
+    /// Fills the "spare" buffer from a *mapped* readback buffer, e.g. as
+    /// returned by [`BufferSlice::get_mapped_range()`], and commits the data.
+    ///
+    /// Returns the number of bytes that have been committed.
+    ///
+    /// # Errors
+    ///
+    /// Fails with the same errors as [`commit()`](Self::commit).
+    ///
+    /// [`BufferSlice::get_mapped_range()`]: wgpu::BufferSlice::get_mapped_range
+    pub fn fill_from_wgpu(&mut self, view: &wgpu::BufferView) -> IoResult<usize> {
+        let length = match NonZeroUsize::new(view.len()) {
+            Some(length) => length,
+            None => return Ok(0),
+        };
+        self.try_spare_mut(length)?[..length.get()].copy_from_slice(view);
+        self.commit(length.get())?;
+        Ok(length.get())
+    }
+}
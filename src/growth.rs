@@ -0,0 +1,53 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::num::NonZeroUsize;
+
+/// Controls how much capacity a [**`SpareBuffer`**](crate::SpareBuffer)
+/// reserves, when the "spare" capacity of the underlying vector is **not**
+/// sufficient for a new "spare" buffer.
+///
+/// This allows the frequency of reallocations to be traded against the
+/// memory footprint, per workload. The policy of a **`SpareBuffer`** can be
+/// set with [`with_growth_policy()`](crate::SpareBuffer::with_growth_policy).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GrowthPolicy {
+    /// Grow the capacity *exponentially*, i.e. by (at least) doubling it, as
+    /// [`Vec::reserve()`](std::vec::Vec::reserve) does. This is the default.
+    #[default]
+    Doubling,
+    /// Grow the capacity to *exactly* the required capacity, as
+    /// [`Vec::reserve_exact()`](std::vec::Vec::reserve_exact) does.
+    Exact,
+    /// Grow the capacity to the required capacity, rounded up to a multiple
+    /// of the given number of elements.
+    Increment(NonZeroUsize),
+    /// Grow the capacity to the required capacity, rounded up, so that the
+    /// size of the storage is a multiple of the given page size, in bytes.
+    PageRounded(NonZeroUsize),
+}
+
+impl GrowthPolicy {
+    /// Returns the *total* capacity to reserve, in elements, for the given
+    /// `required` capacity, if the capacity is to be reserved *exactly*.
+    /// Returns `None`, if the capacity is to be grown exponentially.
+    pub(crate) fn capacity(&self, required: usize, element_size: usize) -> Option<usize> {
+        match self {
+            Self::Doubling => None,
+            Self::Exact => Some(required),
+            Self::Increment(increment) => Some(round_up(required, increment.get())),
+            Self::PageRounded(_) if element_size == 0 => Some(required),
+            Self::PageRounded(page_size) => {
+                let size = required.saturating_mul(element_size);
+                Some((round_up(size, page_size.get()) / element_size).max(required))
+            },
+        }
+    }
+}
+
+/// Rounds `value` up to the next multiple of `multiple`, saturating at
+/// `usize::MAX`, so that reserving the capacity fails instead.
+fn round_up(value: usize, multiple: usize) -> usize {
+    value.div_ceil(multiple).saturating_mul(multiple)
+}
@@ -0,0 +1,142 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::Result as IoResult;
+use std::num::NonZeroUsize;
+use std::ops::{Deref, DerefMut};
+use std::slice::{from_raw_parts, from_raw_parts_mut};
+
+use crate::{Primitive, SpareBuffer, SpareCapacity};
+use crate::buffer::log_event;
+
+/// A handle to the allocated "spare" buffer of a
+/// [**`SpareBuffer`**](crate::SpareBuffer), which **must** be passed to
+/// [`commit()`](Self::commit) in order to commit the data.
+///
+/// The handle dereferences to a `&mut[T]` slice of exactly the requested
+/// length. Because the handle *borrows* the **`SpareBuffer`** and is consumed
+/// by the commit, committing without an allocation, or committing a stale
+/// allocation, are compile errors rather than runtime panics. Dropping the
+/// handle without committing it *discards* the "spare" buffer.
+///
+/// If the **`log`** or the **`tracing`** feature is enabled, a warning is
+/// logged, if the handle is dropped without having been either committed or
+/// explicitly [discarded](Self::discard), e.g. because of an early return,
+/// so that the silent loss of an allocation can be tracked down.
+///
+/// Use [`SpareBuffer::allocate_handle()`](crate::SpareBuffer::allocate_handle)
+/// to obtain a new **`SpareHandle`**.
+pub struct SpareHandle<'b, 'a, T, B = Vec<T>>
+where
+    T: Primitive,
+    B: SpareCapacity<T>
+{
+    buffer: &'b mut SpareBuffer<'a, T, B>,
+    length: NonZeroUsize,
+    finished: bool,
+}
+
+impl<'a, T, B> SpareBuffer<'a, T, B>
+where
+    T: Primitive,
+    B: SpareCapacity<T>
+{
+    /// Allocates a "spare" buffer of the specified `length`, and returns a
+    /// [**`SpareHandle`**](crate::SpareHandle) for it.
+    ///
+    /// See [`allocate_spare()`](Self::allocate_spare) for details. Not
+    /// available with the **`safe-only`** feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `length` exceeds the maximum allocation size, as specified
+    /// by [`with_max_allocation()`](Self::with_max_allocation).
+    pub fn allocate_handle(&mut self, length: NonZeroUsize) -> SpareHandle<'_, 'a, T, B> {
+        self.spare_mut(length);
+        SpareHandle {
+            buffer: self,
+            length,
+            finished: false,
+        }
+    }
+}
+
+impl<'b, 'a, T, B> SpareHandle<'b, 'a, T, B>
+where
+    T: Primitive,
+    B: SpareCapacity<T>
+{
+    /// Returns the length of the "spare" buffer.
+    pub fn len(&self) -> NonZeroUsize {
+        self.length
+    }
+
+    /// Commits the first `additional` elements of the "spare" buffer,
+    /// consuming the handle. See
+    /// [`SpareBuffer::commit()`](crate::SpareBuffer::commit) for details.
+    ///
+    /// # Errors
+    ///
+    /// Fails with the same errors as
+    /// [`SpareBuffer::commit()`](crate::SpareBuffer::commit).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `additional` is greater than the length of the "spare"
+    /// buffer.
+    pub fn commit(mut self, additional: usize) -> IoResult<()> {
+        assert!(additional <= self.length.get(), "Commit size exceeds the length of the allocation!");
+        self.finished = true;
+        self.buffer.commit(additional)
+    }
+
+    /// Discards the "spare" buffer **without** committing any elements,
+    /// consuming the handle.
+    pub fn discard(mut self) {
+        self.finished = true;
+        self.buffer.discard_spare();
+    }
+}
+
+impl<'b, 'a, T, B> Deref for SpareHandle<'b, 'a, T, B>
+where
+    T: Primitive,
+    B: SpareCapacity<T>
+{
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        unsafe {
+            from_raw_parts(self.buffer.spare_ptr(), self.length.get())
+        }
+    }
+}
+
+impl<'b, 'a, T, B> DerefMut for SpareHandle<'b, 'a, T, B>
+where
+    T: Primitive,
+    B: SpareCapacity<T>
+{
+    fn deref_mut(&mut self) -> &mut [T] {
+        let length = self.length.get();
+        unsafe {
+            from_raw_parts_mut(self.buffer.spare_uninit().as_mut_ptr().cast(), length)
+        }
+    }
+}
+
+impl<'b, 'a, T, B> Drop for SpareHandle<'b, 'a, T, B>
+where
+    T: Primitive,
+    B: SpareCapacity<T>
+{
+    fn drop(&mut self) {
+        if !self.finished {
+            if !std::thread::panicking() {
+                log_event!(warn, "A `SpareHandle` of length {} was dropped without being committed or discarded!", self.length);
+            }
+            self.buffer.discard_spare();
+        }
+    }
+}
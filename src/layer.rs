@@ -0,0 +1,91 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::future::Future;
+use std::mem::replace;
+use std::num::NonZeroUsize;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use http::{Request, Response};
+use http_body::Body;
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::SpareBuffer;
+
+/// A `tower` layer that *buffers* the body of each request, with a hard
+/// length limit, before passing the request on to the inner service.
+///
+/// The inner service receives a `Request<Vec<u8>>` that holds the committed
+/// bytes. If the body exceeds the limit, an *empty* response with the status
+/// `413 Payload Too Large` is returned, **without** calling the inner service.
+/// If the body yields an error, the status is `400 Bad Request`. Different
+/// limits for different routes can be applied by adding one layer per route.
+#[derive(Clone, Copy, Debug)]
+pub struct BufferBodyLayer {
+    limit: Option<NonZeroUsize>,
+}
+
+impl BufferBodyLayer {
+    /// Creates a new **`BufferBodyLayer`** with the given *optional* `limit`
+    /// for the length of request bodies.
+    pub fn new(limit: Option<NonZeroUsize>) -> Self {
+        Self {
+            limit,
+        }
+    }
+}
+
+impl<S> Layer<S> for BufferBodyLayer {
+    type Service = BufferBody<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        BufferBody {
+            inner,
+            limit: self.limit,
+        }
+    }
+}
+
+/// The `tower` service that is created by
+/// [**`BufferBodyLayer`**](crate::BufferBodyLayer).
+#[derive(Clone, Debug)]
+pub struct BufferBody<S> {
+    inner: S,
+    limit: Option<NonZeroUsize>,
+}
+
+impl<S, B, R> Service<Request<B>> for BufferBody<S>
+where
+    S: Service<Request<Vec<u8>>, Response = Response<R>> + Clone + Send + 'static,
+    S::Future: Send,
+    B: Body + Send + 'static,
+    B::Data: Send,
+    R: Default
+{
+    type Response = Response<R>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let clone = self.inner.clone();
+        let mut inner = replace(&mut self.inner, clone);
+        let limit = self.limit;
+        Box::pin(async move {
+            let (parts, body) = request.into_parts();
+            let mut data = Vec::new();
+            if let Err(error) = SpareBuffer::from(&mut data, limit).collect_body(body).await {
+                let mut response = Response::new(R::default());
+                *response.status_mut() = error.status();
+                return Ok(response);
+            }
+            inner.call(Request::from_parts(parts, data)).await
+        })
+    }
+}
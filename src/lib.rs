@@ -28,58 +28,281 @@
 //! **must** have been initialized, or the contents of the underlying vector
 //! are ***unspecified*** after the commit!
 //! 
+//! If user code panics while a "spare" buffer is allocated by one of the
+//! functions that take a closure, such as `commit_verified()`,
+//! `lend_spare()`, `branded()` or `poll_fill()`, or while a
+//! [**`SpareWriter`**](crate::SpareWriter) is alive, the "spare" buffer is
+//! *discarded*. After catching the panic, the **`SpareBuffer`** can be used
+//! again, but a new "spare" buffer must be allocated before committing.
+//! 
+//! The underlying storage defaults to a `Vec<T>`, but any backend that
+//! implements the [**`SpareCapacity`**](crate::SpareCapacity) trait can be
+//! used instead.
+//! 
 //! # Example #1
 //! 
 //! For starters, fill a pre-allocated [**`SpareBuffer`**](crate::SpareBuffer)
 //! with some numbers:
 //! ```
-//! fn main() {
-//!     let mut vec: Vec<u8> = Vec::with_capacity(128);
-//!     let mut buffer = SpareBuffer::from(&mut vec, None);
+//! # use std::num::NonZeroUsize;
+//! # use spare_buffer::SpareBuffer;
+//! # #[cfg(not(feature = "safe-only"))] {
+//! let mut vec: Vec<u8> = Vec::with_capacity(128);
+//! let mut buffer = SpareBuffer::from(&mut vec, None);
 //!
-//!     let spare = buffer.allocate_spare(NonZeroUsize::new(100).unwrap());
-//!     for i in 0..50 {
-//!         spare[i] = i as u8;
-//!     }
-//! 
-//!     // Whoops: only &spare[0..50] was initialized, but 100 elements are committed!
-//!     buffer.commit(100).expect("Failed to commit!");
-//! 
-//!     println!("Expect valid numbers:");
-//!     println!("{:?}\n", &vec[..50]);
-//! 
-//!     println!("Expect \"unspecified\" garbage:");
-//!     println!("{:?}\n", &vec[50..]);
+//! let spare = buffer.allocate_spare(NonZeroUsize::new(100).unwrap());
+//! for (i, value) in spare[..50].iter_mut().enumerate() {
+//!     *value = i as u8;
 //! }
+//!
+//! // Whoops: only &spare[0..50] was initialized, but 100 elements are committed!
+//! buffer.commit(100).expect("Failed to commit!");
+//!
+//! println!("Expect valid numbers:");
+//! println!("{:?}\n", &vec[..50]);
+//!
+//! println!("Expect \"unspecified\" garbage:");
+//! println!("{:?}\n", &vec[50..]);
+//! # }
 //! ```
 //! 
 //! # Example #2
 //!
 //! Read a file into a vector, chunk by chunk, using a
 //! [**`SpareBuffer`**](crate::SpareBuffer) to accumulate all data:
-//! ```
-//! fn main() {
-//!     let mut vec: Vec<u8> = Vec::with_capacity(1048576);
-//!     let mut buffer = SpareBuffer::from(&mut vec, NonZeroUsize::new(10485760));
-//!     
-//!     let chunk_size = NonZeroUsize::new(4096).unwrap();
-//!     let mut file = File::open("input.dat").expect("Failed to open input file!");
-//! 
-//!     loop {
-//!         let spare = buffer.allocate_spare(chunk_size);
-//!         let count = file.read(spare).expect("File read error encountered!");
-//!         if count > 0 {
-//!             buffer.commit(count).expect("Failed to commit!");
-//!         } else {
-//!             break; /* EOF*/
-//!         }
+//! ```no_run
+//! # use std::fs::File;
+//! # use std::io::Read;
+//! # use std::num::NonZeroUsize;
+//! # use spare_buffer::SpareBuffer;
+//! # #[cfg(not(feature = "safe-only"))] {
+//! let mut vec: Vec<u8> = Vec::with_capacity(1048576);
+//! let mut buffer = SpareBuffer::from(&mut vec, NonZeroUsize::new(10485760));
+//!
+//! let chunk_size = NonZeroUsize::new(4096).unwrap();
+//! let mut file = File::open("input.dat").expect("Failed to open input file!");
+//!
+//! loop {
+//!     let spare = buffer.allocate_spare(chunk_size);
+//!     let count = file.read(spare).expect("File read error encountered!");
+//!     if count > 0 {
+//!         buffer.commit(count).expect("Failed to commit!");
+//!     } else {
+//!         break; /* EOF*/
 //!     }
-//! 
-//!     println!("Length: {:?}", vec.len());
 //! }
+//!
+//! println!("Length: {:?}", vec.len());
+//! # }
 //! ```
+//!
+//! # Optional features
+//!
+//! - **`allocator-api2`** &ndash; Fill vectors that are backed by a *custom*
+//!   allocator, i.e. an `allocator_api2::vec::Vec<T, A>`, via the
+//!   `SpareBufferIn`, e.g. with bump, pool or DMA-capable allocators.
+//! - **`bytes`** &ndash; Freeze a committed prefix of a **`SpareBuffer<u8>`**
+//!   into a shareable [`Bytes`](https://docs.rs/bytes) handle, via
+//!   `SpareBuffer::freeze_bytes()` or `SpareBuffer::split_to()`, and write
+//!   into a **`SpareBuffer<u8>`** via `bytes::BufMut`.
+//! - **`crc32fast`** &ndash; Accumulate a running CRC-32 of all committed
+//!   data, via `SpareBuffer::with_crc32()` and `SpareBuffer::checksum()`,
+//!   e.g. for ZIP or PNG style formats, and verify it, together with the
+//!   length, via `SpareBuffer::finish_verified_crc32()`.
+//! - **`futures-core`** &ndash; Collect a fallible stream of byte chunks, via
+//!   `SpareBuffer::collect_stream()`.
+//! - **`futures-io`** &ndash; Runtime-agnostic async I/O: fill the "spare"
+//!   buffer from a `futures_io::AsyncRead`, via `SpareBuffer::read_async()`
+//!   or `SpareBuffer::fill_from_async()`, and use a **`SpareBuffer<u8>`** as
+//!   a `futures_io::AsyncWrite` sink, or read its unconsumed data via
+//!   `futures_io::AsyncBufRead`.
+//! - **`fuzzing`** &ndash; The `CheckedSpareBuffer`, which checks the
+//!   contents of the underlying vector against a model after every
+//!   operation, e.g. in `cargo fuzz` targets.
+//! - **`http-body`** &ndash; Accumulate an HTTP request or response body
+//!   under a hard limit, via `SpareBuffer::collect_body()`, e.g. in axum or
+//!   actix services.
+//! - **`log`** &ndash; Emit [log](https://crates.io/crates/log) events, with
+//!   the target `spare_buffer`, for allocations, commits, limit violations
+//!   and reallocations, e.g. to diagnose memory growth in production. Use
+//!   the **`tracing`** feature instead, in order to avoid duplicate events
+//!   when the `log` records are also forwarded via `tracing-log`.
+//! - **`msan`** &ndash; Annotate "spare" buffers for MemorySanitizer: freshly
+//!   allocated "spare" buffers are marked as *uninitialized*, so that reads
+//!   of uncommitted data are flagged, and committed elements are marked as
+//!   *initialized*. Requires a build with `-Zsanitizer=memory`. AddressSanitizer
+//!   poisoning is **not** supported, because the "spare" capacity of a
+//!   [**`Vec<T>`**](std::vec::Vec) must remain addressable for the vector
+//!   itself, once the **`SpareBuffer`** has gone out of scope.
+//! - **`nightly`** &ndash; Fill the "spare" buffer via the *unstable*
+//!   `Read::read_buf()`, with a `BorrowedBuf` over the "spare" capacity, via
+//!   `SpareBuffer::read_buf_into()`, so that readers which implement this API
+//!   avoid the initialization of the buffer. Requires a nightly compiler.
+//! - **`paranoid`** &ndash; Validate *all* internal invariants of a
+//!   **`SpareBuffer`** on every allocation, commit and consume, such as the
+//!   allocation state, the relations between length, capacity and limit,
+//!   and the identity of the underlying vector, while a "spare" buffer is
+//!   allocated. A violation *aborts* the process with detailed diagnostics.
+//!   Intended for staging environments, as the checks are **not** free.
+//!   Also fills the requested length of every new "spare" buffer with
+//!   `0xCD` bytes, as debug builds do.
+//! - **`png`** &ndash; Decode PNG images row by row, via the
+//!   [**`RowDecoder`**](crate::RowDecoder).
+//! - **`rusqlite`** &ndash; Read SQLite BLOBs incrementally, via
+//!   `SpareBuffer::read_blob()`.
+//! - **`safe-only`** &ndash; Compile out all functions that hand out
+//!   `&mut[T]` slices of *uninitialized* memory, as well as
+//!   `commit_unchecked()`, leaving only the safe API, such as the
+//!   [**`SpareWriter`**](crate::SpareWriter). "Spare" buffers that are
+//!   handed to a `Read` implementation, or to other *foreign* code, are
+//!   zero-filled first, and a commit can **never** extend past the
+//!   initialized part of the "spare" buffer. Note that this feature
+//!   *removes* functionality, so it should only be enabled by the final
+//!   application, not by libraries.
+//! - **`smallvec`** &ndash; Use a `smallvec::SmallVec` as the backend of a
+//!   **`SpareBuffer`**, so that short data stays *inline* and only spills to
+//!   the heap, once the inline capacity is exceeded.
+//! - **`sysinfo`** &ndash; Derive the length limit from the currently
+//!   available system memory, via a `MemoryLimit`, which is re-evaluated
+//!   periodically.
+//! - **`test-util`** &ndash; The `FaultInjector`, which makes allocations
+//!   of "spare" buffers fail on the *n*-th call, in order to test the
+//!   handling of out-of-memory conditions.
+//! - **`tokio`** &ndash; Use a **`SpareBuffer<u8>`** as a
+//!   `tokio::io::AsyncWrite` sink, and read its unconsumed data via
+//!   `tokio::io::AsyncBufRead`.
+//! - **`tower`** &ndash; Buffer request bodies under a hard limit, via the
+//!   `BufferBodyLayer` middleware. Implies **`http-body`**.
+//! - **`tracing`** &ndash; Emit [tracing](https://crates.io/crates/tracing)
+//!   events, with the target `spare_buffer`, for the same allocations,
+//!   commits, limit violations and reallocations as the **`log`** feature,
+//!   and a `read_chunks` span around each chunked read, e.g. `read_all_from()`
+//!   or `fill_from()`, so that the buffer behavior shows up in existing
+//!   `tracing` subscribers.
+//! - **`valgrind`** &ndash; Annotate "spare" buffers for Memcheck, via
+//!   Valgrind client requests: freshly allocated "spare" buffers are marked
+//!   as *undefined*, and committed elements are marked as *defined*. The
+//!   client requests are no-ops, when **not** running under Valgrind.
+//!   Supported on `x86_64` and `aarch64`.
+//! - **`wgpu`** &ndash; Upload committed data to, and fill the "spare" buffer
+//!   from, [wgpu](https://crates.io/crates/wgpu) buffers.
+#![cfg_attr(feature = "nightly", feature(core_io_borrowed_buf, read_buf))]
+#[cfg(feature = "allocator-api2")]
+mod allocator;
+mod array;
+#[cfg(feature = "futures-io")]
+mod async_io;
+#[cfg(feature = "http-body")]
+mod body;
+#[cfg(not(feature = "safe-only"))]
+mod brand;
 mod buffer;
+mod bufread;
+mod capacity;
+#[cfg(feature = "fuzzing")]
+mod checked;
+mod chunk;
+mod concat;
+mod deque;
+mod error;
+#[cfg(feature = "test-util")]
+mod fault;
+#[cfg(unix)]
+mod fd;
+mod file;
+mod fixed;
+mod freeze;
+mod future;
+mod gap;
+#[cfg(feature = "wgpu")]
+mod gpu;
+mod growth;
+#[cfg(not(feature = "safe-only"))]
+mod handle;
+#[cfg(feature = "tower")]
+mod layer;
+mod limit;
+#[cfg(any(unix, windows))]
+mod lock;
+#[cfg(feature = "sysinfo")]
+mod memory;
+mod net;
+#[cfg(feature = "nightly")]
+mod nightly;
+#[cfg(feature = "paranoid")]
+mod paranoid;
+mod pcm;
+mod pool;
 mod primitive;
+#[cfg(not(feature = "safe-only"))]
+mod region;
+mod resp;
+mod retry;
+mod ring;
+mod rope;
+mod rows;
+mod sanitizer;
+mod shrink;
+mod slice;
+#[cfg(feature = "rusqlite")]
+mod sqlite;
+mod stats;
+#[cfg(feature = "futures-core")]
+mod stream;
+mod string;
+#[cfg(feature = "tokio")]
+mod tokio_io;
+#[cfg(all(target_os = "linux", not(feature = "safe-only")))]
+mod uring;
+mod vectored;
+mod verify;
+#[cfg(windows)]
+mod windows;
+mod wipe;
+mod writer;
 
-pub use buffer::SpareBuffer;
+#[cfg(feature = "allocator-api2")]
+pub use allocator::SpareBufferIn;
+pub use array::SpareArray;
+#[cfg(not(feature = "safe-only"))]
+pub use brand::{AllocatedToken, BrandedBuffer, IdleToken};
+pub use buffer::{SpareBuffer, SpareVec};
+pub use capacity::SpareCapacity;
+#[cfg(feature = "fuzzing")]
+pub use checked::CheckedSpareBuffer;
+pub use chunk::ChunkPolicy;
+pub use deque::SpareDeque;
+pub use error::AllocationError;
+#[cfg(feature = "http-body")]
+pub use error::BodyError;
+pub use error::VerifyError;
+#[cfg(feature = "test-util")]
+pub use fault::FaultInjector;
+pub use file::read_file;
+#[cfg(not(feature = "safe-only"))]
+pub use future::FillFuture;
+pub use gap::SpareGap;
+pub use growth::GrowthPolicy;
+#[cfg(not(feature = "safe-only"))]
+pub use handle::SpareHandle;
+#[cfg(feature = "tower")]
+pub use layer::{BufferBody, BufferBodyLayer};
+pub use limit::{DynamicLimit, OverflowPolicy};
+#[cfg(feature = "sysinfo")]
+pub use memory::MemoryLimit;
+pub use pcm::{PcmReader, Sample};
+pub use pool::{Acquire, PooledVec, SparePool};
 pub use primitive::Primitive;
+#[cfg(not(feature = "safe-only"))]
+pub use region::OwnedSpareRegion;
+pub use retry::{RetryPolicy, WouldBlockPolicy};
+pub use ring::SpareRing;
+pub use rope::{RopeReader, SpareRope};
+pub use rows::RowDecoder;
+pub use shrink::ShrinkPolicy;
+pub use slice::SpareSlice;
+pub use stats::Stats;
+pub use string::SpareString;
+#[cfg(all(windows, not(feature = "safe-only")))]
+pub use windows::OverlappedRead;
+pub use writer::SpareWriter;
@@ -0,0 +1,53 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+
+/// Controls what a [**`SpareBuffer`**](crate::SpareBuffer) does, if a commit
+/// would exceed the length limit.
+///
+/// Different callers want different behaviour: a hard cap on an HTTP body
+/// should fail, whereas capturing the beginning of a log stream should keep
+/// as much as fits. The policy of a **`SpareBuffer`** can be set with
+/// [`with_overflow_policy()`](crate::SpareBuffer::with_overflow_policy).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Fail with an error of kind
+    /// [`ErrorKind::OutOfMemory`](std::io::ErrorKind::OutOfMemory), and
+    /// commit **nothing**. This is the default.
+    #[default]
+    Error,
+    /// Commit as many elements as fit, and discard the rest. Once the limit
+    /// has been reached, *further* commits fail, as with
+    /// [`Error`](Self::Error).
+    Truncate,
+    /// Commit as many elements as fit, and *silently* discard the rest. Even
+    /// once the limit has been reached, commits **never** fail.
+    Saturate,
+}
+
+/// A *dynamic* length limit, which is consulted on every commit, *in
+/// addition* to the fixed length limit of a
+/// [**`SpareBuffer`**](crate::SpareBuffer).
+///
+/// This allows the cap to depend on external state, such as a global memory
+/// budget or a per-connection quota, rather than on a single number that is
+/// fixed at construction. The limit of a **`SpareBuffer`** can be set with
+/// [`with_dynamic_limit()`](crate::SpareBuffer::with_dynamic_limit).
+///
+/// This trait is implemented for all closures of the form
+/// `Fn(usize, usize) -> bool`.
+pub trait DynamicLimit {
+    /// Returns `true` if `additional` more elements may be appended to the
+    /// underlying vector, which currently holds `length` elements.
+    fn permits(&self, length: usize, additional: usize) -> bool;
+}
+
+impl<F> DynamicLimit for F
+where
+    F: Fn(usize, usize) -> bool
+{
+    fn permits(&self, length: usize, additional: usize) -> bool {
+        self(length, additional)
+    }
+}
@@ -0,0 +1,48 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::{Result as IoResult, Error as IoError};
+
+/// A range of memory, given by its address and its size in bytes.
+pub(crate) type Range = (usize, usize);
+
+/// Locks the given range of memory into RAM, via `mlock(2)`.
+#[cfg(unix)]
+pub(crate) fn lock((address, size): Range) -> IoResult<()> {
+    if size > 0 && unsafe { libc::mlock(address as *const libc::c_void, size) } != 0 {
+        return Err(IoError::last_os_error());
+    }
+    Ok(())
+}
+
+/// Unlocks the given range of memory, via `munlock(2)`.
+#[cfg(unix)]
+pub(crate) fn unlock((address, size): Range) {
+    if size > 0 {
+        unsafe {
+            libc::munlock(address as *const libc::c_void, size);
+        }
+    }
+}
+
+/// Locks the given range of memory into RAM, via `VirtualLock()`.
+#[cfg(windows)]
+pub(crate) fn lock((address, size): Range) -> IoResult<()> {
+    use windows_sys::Win32::System::Memory::VirtualLock;
+    if size > 0 && unsafe { VirtualLock(address as *const core::ffi::c_void, size) } == 0 {
+        return Err(IoError::last_os_error());
+    }
+    Ok(())
+}
+
+/// Unlocks the given range of memory, via `VirtualUnlock()`.
+#[cfg(windows)]
+pub(crate) fn unlock((address, size): Range) {
+    use windows_sys::Win32::System::Memory::VirtualUnlock;
+    if size > 0 {
+        unsafe {
+            VirtualUnlock(address as *const core::ffi::c_void, size);
+        }
+    }
+}
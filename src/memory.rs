@@ -0,0 +1,101 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::mem::size_of;
+use std::num::NonZeroUsize;
+use std::time::{Duration, Instant};
+
+use sysinfo::System;
+
+/// Default interval for re-evaluating the available system memory.
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A length limit that is derived from the *currently available* system
+/// memory, e.g. "at most 25% of the available RAM, but no more than 1 GiB",
+/// for tools that should **not** hard-code a byte cap.
+///
+/// The available memory is re-evaluated, at most once per `interval`, when a
+/// "spare" buffer is allocated or committed. The limit of a
+/// [**`SpareBuffer`**](crate::SpareBuffer) can be set with
+/// [`with_memory_limit()`](crate::SpareBuffer::with_memory_limit), and is
+/// applied *in addition* to the fixed length limit, if any.
+#[derive(Clone, Debug)]
+pub struct MemoryLimit {
+    percent: u8,
+    cap: Option<NonZeroUsize>,
+    interval: Duration,
+    evaluated: Option<(Instant, usize)>,
+}
+
+impl MemoryLimit {
+    /// Creates a new **`MemoryLimit`** of `percent` percent of the available
+    /// system memory.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `percent` is **not** in the range from `1` to `100`!
+    pub fn new(percent: u8) -> Self {
+        assert!((1..=100).contains(&percent), "The percentage must be in the range from 1 to 100!");
+        Self {
+            percent,
+            cap: None,
+            interval: DEFAULT_INTERVAL,
+            evaluated: None,
+        }
+    }
+
+    /// Sets the *optional* upper bound of the limit, in bytes.
+    pub fn with_cap(mut self, cap: Option<NonZeroUsize>) -> Self {
+        self.cap = cap;
+        self.evaluated = None;
+        self
+    }
+
+    /// Sets the interval for re-evaluating the available system memory.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Returns the percentage of the available system memory.
+    pub fn percent(&self) -> u8 {
+        self.percent
+    }
+
+    /// Returns the upper bound of the limit, in bytes, if a cap has been
+    /// specified. Otherwise `None` is returned.
+    pub fn cap(&self) -> Option<NonZeroUsize> {
+        self.cap
+    }
+
+    /// Returns the interval for re-evaluating the available system memory.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Returns the limit, in bytes, as of the last evaluation, or `None`, if
+    /// it has **not** been evaluated yet.
+    pub fn current_bytes(&self) -> Option<usize> {
+        self.evaluated.map(|(_, bytes)| bytes)
+    }
+
+    /// Re-evaluates the limit, if it has never been evaluated, or if the
+    /// interval has elapsed since the last evaluation.
+    pub(crate) fn refresh(&mut self) {
+        if self.evaluated.is_some_and(|(instant, _)| instant.elapsed() < self.interval) {
+            return;
+        }
+        let mut system = System::new();
+        system.refresh_memory();
+        let available = usize::try_from(system.available_memory()).unwrap_or(usize::MAX);
+        let bytes = (available / 100).saturating_mul(self.percent.into())
+            .min(self.cap.map_or(usize::MAX, NonZeroUsize::get));
+        self.evaluated = Some((Instant::now(), bytes));
+    }
+
+    /// Returns the limit as of the last evaluation, in elements of type `T`.
+    pub(crate) fn max_elements<T>(&self) -> usize {
+        self.current_bytes().map_or(usize::MAX, |bytes| bytes / size_of::<T>().max(1))
+    }
+}
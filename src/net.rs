@@ -0,0 +1,52 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::{Result as IoResult, Error as IoError, ErrorKind};
+use std::net::{SocketAddr, UdpSocket};
+use std::num::NonZeroUsize;
+
+use crate::{SpareBuffer, SpareCapacity};
+
+/// Maximum size of a single UDP datagram, in bytes.
+const MAX_DATAGRAM: usize = 65535;
+
+impl<'a, B> SpareBuffer<'a, u8, B>
+where
+    B: SpareCapacity<u8>
+{
+    /// Receives a *single* datagram from `socket` directly into the "spare"
+    /// buffer, via [`UdpSocket::recv_from()`], and commits it. Returns the
+    /// number of bytes that have been committed, and the address of the peer.
+    ///
+    /// The "spare" buffer is allocated for the maximum datagram size, but
+    /// clamped to the length limit, and to the maximum allocation size. Note
+    /// that a datagram that does **not** fit is *truncated* by the operating
+    /// system. Transient errors are handled according to the
+    /// [**`RetryPolicy`**](crate::RetryPolicy) of this **`SpareBuffer`**.
+    ///
+    /// # Errors
+    ///
+    /// Fails with an error of kind [`ErrorKind::OutOfMemory`], if the length
+    /// limit has been reached, or with
+    /// [`AllocationError::Reserve`](crate::AllocationError::Reserve),
+    /// converted to an error of kind `OutOfMemory`, if the capacity can
+    /// **not** be reserved. Errors returned by the `socket`, as well as errors
+    /// returned by [`commit()`](Self::commit), are passed through.
+    pub fn recv_from_into(&mut self, socket: &UdpSocket) -> IoResult<(usize, SocketAddr)> {
+        let retry = self.retry_policy();
+        let max_allocation = self.max_allocation().map_or(usize::MAX, NonZeroUsize::get);
+        let length = match NonZeroUsize::new(MAX_DATAGRAM.min(self.headroom()).min(max_allocation)) {
+            Some(length) => length,
+            None => return Err(IoError::new(ErrorKind::OutOfMemory, "The length limit has been reached!")),
+        };
+        let spare = &mut self.try_spare_mut(length)?[..length.get()];
+        match retry.retry(|| socket.recv_from(spare)) {
+            Ok((count, peer)) => self.commit(count).map(|_| (count, peer)),
+            Err(error) => {
+                self.discard_spare();
+                Err(error)
+            },
+        }
+    }
+}
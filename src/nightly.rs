@@ -0,0 +1,55 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::{BorrowedBuf, Read, Result as IoResult};
+use std::num::NonZeroUsize;
+
+use crate::{SpareBuffer, SpareCapacity};
+
+impl<'a, B> SpareBuffer<'a, u8, B>
+where
+    B: SpareCapacity<u8>
+{
+    /// Reads up to `length` bytes from `reader` into the "spare" buffer, via
+    /// the *unstable* [`Read::read_buf()`], and commits the data.
+    ///
+    /// A [`BorrowedBuf`] is built over the *uninitialized* "spare" capacity,
+    /// so that readers which implement `read_buf()` do **not** need to
+    /// initialize the buffer first. Exactly the *filled* part of the buffer is
+    /// committed. Reads that fail with
+    /// [`ErrorKind::Interrupted`](std::io::ErrorKind::Interrupted) are
+    /// handled according to the [**`RetryPolicy`**](crate::RetryPolicy) of
+    /// this **`SpareBuffer`**. Returns the number of bytes that have been
+    /// committed, which is `0` at the end of the stream.
+    ///
+    /// Only available with the **`nightly`** feature, which requires a
+    /// nightly compiler.
+    ///
+    /// # Errors
+    ///
+    /// Fails with
+    /// [`AllocationError::ExceedsMaxAllocation`](crate::AllocationError::ExceedsMaxAllocation),
+    /// converted to an error of kind
+    /// [`ErrorKind::OutOfMemory`](std::io::ErrorKind::OutOfMemory), if
+    /// `length` exceeds the maximum allocation size. Errors returned by the
+    /// `reader`, as well as errors returned by [`commit()`](Self::commit),
+    /// are passed through.
+    pub fn read_buf_into<R>(&mut self, reader: &mut R, length: NonZeroUsize) -> IoResult<usize>
+    where
+        R: Read
+    {
+        let retry = self.retry_policy();
+        self.try_spare_mut(length)?;
+        let mut buf = BorrowedBuf::from(&mut self.spare_uninit()[..length.get()]);
+        let result = retry.retry(|| reader.read_buf(buf.unfilled()));
+        let filled = buf.len();
+        match result {
+            Ok(()) => self.commit(filled).map(|_| filled),
+            Err(error) => {
+                self.discard_spare();
+                Err(error)
+            },
+        }
+    }
+}
@@ -0,0 +1,78 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::num::NonZeroUsize;
+
+/// Snapshot of the underlying vector, taken when a "spare" buffer is
+/// allocated.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Snapshot {
+    pub address: usize,
+    pub length: usize,
+    pub capacity: usize,
+    pub requested: NonZeroUsize,
+}
+
+/// The internal state of a **`SpareBuffer`**, as seen by
+/// [`check()`](self::check).
+#[derive(Debug)]
+pub(crate) struct State {
+    pub address: usize,
+    pub length: usize,
+    pub capacity: usize,
+    pub limit: Option<NonZeroUsize>,
+    pub max_allocation: Option<NonZeroUsize>,
+    pub consumed: usize,
+    pub allocated: bool,
+    pub snapshot: Option<Snapshot>,
+    pub exceeded_limit: bool,
+}
+
+/// Validates all invariants of the given `state`, and *aborts* the process
+/// with detailed diagnostics, if any of them is violated.
+///
+/// Aborting, rather than panicking, makes sure that the violation can **not**
+/// be caught and ignored, and that the broken state is never used again.
+pub(crate) fn check(operation: &str, state: &State) {
+    let mut violations = Vec::new();
+    if state.length > state.capacity {
+        violations.push(format!("The length {} exceeds the capacity {}!", state.length, state.capacity));
+    }
+    if state.consumed > state.length {
+        violations.push(format!("The consumed count {} exceeds the length {}!", state.consumed, state.length));
+    }
+    if let Some(limit) = state.limit.filter(|limit| state.length > limit.get() && !state.exceeded_limit) {
+        violations.push(format!("The length {} exceeds the limit {}!", state.length, limit));
+    }
+    match (state.allocated, state.snapshot) {
+        (true, None) => violations.push("A spare buffer is allocated, but no snapshot was taken!".to_owned()),
+        (false, Some(_)) => violations.push("No spare buffer is allocated, but a snapshot is still present!".to_owned()),
+        (true, Some(snapshot)) => {
+            if state.address != snapshot.address {
+                violations.push(format!("The vector was moved from {:#x} to {:#x}, while a spare buffer was allocated!", snapshot.address, state.address));
+            }
+            if state.capacity != snapshot.capacity {
+                violations.push(format!("The capacity changed from {} to {}, while a spare buffer was allocated!", snapshot.capacity, state.capacity));
+            }
+            if state.length != snapshot.length {
+                violations.push(format!("The length changed from {} to {}, while a spare buffer was allocated!", snapshot.length, state.length));
+            }
+            if snapshot.capacity - snapshot.length < snapshot.requested.get() {
+                violations.push(format!("The spare capacity {} is less than the requested length {}!", snapshot.capacity - snapshot.length, snapshot.requested));
+            }
+            if let Some(max_allocation) = state.max_allocation.filter(|max_allocation| snapshot.requested > *max_allocation) {
+                violations.push(format!("The requested length {} exceeds the maximum allocation size {}!", snapshot.requested, max_allocation));
+            }
+        },
+        (false, None) => {},
+    }
+    if !violations.is_empty() {
+        eprintln!("spare_buffer: invariant violation detected in `{}`:", operation);
+        for violation in &violations {
+            eprintln!("  - {}", violation);
+        }
+        eprintln!("spare_buffer: state: {:#?}", state);
+        std::process::abort();
+    }
+}
@@ -0,0 +1,158 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::{Read, Result as IoResult, Error as IoError, ErrorKind};
+use std::marker::PhantomData;
+use std::mem::size_of;
+use std::num::NonZeroUsize;
+use std::slice::from_raw_parts_mut;
+
+use crate::{Primitive, SpareBuffer};
+
+/// Maximum size of a single sample, in bytes.
+const MAX_SAMPLE_SIZE: usize = 8;
+
+/// Primitive types that can be used as raw PCM samples.
+///
+/// # Safety
+///
+/// In addition to the requirements of [**`Primitive`**](crate::Primitive),
+/// implementors **must** guarantee that the size of the type does not exceed
+/// 8 bytes.
+pub unsafe trait Sample: Primitive {}
+
+unsafe impl Sample for i8 {}
+unsafe impl Sample for i16 {}
+unsafe impl Sample for i32 {}
+unsafe impl Sample for u8 {}
+unsafe impl Sample for u16 {}
+unsafe impl Sample for u32 {}
+unsafe impl Sample for f32 {}
+unsafe impl Sample for f64 {}
+
+/// Reads raw PCM samples from a byte stream into a
+/// [**`SpareBuffer`**](crate::SpareBuffer).
+///
+/// The bytes are read *directly* into the "spare" capacity of the buffer, but
+/// only *whole* samples are committed. If a read ends in the middle of a
+/// sample, the incomplete sample is held back and completed by the next read.
+///
+/// Samples are expected in the *native* byte order, which is what PCM devices
+/// (e.g. ALSA with `S16_LE` or `S32_LE` on little-endian hosts) and pipes of
+/// raw samples usually deliver.
+pub struct PcmReader<R, T>
+where
+    R: Read,
+    T: Sample
+{
+    reader: R,
+    pending: [u8; MAX_SAMPLE_SIZE],
+    pending_len: usize,
+    _sample: PhantomData<T>,
+}
+
+impl<R, T> PcmReader<R, T>
+where
+    R: Read,
+    T: Sample
+{
+    /// Creates a new **`PcmReader`** that reads samples from `reader`.
+    pub fn new(reader: R) -> Self {
+        assert!(size_of::<T>() <= MAX_SAMPLE_SIZE, "Sample size is not supported!");
+        Self {
+            reader,
+            pending: [0u8; MAX_SAMPLE_SIZE],
+            pending_len: 0,
+            _sample: PhantomData,
+        }
+    }
+
+    /// Returns the number of bytes of an incomplete sample that are currently
+    /// held back.
+    pub fn pending_len(&self) -> usize {
+        self.pending_len
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    /// Consumes the **`PcmReader`**, returning the underlying reader. Any
+    /// bytes of an incomplete sample that are held back are discarded.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Reads up to `max_samples` samples and commits them to `buffer`.
+    ///
+    /// Returns the number of *whole* samples that have been committed. A
+    /// return value of `0` indicates that the end of the stream was reached.
+    /// Transient errors are retried according to the
+    /// [retry policy](crate::SpareBuffer::retry_policy) of `buffer`.
+    ///
+    /// The number of samples is clamped to the remaining headroom of
+    /// `buffer`, so that **no** samples are read that can **not** be
+    /// committed. The incomplete sample that is held back is only updated,
+    /// once the whole samples have been committed.
+    ///
+    /// # Errors
+    ///
+    /// Fails with an error of kind [`ErrorKind::OutOfMemory`], if the length
+    /// limit of `buffer` has been reached. Fails with
+    /// [`ErrorKind::UnexpectedEof`], if the stream ends in the middle of a
+    /// sample. Errors of the underlying reader, as well as errors returned by
+    /// [`SpareBuffer::commit()`](crate::SpareBuffer::commit), are passed
+    /// through. If the commit fails, e.g. because of a dynamic limit, the
+    /// samples that have been read are lost, but the incomplete sample that
+    /// was held back before is retained.
+    pub fn read_into(&mut self, buffer: &mut SpareBuffer<'_, T>, max_samples: NonZeroUsize) -> IoResult<usize> {
+        let sample_size = size_of::<T>();
+        let max_allocation = buffer.max_allocation().map_or(usize::MAX, NonZeroUsize::get);
+        let max_samples = match NonZeroUsize::new(max_samples.get().min(buffer.headroom()).min(max_allocation)) {
+            Some(max_samples) => max_samples,
+            None => return Err(IoError::new(ErrorKind::OutOfMemory, "The length limit has been reached!")),
+        };
+        let total_size = max_samples.get().checked_mul(sample_size).expect("Numerical overflow! (total_size)");
+        let policy = buffer.retry_policy();
+        loop {
+            let spare = buffer.try_spare_mut(max_samples)?;
+            let bytes = unsafe {
+                from_raw_parts_mut(spare.as_mut_ptr() as *mut u8, total_size)
+            };
+            bytes[..self.pending_len].copy_from_slice(&self.pending[..self.pending_len]);
+            let count = match policy.retry(|| self.reader.read(&mut bytes[self.pending_len..])) {
+                Ok(count) => count,
+                Err(error) => {
+                    buffer.discard_spare();
+                    return Err(error);
+                },
+            };
+            if count == 0 {
+                buffer.discard_spare();
+                return if self.pending_len > 0 {
+                    Err(IoError::new(ErrorKind::UnexpectedEof, "The stream ended with an incomplete sample!"))
+                } else {
+                    Ok(0)
+                };
+            }
+            let available = self.pending_len + count;
+            let samples = available / sample_size;
+            let complete = samples * sample_size;
+            let mut pending = [0u8; MAX_SAMPLE_SIZE];
+            pending[..available - complete].copy_from_slice(&bytes[complete..available]);
+            buffer.commit(samples)?;
+            self.pending = pending;
+            self.pending_len = available - complete;
+            if samples > 0 {
+                return Ok(samples);
+            }
+        }
+    }
+}
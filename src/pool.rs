@@ -0,0 +1,321 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::future::Future;
+use std::num::NonZeroUsize;
+use std::ops::{Deref, DerefMut};
+use std::pin::Pin;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::task::{Context, Poll, Waker};
+use std::time::{Duration, Instant};
+
+use crate::{Primitive, SpareVec};
+
+/// A *pool* of vectors, which hands out owned [**`SpareVec`**](crate::SpareVec)s
+/// and reclaims their underlying vectors, once they are returned.
+///
+/// The capacity of a returned vector is *preserved*, while its length is
+/// cleared, so that high-throughput servers do **not** need to allocate a
+/// new vector for every request. The **`SparePool`** can be shared between
+/// threads, e.g. via an [**`Arc`**](std::sync::Arc).
+///
+/// In order to **not** retain large vectors forever, the capacity that is
+/// retained per vector can be bounded by
+/// [`with_max_capacity()`](Self::with_max_capacity), and vectors that have
+/// been idle for too long can be freed by calling [`trim()`](Self::trim)
+/// periodically.
+///
+/// Note that a **`SpareVec`** is **not** returned to the pool automatically,
+/// when it is dropped. It **must** be returned via [`put()`](Self::put).
+///
+/// Memory-bounded *async* servers can bound the number of buffers that are
+/// handed out at the same time by
+/// [`with_max_outstanding()`](Self::with_max_outstanding), and then use
+/// [`acquire()`](Self::acquire), so that tasks *wait* for a buffer, instead
+/// of allocating unboundedly under load. A [**`PooledVec`**](crate::PooledVec)
+/// that was acquired this way *is* returned automatically, when dropped.
+pub struct SparePool<T>
+where
+    T: Primitive
+{
+    vectors: Mutex<Vec<(Vec<T>, Instant)>>,
+    max_pooled: usize,
+    max_capacity: Option<NonZeroUsize>,
+    max_outstanding: Option<NonZeroUsize>,
+    permits: Mutex<Permits>,
+}
+
+/// The state of the *semaphore* that bounds the number of buffers which are
+/// handed out by [`SparePool::acquire()`].
+struct Permits {
+    available: usize,
+    waiters: Vec<Waker>,
+}
+
+impl<T> SparePool<T>
+where
+    T: Primitive
+{
+    /// Creates a new, empty **`SparePool`**, which keeps up to `max_pooled`
+    /// vectors. Vectors that are returned while the pool is full are freed.
+    pub const fn new(max_pooled: usize) -> Self {
+        Self {
+            vectors: Mutex::new(Vec::new()),
+            max_pooled,
+            max_capacity: None,
+            max_outstanding: None,
+            permits: Mutex::new(Permits { available: 0, waiters: Vec::new() }),
+        }
+    }
+
+    /// Sets the maximum capacity, in elements, that is retained per vector.
+    /// Vectors with a larger capacity are *shrunk* to this capacity, when they
+    /// are returned to the pool, which may reallocate.
+    pub const fn with_max_capacity(mut self, max_capacity: NonZeroUsize) -> Self {
+        self.max_capacity = Some(max_capacity);
+        self
+    }
+
+    /// Sets the maximum number of buffers that can be *outstanding*, i.e.
+    /// handed out by [`acquire()`](Self::acquire) and **not** returned yet,
+    /// at the same time. Further calls to `acquire()` wait, until a buffer
+    /// is returned. Without a maximum, `acquire()` never waits.
+    ///
+    /// Buffers that are handed out by [`get()`](Self::get) are **not**
+    /// counted.
+    pub fn with_max_outstanding(mut self, max_outstanding: NonZeroUsize) -> Self {
+        self.max_outstanding = Some(max_outstanding);
+        self.permits.get_mut().unwrap_or_else(PoisonError::into_inner).available = max_outstanding.get();
+        self
+    }
+
+    /// Returns the maximum number of outstanding buffers, if it has been
+    /// set. Otherwise `None` is returned.
+    pub fn max_outstanding(&self) -> Option<NonZeroUsize> {
+        self.max_outstanding
+    }
+
+    /// Returns the maximum number of vectors that are kept by the pool.
+    pub fn max_pooled(&self) -> usize {
+        self.max_pooled
+    }
+
+    /// Returns the maximum capacity that is retained per vector, if it has
+    /// been set. Otherwise `None` is returned.
+    pub fn max_capacity(&self) -> Option<NonZeroUsize> {
+        self.max_capacity
+    }
+
+    /// Returns the number of vectors that are currently kept by the pool.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Returns `true` if the pool currently keeps **no** vectors.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Hands out an *empty* [**`SpareVec`**](crate::SpareVec), which owns a
+    /// vector from the pool, if any is available. Otherwise, a new vector is
+    /// created.
+    ///
+    /// An *optional* `limit` for the length of the vector can be specified,
+    /// exactly like for [`SpareVec::owned()`](crate::SpareBuffer::owned).
+    pub fn get(&self, limit: Option<NonZeroUsize>) -> SpareVec<T> {
+        let vector = self.lock().pop().map(|(vector, _)| vector).unwrap_or_default();
+        SpareVec::owned(vector, limit)
+    }
+
+    /// Returns the underlying vector of `buffer` to the pool, after clearing
+    /// it. The vector is freed instead, if the pool is full, or if it has
+    /// **no** capacity. A vector whose capacity exceeds the
+    /// [maximum capacity](Self::with_max_capacity), if any, is shrunk.
+    ///
+    /// The vector is **not** wiped. Do **not** return buffers that contain
+    /// secrets, unless they have been wiped via
+    /// [`wipe()`](crate::SpareBuffer::wipe) before.
+    pub fn put(&self, buffer: SpareVec<T>) {
+        let mut vector = buffer.into_vec();
+        if vector.capacity() > 0 {
+            vector.clear();
+            if let Some(max_capacity) = self.max_capacity.filter(|max_capacity| vector.capacity() > max_capacity.get()) {
+                vector.shrink_to(max_capacity.get());
+            }
+            let mut vectors = self.lock();
+            if vectors.len() < self.max_pooled {
+                vectors.push((vector, Instant::now()));
+            }
+        }
+    }
+
+    /// Frees all pooled vectors that have been *idle*, i.e. have **not** been
+    /// handed out again, for longer than `max_idle` since they were returned,
+    /// e.g. when called periodically by a housekeeping task. The vectors that
+    /// have been idle for the *shortest* time are kept.
+    ///
+    /// Returns the number of vectors that have been freed. Passing
+    /// [`Duration::ZERO`] frees *all* pooled vectors.
+    pub fn trim(&self, max_idle: Duration) -> usize {
+        let mut vectors = self.lock();
+        let count = vectors.len();
+        vectors.retain(|(_, returned)| returned.elapsed() < max_idle);
+        count - vectors.len()
+    }
+
+    /// Takes a permit, if one is available, or if there is **no** maximum.
+    /// Otherwise, the waker of `cx`, if any, is registered, *atomically*, so
+    /// that **no** wake-up can be lost.
+    fn take_permit(&self, cx: Option<&mut Context<'_>>) -> bool {
+        if self.max_outstanding.is_none() {
+            return true;
+        }
+        let mut permits = self.permits.lock().unwrap_or_else(PoisonError::into_inner);
+        if permits.available > 0 {
+            permits.available -= 1;
+            return true;
+        }
+        if let Some(cx) = cx {
+            if !permits.waiters.iter().any(|waker| waker.will_wake(cx.waker())) {
+                permits.waiters.push(cx.waker().clone());
+            }
+        }
+        false
+    }
+
+    /// Returns a permit, and wakes up *all* waiting tasks, which then compete
+    /// for the permit. This way, a task that is woken up, but whose future
+    /// has been dropped in the meantime, does **not** swallow the wake-up.
+    fn release_permit(&self) {
+        if self.max_outstanding.is_some() {
+            let waiters = {
+                let mut permits = self.permits.lock().unwrap_or_else(PoisonError::into_inner);
+                permits.available += 1;
+                std::mem::take(&mut permits.waiters)
+            };
+            waiters.into_iter().for_each(Waker::wake);
+        }
+    }
+
+    /// Locks the list of pooled vectors. A poisoned lock is recovered, since
+    /// the list is always in a consistent state.
+    fn lock(&self) -> MutexGuard<'_, Vec<(Vec<T>, Instant)>> {
+        self.vectors.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// The *async* functions, which hand out buffers that borrow the pool.
+impl<T> SparePool<T>
+where
+    T: Primitive + 'static
+{
+    /// Returns a future that hands out an *empty* buffer, exactly like
+    /// [`get()`](Self::get) does, once fewer than the
+    /// [maximum number](Self::with_max_outstanding) of buffers are
+    /// outstanding. The future does **not** depend on a specific runtime.
+    ///
+    /// The buffer is returned to the pool, and the next waiting task is
+    /// woken up, when the [**`PooledVec`**](crate::PooledVec) is dropped.
+    pub fn acquire(&self, limit: Option<NonZeroUsize>) -> Acquire<'_, T> {
+        Acquire { pool: self, limit }
+    }
+
+    /// Hands out an *empty* buffer, exactly like
+    /// [`acquire()`](Self::acquire) does, but returns `None` immediately,
+    /// instead of waiting, if the maximum number of buffers is outstanding.
+    pub fn try_acquire(&self, limit: Option<NonZeroUsize>) -> Option<PooledVec<'_, T>> {
+        self.take_permit(None).then(|| self.pooled(limit))
+    }
+
+    /// Wraps a buffer from the pool, for which a permit has been taken.
+    fn pooled(&self, limit: Option<NonZeroUsize>) -> PooledVec<'_, T> {
+        PooledVec { pool: self, buffer: Some(self.get(limit)) }
+    }
+}
+
+/// A future that waits until the [**`SparePool`**](crate::SparePool) can
+/// hand out another buffer.
+///
+/// Use [`SparePool::acquire()`](crate::SparePool::acquire) to create a new
+/// **`Acquire`** future.
+pub struct Acquire<'p, T>
+where
+    T: Primitive + 'static
+{
+    pool: &'p SparePool<T>,
+    limit: Option<NonZeroUsize>,
+}
+
+impl<'p, T> Future for Acquire<'p, T>
+where
+    T: Primitive + 'static
+{
+    type Output = PooledVec<'p, T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.pool.take_permit(Some(cx)) {
+            true => Poll::Ready(self.pool.pooled(self.limit)),
+            false => Poll::Pending,
+        }
+    }
+}
+
+/// An owned [**`SpareVec`**](crate::SpareVec) that was handed out by
+/// [`SparePool::acquire()`](crate::SparePool::acquire), and that dereferences
+/// to the **`SpareVec`**.
+///
+/// The underlying vector is returned to the pool, exactly like by
+/// [`SparePool::put()`](crate::SparePool::put), and the permit is released,
+/// when the **`PooledVec`** is dropped.
+pub struct PooledVec<'p, T>
+where
+    T: Primitive + 'static
+{
+    pool: &'p SparePool<T>,
+    buffer: Option<SpareVec<T>>,
+}
+
+impl<'p, T> PooledVec<'p, T>
+where
+    T: Primitive + 'static
+{
+    /// Takes the **`SpareVec`** out of the pool, e.g. in order to keep the
+    /// data. The permit is released, but the vector is **not** returned to
+    /// the pool.
+    pub fn into_inner(mut self) -> SpareVec<T> {
+        self.buffer.take().expect("Buffer already taken!")
+    }
+}
+
+impl<'p, T> Deref for PooledVec<'p, T>
+where
+    T: Primitive + 'static
+{
+    type Target = SpareVec<T>;
+
+    fn deref(&self) -> &SpareVec<T> {
+        self.buffer.as_ref().expect("Buffer already taken!")
+    }
+}
+
+impl<'p, T> DerefMut for PooledVec<'p, T>
+where
+    T: Primitive + 'static
+{
+    fn deref_mut(&mut self) -> &mut SpareVec<T> {
+        self.buffer.as_mut().expect("Buffer already taken!")
+    }
+}
+
+impl<'p, T> Drop for PooledVec<'p, T>
+where
+    T: Primitive + 'static
+{
+    fn drop(&mut self) {
+        if let Some(buffer) = self.buffer.take() {
+            self.pool.put(buffer);
+        }
+        self.pool.release_permit();
+    }
+}
@@ -2,23 +2,112 @@
  * Spare Buffer
  * This is free and unencumbered software released into the public domain.
  */
+use std::mem::MaybeUninit;
+use std::ptr::write_bytes;
 
 /// Primitive types.
-pub trait Primitive: Copy + Clone {}
+///
+/// Implemented for all built-in integer and floating-point types. Other types
+/// can opt in with an `unsafe impl`, e.g. `#[repr(C)]` structs of primitive
+/// fields **without** any padding. Types with *invalid* bit patterns, such as
+/// `bool` or `char`, can **not** be used, because the "spare" buffer is
+/// handed out as a `&mut[T]` slice, the contents of which are unspecified.
+///
+/// # Safety
+///
+/// Implementors **must** guarantee that the type has **no** padding bytes and
+/// **no** interior mutability, so that the "committed" elements can be viewed
+/// as plain bytes, e.g. when uploading them to a GPU buffer, and that *every*
+/// bit pattern of `size_of::<Self>()` bytes, including the *all-zero* bit
+/// pattern, is a valid value of the type. Implementations of
+/// [`fill_spare()`](Self::fill_spare) **must** initialize *all* elements of
+/// the given slice.
+pub unsafe trait Primitive: Copy + Clone {
+    /// Initializes all elements of the given "spare" buffer with `value`.
+    ///
+    /// The default implementation writes the elements one by one. The
+    /// implementations for the built-in types use a *memset* instead, if the
+    /// `value` is zero.
+    fn fill_spare(spare: &mut [MaybeUninit<Self>], value: Self) {
+        for element in spare {
+            element.write(value);
+        }
+    }
+}
 
-impl Primitive for bool {}
-impl Primitive for char {}
-impl Primitive for f32 {}
-impl Primitive for f64 {}
-impl Primitive for i8 {}
-impl Primitive for i16 {}
-impl Primitive for i32 {}
-impl Primitive for i64 {}
-impl Primitive for i128 {}
-impl Primitive for isize {}
-impl Primitive for u8 {}
-impl Primitive for u16 {}
-impl Primitive for u32 {}
-impl Primitive for u64 {}
-impl Primitive for u128 {}
-impl Primitive for usize {}
+macro_rules! impl_primitive {
+    ($($type:ty: |$value:ident| $is_zero:expr),+ $(,)?) => {
+        $(
+            unsafe impl Primitive for $type {
+                fn fill_spare(spare: &mut [MaybeUninit<Self>], $value: Self) {
+                    if $is_zero {
+                        unsafe {
+                            write_bytes(spare.as_mut_ptr(), 0u8, spare.len())
+                        }
+                    } else {
+                        spare.fill(MaybeUninit::new($value));
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_primitive! {
+    f32: |value| value.to_bits() == 0,
+    f64: |value| value.to_bits() == 0,
+    i8: |value| value == 0,
+    i16: |value| value == 0,
+    i32: |value| value == 0,
+    i64: |value| value == 0,
+    i128: |value| value == 0,
+    isize: |value| value == 0,
+    u8: |value| value == 0,
+    u16: |value| value == 0,
+    u32: |value| value == 0,
+    u64: |value| value == 0,
+    u128: |value| value == 0,
+    usize: |value| value == 0,
+}
+
+/// Reinterprets a slice of *initialized* `MaybeUninit<T>` elements as `&[T]`.
+///
+/// # Safety
+///
+/// All elements of `slice` **must** have been initialized.
+pub(crate) unsafe fn assume_init<T>(slice: &[MaybeUninit<T>]) -> &[T] {
+    &*(slice as *const [MaybeUninit<T>] as *const [T])
+}
+
+/// Reinterprets a slice of `MaybeUninit<T>` elements as `&mut[T]`, which is
+/// how "spare" buffers are handed out.
+///
+/// # Safety
+///
+/// The caller **must** uphold the same contract as for the "spare" buffer of
+/// a **`SpareBuffer`**, i.e. only elements that have been written may be
+/// committed.
+pub(crate) unsafe fn assume_init_mut<T>(slice: &mut [MaybeUninit<T>]) -> &mut [T] {
+    &mut *(slice as *mut [MaybeUninit<T>] as *mut [T])
+}
+
+/// Returns the *all-zero* value of `T`, which is a valid value of every
+/// [**`Primitive`**](crate::Primitive) type.
+pub(crate) fn zeroed<T: Primitive>() -> T {
+    unsafe {
+        MaybeUninit::zeroed().assume_init()
+    }
+}
+
+/// Reinterprets a "spare" buffer as a `&mut[T]` slice that is handed to
+/// *foreign* code, such as a [`Read`](std::io::Read) implementation.
+///
+/// With the **`safe-only`** feature, the elements are *zero-filled* first,
+/// because a safe `Read` implementation is allowed to *read* the slice, too.
+pub(crate) fn foreign_mut<T: Primitive>(slice: &mut [MaybeUninit<T>]) -> &mut [T] {
+    #[cfg(feature = "safe-only")]
+    T::fill_spare(slice, zeroed());
+    unsafe {
+        assume_init_mut(slice)
+    }
+}
@@ -0,0 +1,148 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::{Result as IoResult, Error as IoError};
+use std::num::NonZeroUsize;
+use std::ptr::NonNull;
+use std::slice::from_raw_parts_mut;
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender};
+
+use crate::{Primitive, SpareBuffer, SpareCapacity};
+
+/// An *owned* handle to the "spare" buffer of a
+/// [**`SpareBuffer`**](crate::SpareBuffer), which can be sent to another
+/// thread in order to be filled there.
+///
+/// Once the "spare" buffer has been filled, the number of elements that are
+/// valid is reported back to the owning side by calling
+/// [`complete()`](Self::complete), which then triggers the commit. Dropping
+/// the handle without completing it is equivalent to completing it with a
+/// count of `0`.
+///
+/// Use [`SpareBuffer::lend_spare()`](crate::SpareBuffer::lend_spare) to
+/// obtain a new **`OwnedSpareRegion`**.
+pub struct OwnedSpareRegion<T>
+where
+    T: Primitive
+{
+    ptr: NonNull<T>,
+    len: usize,
+    completion: Option<SyncSender<usize>>,
+}
+
+unsafe impl<T> Send for OwnedSpareRegion<T>
+where
+    T: Primitive + Send
+{
+}
+
+impl<T> OwnedSpareRegion<T>
+where
+    T: Primitive
+{
+    /// Returns the length of the "spare" buffer.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the "spare" buffer has a length of zero.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns a `&mut[T]` slice of the "spare" buffer. No guarantees are
+    /// provided about the *initial* contents of the buffer!
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe {
+            from_raw_parts_mut(self.ptr.as_ptr(), self.len)
+        }
+    }
+
+    /// Reports that the first `filled` elements of the "spare" buffer have
+    /// been initialized, so that they will be committed by the owning side.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `filled` is greater than the length of the "spare" buffer.
+    pub fn complete(mut self, filled: usize) {
+        assert!(filled <= self.len, "Completed size exceeds the length of the spare buffer!");
+        if let Some(completion) = self.completion.take() {
+            let _ = completion.send(filled);
+        }
+    }
+}
+
+impl<T> Drop for OwnedSpareRegion<T>
+where
+    T: Primitive
+{
+    fn drop(&mut self) {
+        if let Some(completion) = self.completion.take() {
+            let _ = completion.send(0);
+        }
+    }
+}
+
+/// Waits for the completion of an outstanding **`OwnedSpareRegion`**, even
+/// when unwinding, so that the "spare" buffer outlives all writes to it.
+struct Completion(Option<Receiver<usize>>);
+
+impl Completion {
+    fn wait(mut self) -> usize {
+        self.0.take().map_or(0, |receiver| receiver.recv().unwrap_or(0))
+    }
+}
+
+impl Drop for Completion {
+    fn drop(&mut self) {
+        if let Some(receiver) = self.0.take() {
+            let _ = receiver.recv();
+        }
+    }
+}
+
+impl<'a, T, B> SpareBuffer<'a, T, B>
+where
+    T: Primitive,
+    B: SpareCapacity<T>
+{
+    /// Allocates a "spare" buffer of the specified `length`, and *lends* it
+    /// to `f` as an [**`OwnedSpareRegion`**](crate::OwnedSpareRegion), which
+    /// can be sent to a worker thread to be filled there.
+    ///
+    /// While the region is outstanding, `f` has *read-only* access to all
+    /// "committed" elements, which are passed as a `&[T]` slice. After `f`
+    /// has returned, this function **blocks** until the region has been
+    /// [completed](crate::OwnedSpareRegion::complete) or dropped, and then
+    /// commits the reported number of elements.
+    ///
+    /// Returns the value returned by `f`, and the number of elements that
+    /// have been committed.
+    ///
+    /// Note that this function will block *forever*, if the region is leaked
+    /// (e.g. by [`std::mem::forget()`]) instead of being completed or dropped.
+    ///
+    /// # Errors
+    ///
+    /// Fails with the same errors as [`commit()`](Self::commit).
+    pub fn lend_spare<F, R>(&mut self, length: NonZeroUsize, f: F) -> IoResult<(R, usize)>
+    where
+        F: FnOnce(OwnedSpareRegion<T>, &[T]) -> R
+    {
+        let (sender, receiver) = sync_channel(1);
+        let completion = Completion(Some(receiver));
+        let result = self.guarded(|buffer| {
+            let (committed, spare) = buffer.try_split_at_spare_mut(length)?;
+            let region = OwnedSpareRegion {
+                ptr: NonNull::from(spare).cast(),
+                len: length.get(),
+                completion: Some(sender),
+            };
+            Ok::<R, IoError>(f(region, committed))
+        })?;
+        let filled = completion.wait();
+        self.commit(filled)?;
+        Ok((result, filled))
+    }
+}
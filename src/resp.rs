@@ -0,0 +1,67 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::{Read, Result as IoResult, Error as IoError, ErrorKind};
+use std::num::NonZeroUsize;
+
+use crate::SpareBuffer;
+
+impl<'a> SpareBuffer<'a, u8> {
+    /// Reads the payload of a [RESP](https://redis.io/docs/latest/develop/reference/protocol-spec/)
+    /// bulk string, whose length `declared_len` has already been parsed from
+    /// the `$<length>\r\n` header, followed by the terminating `\r\n`.
+    ///
+    /// The "spare" buffer is allocated *exactly* for the declared length and
+    /// filled *directly* from `reader`. The payload is committed only after it
+    /// has been read completely, and the terminator has been validated.
+    ///
+    /// # Errors
+    ///
+    /// If a length limit has been specified, then this function will fail,
+    /// *before* reading any data, if the declared length would cause the total
+    /// length of the underlying vector to exceed the specified limit, or if it
+    /// exceeds the [maximum allocation size](Self::with_max_allocation).
+    ///
+    /// Fails with [`ErrorKind::UnexpectedEof`], if the stream ends before the
+    /// declared length was read, or with [`ErrorKind::InvalidData`], if the
+    /// payload is **not** followed by `\r\n`. Errors of the underlying reader
+    /// are passed through, unless they are retried according to the
+    /// [retry policy](Self::retry_policy). Nothing is committed, if an error
+    /// occurs, and the "spare" buffer is discarded.
+    pub fn read_bulk_string<R>(&mut self, reader: &mut R, declared_len: usize) -> IoResult<()>
+    where
+        R: Read
+    {
+        if !self.fits_limit(declared_len) {
+            return Err(IoError::new(ErrorKind::OutOfMemory, "The declared length exceeds the specified limit!"));
+        }
+        if let Err(error) = self.read_bulk_payload(reader, declared_len) {
+            self.discard_spare();
+            return Err(error);
+        }
+        if declared_len > 0 {
+            self.commit(declared_len)?;
+        }
+        Ok(())
+    }
+
+    /// Reads the payload and the terminator of a bulk string into the "spare"
+    /// buffer, for *internal* use. See
+    /// [`read_bulk_string()`](Self::read_bulk_string) for details.
+    fn read_bulk_payload<R>(&mut self, reader: &mut R, declared_len: usize) -> IoResult<()>
+    where
+        R: Read
+    {
+        if let Some(length) = NonZeroUsize::new(declared_len) {
+            let policy = self.retry_policy();
+            policy.read_exact(reader, &mut self.try_spare_mut(length)?[..declared_len])?;
+        }
+        let mut terminator = [0u8; 2];
+        self.retry_policy().read_exact(reader, &mut terminator)?;
+        if terminator != *b"\r\n" {
+            return Err(IoError::new(ErrorKind::InvalidData, "Bulk string is not terminated by CRLF!"));
+        }
+        Ok(())
+    }
+}
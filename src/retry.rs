@@ -0,0 +1,157 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::hint::spin_loop;
+use std::io::{Read, Result as IoResult, Error as IoError, ErrorKind};
+use std::num::NonZeroUsize;
+use std::thread::sleep;
+use std::time::Duration;
+
+/// How to handle errors of kind [`ErrorKind::WouldBlock`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WouldBlockPolicy {
+    /// Fail immediately, i.e. pass the error through to the caller.
+    Fail,
+    /// Retry immediately, after issuing a spin-loop hint.
+    Spin,
+    /// Retry after sleeping for a duration that starts at `initial` and is
+    /// *doubled* with each attempt, but never exceeds `max`.
+    Backoff {
+        initial: Duration,
+        max: Duration,
+    },
+}
+
+/// Controls how the fill helpers of this crate handle *transient* I/O errors.
+///
+/// By default, reads that fail with [`ErrorKind::Interrupted`] are retried
+/// without limit, whereas errors of kind [`ErrorKind::WouldBlock`] are passed
+/// through. The policy of a [**`SpareBuffer`**](crate::SpareBuffer) can be
+/// changed with [`with_retry_policy()`](crate::SpareBuffer::with_retry_policy).
+///
+/// The *asynchronous* helpers only ever retry [`ErrorKind::Interrupted`],
+/// because blocking or spinning would stall the executor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    interrupted: bool,
+    would_block: WouldBlockPolicy,
+    max_attempts: Option<NonZeroUsize>,
+}
+
+impl RetryPolicy {
+    /// Creates the default **`RetryPolicy`**.
+    pub const fn new() -> Self {
+        Self {
+            interrupted: true,
+            would_block: WouldBlockPolicy::Fail,
+            max_attempts: None,
+        }
+    }
+
+    /// Creates a **`RetryPolicy`** that never retries, i.e. passes *all*
+    /// errors through to the caller.
+    pub const fn never() -> Self {
+        Self {
+            interrupted: false,
+            would_block: WouldBlockPolicy::Fail,
+            max_attempts: None,
+        }
+    }
+
+    /// Sets whether errors of kind [`ErrorKind::Interrupted`] are retried.
+    pub const fn retry_interrupted(mut self, retry: bool) -> Self {
+        self.interrupted = retry;
+        self
+    }
+
+    /// Sets how errors of kind [`ErrorKind::WouldBlock`] are handled.
+    pub const fn on_would_block(mut self, policy: WouldBlockPolicy) -> Self {
+        self.would_block = policy;
+        self
+    }
+
+    /// Sets the maximum number of attempts per operation, *including* the
+    /// first one. Once exhausted, the last error is passed through. `None`
+    /// means that there is **no** limit.
+    pub const fn max_attempts(mut self, max_attempts: Option<NonZeroUsize>) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Returns `true` if errors of kind [`ErrorKind::Interrupted`] are
+    /// retried.
+    pub fn retries_interrupted(&self) -> bool {
+        self.interrupted
+    }
+
+    /// Returns how errors of kind [`ErrorKind::WouldBlock`] are handled.
+    pub fn would_block(&self) -> WouldBlockPolicy {
+        self.would_block
+    }
+
+    /// Returns the maximum number of attempts per operation, if limited.
+    pub fn attempts(&self) -> Option<NonZeroUsize> {
+        self.max_attempts
+    }
+
+    /// Returns `true` if another attempt may be made, after `attempts`
+    /// attempts have failed with `error`. Waits as required by the policy.
+    pub(crate) fn should_retry(&self, error: &IoError, attempts: usize, blocking: bool) -> bool {
+        if self.max_attempts.is_some_and(|max_attempts| attempts >= max_attempts.get()) {
+            return false;
+        }
+        match error.kind() {
+            ErrorKind::Interrupted => self.interrupted,
+            ErrorKind::WouldBlock if blocking => match self.would_block {
+                WouldBlockPolicy::Fail => false,
+                WouldBlockPolicy::Spin => {
+                    spin_loop();
+                    true
+                },
+                WouldBlockPolicy::Backoff { initial, max } => {
+                    let factor = 1u32.checked_shl(attempts.saturating_sub(1) as u32).unwrap_or(u32::MAX);
+                    sleep(initial.saturating_mul(factor).min(max));
+                    true
+                },
+            },
+            _ => false,
+        }
+    }
+
+    /// Runs `operation`, retrying it as long as permitted by the policy.
+    pub(crate) fn retry<F, R>(&self, mut operation: F) -> IoResult<R>
+    where
+        F: FnMut() -> IoResult<R>
+    {
+        let mut attempts = 0usize;
+        loop {
+            attempts += 1;
+            match operation() {
+                Err(error) if self.should_retry(&error, attempts, true) => continue,
+                result => return result,
+            }
+        }
+    }
+
+    /// Reads *exactly* enough bytes from `reader` to fill `buffer`, retrying
+    /// reads as permitted by the policy.
+    pub(crate) fn read_exact<R>(&self, reader: &mut R, mut buffer: &mut [u8]) -> IoResult<()>
+    where
+        R: Read
+    {
+        while !buffer.is_empty() {
+            match self.retry(|| reader.read(buffer))? {
+                0 => return Err(IoError::new(ErrorKind::UnexpectedEof, "The stream ended prematurely!")),
+                count => buffer = &mut buffer[count..],
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,247 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::{BufRead, Read, Result as IoResult, Error as IoError, ErrorKind};
+use std::mem::MaybeUninit;
+use std::num::NonZeroUsize;
+
+use crate::{Primitive, RetryPolicy};
+use crate::primitive::{assume_init, foreign_mut};
+#[cfg(not(feature = "safe-only"))]
+use crate::primitive::assume_init_mut;
+use crate::sanitizer::{mark_init, mark_uninit};
+
+/// A *fixed-capacity* ring buffer, for streaming parsers that consume the
+/// data as fast as it is read.
+///
+/// New data is written *directly* into the "spare" part of the ring, at the
+/// tail, **without** initializing the memory first, exactly like with a
+/// [**`SpareBuffer`**](crate::SpareBuffer). Because the free space may wrap
+/// around the end of the storage, the "spare" buffer is handed out as *two*
+/// slices, the second of which may be empty. Committed data is consumed at
+/// the head.
+///
+/// The **`SpareRing`** **never** reallocates. Once it is full, data must be
+/// [consumed](Self::consume) before more data can be committed.
+pub struct SpareRing<T>
+where
+    T: Primitive
+{
+    storage: Box<[MaybeUninit<T>]>,
+    head: usize,
+    length: usize,
+    allocated: bool,
+}
+
+impl<T> SpareRing<T>
+where
+    T: Primitive
+{
+    /// Creates a new, empty **`SpareRing`** with the given `capacity`.
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        Self {
+            storage: Box::new_uninit_slice(capacity.get()),
+            head: 0,
+            length: 0,
+            allocated: false,
+        }
+    }
+
+    /// Returns the *fixed* capacity of the ring.
+    pub fn capacity(&self) -> usize {
+        self.storage.len()
+    }
+
+    /// Returns the number of "committed" elements that have **not** been
+    /// consumed yet.
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Returns `true` if the ring contains no "committed" elements.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Returns `true` if the ring is full, i.e. nothing can be committed,
+    /// before some elements have been consumed.
+    pub fn is_full(&self) -> bool {
+        self.length == self.storage.len()
+    }
+
+    /// Returns the number of elements that can still be committed.
+    pub fn free(&self) -> usize {
+        self.storage.len() - self.length
+    }
+
+    /// Returns the "committed" elements, in order, as two `&[T]` slices. The
+    /// second slice is empty, unless the data wraps around the end of the
+    /// storage.
+    pub fn data(&self) -> (&[T], &[T]) {
+        let (first, second) = split_ring(&self.storage, self.head, self.length);
+        unsafe {
+            (assume_init(first), assume_init(second))
+        }
+    }
+
+    /// Allocates the "spare" buffer, i.e. *all* of the free space at the
+    /// tail of the ring, as two `&mut[T]` slices. The second slice is empty,
+    /// unless the free space wraps around the end of the storage. Both slices
+    /// are empty, if the ring is full.
+    ///
+    /// No guarantees are provided about the *initial* contents of the "spare"
+    /// buffer! It is **not** considered to be a valid part of the ring, until
+    /// the [`commit()`](Self::commit) function is called eventually.
+    ///
+    /// Not available with the **`safe-only`** feature.
+    #[cfg(not(feature = "safe-only"))]
+    pub fn allocate_spare(&mut self) -> (&mut[T], &mut[T]) {
+        let (first, second) = self.spare_uninit();
+        unsafe {
+            (assume_init_mut(first), assume_init_mut(second))
+        }
+    }
+
+    /// Allocates the "spare" buffer, for *internal* use. See
+    /// [`allocate_spare()`](Self::allocate_spare) for details.
+    fn spare_uninit(&mut self) -> (&mut[MaybeUninit<T>], &mut[MaybeUninit<T>]) {
+        self.allocated = true;
+        let tail = (self.head + self.length) % self.storage.len();
+        let free = self.storage.len() - self.length;
+        let (first, second) = split_ring_mut(&mut self.storage, tail, free);
+        mark_uninit(first.as_ptr(), first.len());
+        mark_uninit(second.as_ptr(), second.len());
+        (first, second)
+    }
+
+    /// Commits the first `additional` elements of the "spare" buffer, i.e.
+    /// *appends* them at the tail of the ring **without** copying the data.
+    /// If the "spare" buffer consists of two slices, the elements of the
+    /// first slice are committed first.
+    ///
+    /// All elements to be committed **must** have been initialized, exactly
+    /// like for [`SpareBuffer::commit()`](crate::SpareBuffer::commit). This
+    /// function always invalidates the current "spare" buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `additional` is greater than the free space, or if **no**
+    /// "spare" buffer was allocated before!
+    pub fn commit(&mut self, additional: usize) {
+        assert!(std::mem::replace(&mut self.allocated, false), "No spare buffer allocated!");
+        assert!(additional <= self.free(), "Commit size exceeds available capacity!");
+        let tail = (self.head + self.length) % self.storage.len();
+        let (first, second) = split_ring(&self.storage, tail, additional);
+        mark_init(first.as_ptr(), first.len());
+        mark_init(second.as_ptr(), second.len());
+        self.length += additional;
+    }
+
+    /// Consumes the first `amount` "committed" elements at the head of the
+    /// ring, so that their space can be reused. The amount is clamped to the
+    /// number of committed elements.
+    ///
+    /// The current "spare" buffer is invalidated.
+    pub fn consume(&mut self, amount: usize) {
+        self.allocated = false;
+        let amount = amount.min(self.length);
+        self.length -= amount;
+        self.head = if self.length > 0 { (self.head + amount) % self.storage.len() } else { 0 };
+    }
+
+    /// Consumes *all* "committed" elements.
+    pub fn clear(&mut self) {
+        self.consume(self.length)
+    }
+
+    /// Discards the current "spare" buffer, if any, **without** committing
+    /// anything.
+    fn discard(&mut self) {
+        self.allocated = false;
+    }
+}
+
+impl SpareRing<u8> {
+    /// Reads from `reader` *directly* into the free space at the tail of the
+    /// ring, and commits the data. Reads that fail with
+    /// [`ErrorKind::Interrupted`](std::io::ErrorKind::Interrupted) are
+    /// retried.
+    ///
+    /// Only the *first* slice of the "spare" buffer is read into, so that a
+    /// single call to [`read()`](std::io::Read::read) is issued. Returns the
+    /// number of bytes that have been committed, which is `0` at the end of
+    /// the stream.
+    ///
+    /// # Errors
+    ///
+    /// Fails with an error of kind [`ErrorKind::OutOfMemory`], if the ring is
+    /// full. Errors returned by the `reader` are passed through.
+    pub fn read_from<R>(&mut self, reader: &mut R) -> IoResult<usize>
+    where
+        R: Read
+    {
+        if self.is_full() {
+            return Err(IoError::new(ErrorKind::OutOfMemory, "The ring buffer is full!"));
+        }
+        let spare = foreign_mut(self.spare_uninit().0);
+        match RetryPolicy::new().retry(|| reader.read(spare)) {
+            Ok(count) => {
+                self.commit(count);
+                Ok(count)
+            },
+            Err(error) => {
+                self.discard();
+                Err(error)
+            },
+        }
+    }
+}
+
+/// Reads and consumes the "committed" bytes, at the head of the ring.
+impl Read for SpareRing<u8> {
+    fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
+        let (first, second) = self.data();
+        let count = buf.len().min(first.len() + second.len());
+        let split = count.min(first.len());
+        buf[..split].copy_from_slice(&first[..split]);
+        buf[split..count].copy_from_slice(&second[..count - split]);
+        self.consume(count);
+        Ok(count)
+    }
+}
+
+/// Returns the *contiguous* "committed" bytes at the head of the ring. Once
+/// they have been consumed, the bytes that wrapped around the end of the
+/// storage are returned.
+impl BufRead for SpareRing<u8> {
+    fn fill_buf(&mut self) -> IoResult<&[u8]> {
+        Ok(self.data().0)
+    }
+
+    fn consume(&mut self, amount: usize) {
+        SpareRing::consume(self, amount)
+    }
+}
+
+/// Splits the `length` elements, starting at `start`, into two slices, the
+/// second of which holds the elements that wrap around the end of `storage`.
+fn split_ring<T>(storage: &[T], start: usize, length: usize) -> (&[T], &[T]) {
+    let (before, after) = storage.split_at(start);
+    if length <= after.len() {
+        (&after[..length], &[])
+    } else {
+        (after, &before[..length - after.len()])
+    }
+}
+
+/// The same as [`split_ring()`](self::split_ring), but for `&mut[T]` slices.
+fn split_ring_mut<T>(storage: &mut [T], start: usize, length: usize) -> (&mut [T], &mut [T]) {
+    let (before, after) = storage.split_at_mut(start);
+    if length <= after.len() {
+        (&mut after[..length], &mut [])
+    } else {
+        let wrapped = length - after.len();
+        (after, &mut before[..wrapped])
+    }
+}
@@ -0,0 +1,260 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::{BufRead, Read, Result as IoResult, Error as IoError, ErrorKind};
+use std::mem::MaybeUninit;
+use std::num::NonZeroUsize;
+
+use crate::{Primitive, RetryPolicy};
+#[cfg(not(feature = "safe-only"))]
+use crate::primitive::assume_init_mut;
+use crate::primitive::foreign_mut;
+use crate::sanitizer::{mark_init, mark_uninit};
+
+/// A *segmented* alternative to the [**`SpareBuffer`**](crate::SpareBuffer),
+/// which grows **without** ever moving the committed data.
+///
+/// The data is stored in a list of *segments*, each of which is a vector with
+/// a fixed capacity of (at least) `segment_size` elements. If the "spare"
+/// capacity of the last segment is **not** sufficient for a new "spare"
+/// buffer, a new segment is opened, instead of reallocating. This avoids the
+/// huge copies and the temporary *doubling* of the memory consumption that
+/// occur when accumulating multi-gigabyte payloads in a single vector.
+///
+/// The committed data can be iterated segment by segment, read via the
+/// [**`RopeReader`**](crate::RopeReader), or finally be concatenated into a
+/// single vector with [`into_contiguous()`](Self::into_contiguous).
+pub struct SpareRope<T>
+where
+    T: Primitive
+{
+    segments: Vec<Vec<T>>,
+    segment_size: NonZeroUsize,
+    limit: Option<NonZeroUsize>,
+    length: usize,
+    allocated: bool,
+}
+
+impl<T> SpareRope<T>
+where
+    T: Primitive
+{
+    /// Creates a new, empty **`SpareRope`** with the given `segment_size`.
+    ///
+    /// An *optional* `limit` for the *total* length can be specified. The
+    /// [`commit()`](Self::commit) fails, if it would exceed this limit.
+    pub fn new(segment_size: NonZeroUsize, limit: Option<NonZeroUsize>) -> Self {
+        Self {
+            segments: Vec::new(),
+            segment_size,
+            limit,
+            length: 0,
+            allocated: false,
+        }
+    }
+
+    /// Returns the *total* number of "committed" elements in all segments.
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Returns `true` if the **`SpareRope`** contains no "committed"
+    /// elements.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Returns the length limit, if a limit has been specified. Otherwise
+    /// `None` is returned.
+    pub fn limit(&self) -> Option<NonZeroUsize> {
+        self.limit
+    }
+
+    /// Returns the minimum capacity of a new segment.
+    pub fn segment_size(&self) -> NonZeroUsize {
+        self.segment_size
+    }
+
+    /// Returns an iterator over the "committed" elements of all *non-empty*
+    /// segments, as `&[T]` slices, in order.
+    pub fn segments(&self) -> impl Iterator<Item = &[T]> + '_ {
+        self.segments.iter().filter(|segment| !segment.is_empty()).map(Vec::as_slice)
+    }
+
+    /// Returns an iterator over *all* "committed" elements, in order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        self.segments().flatten()
+    }
+
+    /// Allocates a "spare" buffer of the specified `length`.
+    ///
+    /// If the "spare" capacity of the last segment is **not** sufficient, a
+    /// new segment with a capacity of `max(length, segment_size)` elements is
+    /// opened. The committed data is **never** moved.
+    ///
+    /// Returns a `&mut[T]` slice which allows the caller to access the
+    /// allocated "spare" buffer. No guarantees are provided about the
+    /// *initial* contents of the buffer! The "spare" buffer is **not**
+    /// considered to be a valid part of the **`SpareRope`**, until the
+    /// [`commit()`](Self::commit) function is called eventually.
+    ///
+    /// Not available with the **`safe-only`** feature.
+    #[cfg(not(feature = "safe-only"))]
+    pub fn allocate_spare(&mut self, length: NonZeroUsize) -> &mut[T] {
+        unsafe {
+            assume_init_mut(self.spare_uninit(length))
+        }
+    }
+
+    /// Allocates a "spare" buffer of the specified `length`, for *internal*
+    /// use. See [`allocate_spare()`](Self::allocate_spare) for details.
+    fn spare_uninit(&mut self, length: NonZeroUsize) -> &mut[MaybeUninit<T>] {
+        let required = length.get();
+        if self.segments.last().is_some_and(|segment| segment.is_empty() && segment.capacity() < required) {
+            self.segments.pop();
+        }
+        if self.segments.last().is_none_or(|segment| segment.capacity() - segment.len() < required) {
+            self.segments.push(Vec::with_capacity(required.max(self.segment_size.get())));
+        }
+        self.allocated = true;
+        let spare = self.segments.last_mut().unwrap().spare_capacity_mut();
+        mark_uninit(spare.as_ptr(), spare.len());
+        spare
+    }
+
+    /// Commits the first `additional` elements of the "spare" buffer, i.e.
+    /// *appends* them to the last segment **without** copying the data.
+    ///
+    /// The same requirements as for
+    /// [`SpareBuffer::commit()`](crate::SpareBuffer::commit) apply, i.e. all
+    /// elements to be committed **must** have been initialized. This function
+    /// always invalidates the current "spare" buffer.
+    ///
+    /// # Errors
+    ///
+    /// If a length limit has been specified, then this function will fail, if
+    /// adding `additional` more elements would cause the total length to
+    /// exceed the specified limit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `additional` is greater than the available "spare" capacity,
+    /// or if **no** "spare" buffer was allocated before!
+    pub fn commit(&mut self, additional: usize) -> IoResult<()> {
+        assert!(std::mem::replace(&mut self.allocated, false), "No spare buffer allocated!");
+        if additional > 0 {
+            let segment = self.segments.last_mut().expect("No segment available!");
+            assert!(additional <= segment.capacity() - segment.len(), "Commit size exceeds available capacity!");
+            let new_length = self.length.checked_add(additional).expect("Numerical overflow! (new_length)");
+            if new_length > self.limit.map_or(usize::MAX, NonZeroUsize::get) {
+                return Err(IoError::new(ErrorKind::OutOfMemory, "The new length exceeds the specified limit!"));
+            }
+            unsafe {
+                mark_init(segment.as_ptr().add(segment.len()), additional);
+                segment.set_len(segment.len() + additional);
+            }
+            self.length = new_length;
+        }
+        Ok(())
+    }
+
+    /// Discards the current "spare" buffer, if any, **without** committing
+    /// anything.
+    fn discard(&mut self) {
+        self.allocated = false;
+    }
+
+    /// Concatenates all segments into a *single* vector.
+    ///
+    /// If there is only one non-empty segment, it is returned *as is*.
+    /// Otherwise, each element is copied exactly once.
+    pub fn into_contiguous(mut self) -> Vec<T> {
+        self.segments.retain(|segment| !segment.is_empty());
+        if self.segments.len() <= 1 {
+            return self.segments.pop().unwrap_or_default();
+        }
+        let mut contiguous = Vec::with_capacity(self.length);
+        for segment in &self.segments {
+            contiguous.extend_from_slice(segment);
+        }
+        contiguous
+    }
+}
+
+impl SpareRope<u8> {
+    /// Reads up to `length` bytes from `reader` into the "spare" buffer, and
+    /// commits the data. Reads that fail with
+    /// [`ErrorKind::Interrupted`](std::io::ErrorKind::Interrupted) are
+    /// retried.
+    ///
+    /// Returns the number of bytes that have been committed, which is `0` at
+    /// the end of the stream.
+    ///
+    /// # Errors
+    ///
+    /// Errors returned by the `reader`, as well as errors returned by
+    /// [`commit()`](Self::commit), are passed through.
+    pub fn read_from<R>(&mut self, reader: &mut R, length: NonZeroUsize) -> IoResult<usize>
+    where
+        R: Read
+    {
+        let spare = foreign_mut(&mut self.spare_uninit(length)[..length.get()]);
+        match RetryPolicy::new().retry(|| reader.read(spare)) {
+            Ok(count) => self.commit(count).map(|_| count),
+            Err(error) => {
+                self.discard();
+                Err(error)
+            },
+        }
+    }
+
+    /// Returns a [**`RopeReader`**](crate::RopeReader) that reads all
+    /// "committed" bytes, from the beginning.
+    pub fn reader(&self) -> RopeReader<'_> {
+        RopeReader {
+            segments: &self.segments,
+            offset: 0,
+        }
+    }
+}
+
+/// Reads the "committed" bytes of a [**`SpareRope`**](crate::SpareRope),
+/// segment by segment.
+///
+/// Use [`SpareRope::reader()`](crate::SpareRope::reader) to obtain a new
+/// **`RopeReader`**.
+#[derive(Clone)]
+pub struct RopeReader<'a> {
+    segments: &'a [Vec<u8>],
+    offset: usize,
+}
+
+impl<'a> Read for RopeReader<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
+        let available = self.fill_buf()?;
+        let count = buf.len().min(available.len());
+        buf[..count].copy_from_slice(&available[..count]);
+        self.consume(count);
+        Ok(count)
+    }
+}
+
+impl<'a> BufRead for RopeReader<'a> {
+    fn fill_buf(&mut self) -> IoResult<&[u8]> {
+        while let Some((segment, remaining)) = self.segments.split_first() {
+            if self.offset < segment.len() {
+                return Ok(&segment[self.offset..]);
+            }
+            self.segments = remaining;
+            self.offset = 0;
+        }
+        Ok(&[])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        if let Some(segment) = self.segments.first() {
+            self.offset = self.offset.saturating_add(amount).min(segment.len());
+        }
+    }
+}
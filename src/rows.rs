@@ -0,0 +1,191 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::{Result as IoResult, Error as IoError, ErrorKind};
+use std::num::NonZeroUsize;
+
+use crate::{Primitive, SpareBuffer};
+
+/// An adapter that decodes rows (scanlines) of an image *directly* into the
+/// "spare" capacity of a [**`SpareBuffer`**](crate::SpareBuffer).
+///
+/// Each row is written into the "spare" buffer and committed *separately*, so
+/// that the rows decoded so far can be processed progressively. The total
+/// decoded size is checked against the length limit *before* any row is
+/// decoded, which provides a hard cap for untrusted images.
+///
+/// Use [`SpareBuffer::decode_rows()`](crate::SpareBuffer::decode_rows) to
+/// create a new **`RowDecoder`**.
+pub struct RowDecoder<'b, 'a, T>
+where
+    T: Primitive
+{
+    buffer: &'b mut SpareBuffer<'a, T>,
+    row_len: NonZeroUsize,
+    rows: usize,
+    rows_decoded: usize,
+    start: usize,
+}
+
+impl<'a, T> SpareBuffer<'a, T>
+where
+    T: Primitive
+{
+    /// Creates a new [**`RowDecoder`**](crate::RowDecoder) for an image of
+    /// `rows` rows, each of which consists of `row_len` elements.
+    ///
+    /// The capacity for *all* rows is reserved up front.
+    ///
+    /// # Errors
+    ///
+    /// If a length limit has been specified, then this function will fail, if
+    /// the decoded image would cause the total length of the underlying vector
+    /// to exceed the specified limit.
+    pub fn decode_rows(&mut self, row_len: NonZeroUsize, rows: usize) -> IoResult<RowDecoder<'_, 'a, T>> {
+        let total = match row_len.get().checked_mul(rows) {
+            Some(total) if self.fits_limit(total) => total,
+            _ => return Err(IoError::new(ErrorKind::OutOfMemory, "The decoded size exceeds the specified limit!")),
+        };
+        if let Some(additional) = NonZeroUsize::new(total) {
+            self.try_spare_mut(additional)?;
+        }
+        let start = self.len();
+        Ok(RowDecoder {
+            buffer: self,
+            row_len,
+            rows,
+            rows_decoded: 0,
+            start,
+        })
+    }
+}
+
+impl<'b, 'a, T> RowDecoder<'b, 'a, T>
+where
+    T: Primitive
+{
+    /// Returns the length of a single row.
+    pub fn row_len(&self) -> NonZeroUsize {
+        self.row_len
+    }
+
+    /// Returns the total number of rows.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Returns the number of rows that have been decoded and committed so far.
+    pub fn rows_decoded(&self) -> usize {
+        self.rows_decoded
+    }
+
+    /// Returns `true` if *all* rows have been decoded and committed.
+    pub fn is_complete(&self) -> bool {
+        self.rows_decoded >= self.rows
+    }
+
+    /// Returns a `&[T]` slice of all rows that have been decoded and
+    /// committed so far.
+    pub fn decoded(&self) -> &[T] {
+        &self.buffer.data()[self.start..]
+    }
+
+    /// Returns the "spare" buffer for the next row, as a `&mut[T]` slice of
+    /// exactly [`row_len()`](Self::row_len) elements, or `None` if *all* rows
+    /// have been decoded already.
+    ///
+    /// The *whole* row **must** be filled with valid data, before it is
+    /// committed by calling [`commit_row()`](Self::commit_row).
+    ///
+    /// Not available with the **`safe-only`** feature.
+    #[cfg(not(feature = "safe-only"))]
+    pub fn next_row(&mut self) -> Option<&mut [T]> {
+        self.spare_row()
+    }
+
+    /// Commits the row that was previously returned by
+    /// [`next_row()`](Self::next_row), and returns a `&[T]` slice of the
+    /// committed row.
+    ///
+    /// Not available with the **`safe-only`** feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if **no** row was returned by [`next_row()`](Self::next_row)
+    /// before!
+    #[cfg(not(feature = "safe-only"))]
+    pub fn commit_row(&mut self) -> IoResult<&[T]> {
+        self.commit_spare_row()
+    }
+
+    /// Returns the "spare" buffer for the next row, for *internal* use.
+    #[cfg_attr(all(feature = "safe-only", not(feature = "png")), allow(dead_code))]
+    fn spare_row(&mut self) -> Option<&mut [T]> {
+        if self.is_complete() {
+            return None;
+        }
+        Some(&mut self.buffer.spare_mut(self.row_len)[..self.row_len.get()])
+    }
+
+    /// Commits the row that was previously returned by
+    /// [`spare_row()`](Self::spare_row), for *internal* use.
+    #[cfg_attr(all(feature = "safe-only", not(feature = "png")), allow(dead_code))]
+    fn commit_spare_row(&mut self) -> IoResult<&[T]> {
+        self.buffer.commit(self.row_len.get())?;
+        self.rows_decoded += 1;
+        let end = self.buffer.len();
+        Ok(&self.buffer.data()[end - self.row_len.get()..])
+    }
+}
+
+#[cfg(feature = "png")]
+impl<'a> SpareBuffer<'a, u8> {
+    /// Creates a new [**`RowDecoder`**](crate::RowDecoder) for the current
+    /// frame of the given [`png::Reader`].
+    ///
+    /// The length of a row, and the total number of rows, are derived from
+    /// the image header. Use [`RowDecoder::read_png_row()`] to decode the rows.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`ErrorKind::Unsupported`], if the image is *interlaced*,
+    /// because interlaced passes can **not** be committed row by row.
+    /// Otherwise, the same errors as for [`decode_rows()`](Self::decode_rows)
+    /// may occur.
+    pub fn decode_png<R>(&mut self, reader: &png::Reader<R>) -> IoResult<RowDecoder<'_, 'a, u8>>
+    where
+        R: std::io::BufRead + std::io::Seek
+    {
+        let info = reader.info();
+        if info.interlaced {
+            return Err(IoError::new(ErrorKind::Unsupported, "Interlaced images are not supported!"));
+        }
+        let row_len = reader.output_line_size(info.width).and_then(NonZeroUsize::new)
+            .ok_or_else(|| IoError::new(ErrorKind::InvalidData, "Invalid image dimensions!"))?;
+        let rows = usize::try_from(info.height).map_err(|_| IoError::new(ErrorKind::OutOfMemory, "The decoded size exceeds the specified limit!"))?;
+        self.decode_rows(row_len, rows)
+    }
+}
+
+#[cfg(feature = "png")]
+impl<'b, 'a> RowDecoder<'b, 'a, u8> {
+    /// Decodes the next row from the given [`png::Reader`] *directly* into the
+    /// "spare" buffer and commits it.
+    ///
+    /// Returns a `&[u8]` slice of the committed row, or `None` if *all* rows
+    /// have been decoded already.
+    pub fn read_png_row<R>(&mut self, reader: &mut png::Reader<R>) -> IoResult<Option<&[u8]>>
+    where
+        R: std::io::BufRead + std::io::Seek
+    {
+        let row = match self.spare_row() {
+            Some(row) => row,
+            None => return Ok(None),
+        };
+        match reader.read_row(row)? {
+            Some(_) => self.commit_spare_row().map(Some),
+            None => Err(IoError::new(ErrorKind::UnexpectedEof, "The image ended prematurely!")),
+        }
+    }
+}
@@ -0,0 +1,124 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+#[cfg(feature = "msan")]
+use std::ffi::c_void;
+#[cfg(any(debug_assertions, feature = "msan", feature = "paranoid", feature = "valgrind"))]
+use std::mem::size_of;
+#[cfg(any(debug_assertions, feature = "paranoid"))]
+use std::ptr::write_bytes;
+
+#[cfg(feature = "msan")]
+extern "C" {
+    fn __msan_allocated_memory(data: *const c_void, size: usize);
+    fn __msan_unpoison(data: *const c_void, size: usize);
+}
+
+/// Marks `len` elements at `ptr` as *uninitialized*, so that reads of the
+/// "spare" buffer are flagged by MemorySanitizer or Memcheck, until they are
+/// written.
+#[cfg(any(feature = "msan", feature = "valgrind"))]
+#[inline]
+pub(crate) fn mark_uninit<T>(ptr: *const T, len: usize) {
+    let size = len * size_of::<T>();
+    #[cfg(feature = "msan")]
+    unsafe {
+        __msan_allocated_memory(ptr.cast(), size)
+    }
+    #[cfg(feature = "valgrind")]
+    valgrind::client_request(valgrind::MAKE_MEM_UNDEFINED, ptr as usize, size);
+}
+
+/// Marks `len` elements at `ptr` as *initialized*, since they have been
+/// committed.
+#[cfg(any(feature = "msan", feature = "valgrind"))]
+#[inline]
+pub(crate) fn mark_init<T>(ptr: *const T, len: usize) {
+    let size = len * size_of::<T>();
+    #[cfg(feature = "msan")]
+    unsafe {
+        __msan_unpoison(ptr.cast(), size)
+    }
+    #[cfg(feature = "valgrind")]
+    valgrind::client_request(valgrind::MAKE_MEM_DEFINED, ptr as usize, size);
+}
+
+/// The byte pattern that a newly allocated "spare" buffer is filled with, in
+/// debug builds, or with the **`paranoid`** feature.
+#[cfg(any(debug_assertions, feature = "paranoid"))]
+const POISON: u8 = 0xCD;
+
+/// Fills `len` elements at `ptr` with the [`POISON`] pattern, so that
+/// committing elements that have **not** been written is instantly visible.
+#[cfg(any(debug_assertions, feature = "paranoid"))]
+#[inline]
+pub(crate) fn poison<T>(ptr: *mut T, len: usize) {
+    unsafe {
+        write_bytes(ptr.cast::<u8>(), POISON, len * size_of::<T>())
+    }
+}
+
+#[cfg(not(any(debug_assertions, feature = "paranoid")))]
+#[inline(always)]
+pub(crate) fn poison<T>(_ptr: *mut T, _len: usize) {}
+
+#[cfg(not(any(feature = "msan", feature = "valgrind")))]
+#[inline(always)]
+pub(crate) fn mark_uninit<T>(_ptr: *const T, _len: usize) {}
+
+#[cfg(not(any(feature = "msan", feature = "valgrind")))]
+#[inline(always)]
+pub(crate) fn mark_init<T>(_ptr: *const T, _len: usize) {}
+
+/// Valgrind client requests, as defined in `valgrind.h` and `memcheck.h`.
+///
+/// The "special instruction" sequence is a **no-op** when the program is
+/// **not** running under Valgrind.
+#[cfg(feature = "valgrind")]
+mod valgrind {
+    /// `VG_USERREQ_TOOL_BASE('M', 'C') + 1`
+    pub const MAKE_MEM_UNDEFINED: usize = 0x4D43_0001;
+    /// `VG_USERREQ_TOOL_BASE('M', 'C') + 2`
+    pub const MAKE_MEM_DEFINED: usize = 0x4D43_0002;
+
+    #[cfg(target_arch = "x86_64")]
+    #[inline]
+    pub fn client_request(request: usize, addr: usize, len: usize) {
+        let args: [usize; 6] = [request, addr, len, 0, 0, 0];
+        unsafe {
+            std::arch::asm!(
+                "rol rdi, 3",
+                "rol rdi, 13",
+                "rol rdi, 61",
+                "rol rdi, 51",
+                "xchg rbx, rbx",
+                in("rax") args.as_ptr(),
+                inout("rdx") 0usize => _,
+                options(nostack)
+            );
+        }
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    #[inline]
+    pub fn client_request(request: usize, addr: usize, len: usize) {
+        let args: [usize; 6] = [request, addr, len, 0, 0, 0];
+        unsafe {
+            std::arch::asm!(
+                "ror x12, x12, #3",
+                "ror x12, x12, #13",
+                "ror x12, x12, #51",
+                "ror x12, x12, #61",
+                "orr x10, x10, x10",
+                in("x4") args.as_ptr(),
+                inout("x3") 0usize => _,
+                options(nostack)
+            );
+        }
+    }
+
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+    #[inline(always)]
+    pub fn client_request(_request: usize, _addr: usize, _len: usize) {}
+}
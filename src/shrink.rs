@@ -0,0 +1,73 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::num::NonZeroUsize;
+
+/// Controls when a [**`SpareBuffer`**](crate::SpareBuffer) *automatically*
+/// releases excess "spare" capacity, e.g. after a burst of traffic.
+///
+/// A commit of at most `low_watermark` elements counts as an *idle* commit.
+/// Once `idle_commits` idle commits happened in a row, and the unused "spare"
+/// capacity of the underlying vector exceeds `high_watermark` elements, the
+/// vector is shrunk, so that *at most* `low_watermark` elements of "spare"
+/// capacity are retained. A commit of more than `low_watermark` elements
+/// resets the count.
+///
+/// The policy of a [**`SpareBuffer`**](crate::SpareBuffer) can be set with
+/// [`with_shrink_policy()`](crate::SpareBuffer::with_shrink_policy).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ShrinkPolicy {
+    low_watermark: usize,
+    high_watermark: NonZeroUsize,
+    idle_commits: NonZeroUsize,
+}
+
+impl ShrinkPolicy {
+    /// Creates a new **`ShrinkPolicy`** with the given watermarks and the
+    /// number of consecutive idle commits that triggers the shrink.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `low_watermark` is **not** less than `high_watermark`!
+    pub fn new(low_watermark: usize, high_watermark: NonZeroUsize, idle_commits: NonZeroUsize) -> Self {
+        assert!(low_watermark < high_watermark.get(), "The low watermark must be less than the high watermark!");
+        Self {
+            low_watermark,
+            high_watermark,
+            idle_commits,
+        }
+    }
+
+    /// Returns the low watermark, in elements.
+    pub fn low_watermark(&self) -> usize {
+        self.low_watermark
+    }
+
+    /// Returns the high watermark, in elements.
+    pub fn high_watermark(&self) -> NonZeroUsize {
+        self.high_watermark
+    }
+
+    /// Returns the number of consecutive idle commits that triggers the
+    /// shrink.
+    pub fn idle_commits(&self) -> NonZeroUsize {
+        self.idle_commits
+    }
+
+    /// Updates the `idle` count after a commit of `additional` elements, and
+    /// returns the *new* capacity, if the vector is to be shrunk.
+    pub(crate) fn update(&self, idle: &mut usize, additional: usize, length: usize, capacity: usize) -> Option<usize> {
+        if additional > self.low_watermark {
+            *idle = 0;
+            return None;
+        }
+        *idle = idle.saturating_add(1);
+        if *idle >= self.idle_commits.get() && capacity - length > self.high_watermark.get() {
+            *idle = 0;
+            Some(length.saturating_add(self.low_watermark))
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,245 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::{Read, Result as IoResult};
+use std::mem::MaybeUninit;
+use std::num::NonZeroUsize;
+#[cfg(not(feature = "safe-only"))]
+use std::slice::from_raw_parts_mut;
+
+use crate::{Primitive, SpareCapacity};
+use crate::fixed::Fixed;
+use crate::primitive::assume_init_mut;
+
+/// A *fixed-capacity* buffer over *caller-provided* memory, i.e. a
+/// `&mut[MaybeUninit<T>]` slice, which **never** allocates.
+///
+/// Provides the same allocate/commit API as the
+/// [**`SpareBuffer`**](crate::SpareBuffer), for users who manage their own
+/// allocations, e.g. memory from an arena, a `static` or a memory-mapped
+/// file. The initialized length is tracked with the same commit semantics,
+/// and the "spare" buffer is simply the unused part of the slice.
+///
+/// This includes *persistently mapped* GPU memory, e.g. a host-visible
+/// Vulkan allocation, which can be wrapped via
+/// [`from_raw_parts()`](Self::from_raw_parts), so that decoded data is
+/// written *straight* into GPU-visible memory. Note that mapped memory is
+/// often *write-combining*, i.e. reading it back, e.g. via
+/// [`data()`](Self::data), is slow. A mapped [`wgpu`](https://crates.io/crates/wgpu)
+/// buffer can **not** be wrapped, because a `wgpu::BufferViewMut` only
+/// provides *write-only* access; use `SpareBuffer::upload_to_wgpu()` of the
+/// **`wgpu`** feature instead.
+///
+/// The **`SpareSlice`** also implements the
+/// [**`SpareCapacity`**](crate::SpareCapacity) trait, so that it can be used
+/// as the backend of a **`SpareBuffer`**. Reserving more than the fixed
+/// capacity *panics*, in that case.
+pub struct SpareSlice<'a, T>
+where
+    T: Primitive
+{
+    inner: Fixed<T, &'a mut [MaybeUninit<T>]>,
+}
+
+impl<'a, T> SpareSlice<'a, T>
+where
+    T: Primitive
+{
+    /// Creates a new, empty **`SpareSlice`** over the given `storage`. The
+    /// previous contents of the storage are ignored.
+    pub fn from(storage: &'a mut [MaybeUninit<T>]) -> Self {
+        Self {
+            inner: Fixed::new(storage),
+        }
+    }
+
+    /// Creates a new, empty **`SpareSlice`** over the `length` elements of
+    /// storage at `ptr`, e.g. a pointer to *mapped* memory, as returned by
+    /// `vkMapMemory()`. The previous contents of the storage are ignored.
+    ///
+    /// Not available with the **`safe-only`** feature.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` **must** be non-null, properly aligned, and valid for reads and
+    /// writes of `length` elements, for the *whole* lifetime `'a`, during
+    /// which the storage **must not** be accessed in any other way, e.g. it
+    /// **must not** be unmapped. This is exactly the contract of
+    /// [`std::slice::from_raw_parts_mut()`].
+    #[cfg(not(feature = "safe-only"))]
+    pub unsafe fn from_raw_parts(ptr: *mut MaybeUninit<T>, length: usize) -> Self {
+        Self::from(from_raw_parts_mut(ptr, length))
+    }
+
+    /// Returns the *fixed* capacity, i.e. the length of the storage.
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity()
+    }
+
+    /// Returns the number of "committed" elements.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if the slice contains no "committed" elements.
+    pub fn is_empty(&self) -> bool {
+        self.inner.len() == 0
+    }
+
+    /// Returns `true` if the slice is full, i.e. nothing can be committed
+    /// anymore.
+    pub fn is_full(&self) -> bool {
+        self.inner.free() == 0
+    }
+
+    /// Returns the number of elements that can still be committed.
+    pub fn free(&self) -> usize {
+        self.inner.free()
+    }
+
+    /// Returns a `&[T]` slice of all "committed" elements.
+    pub fn data(&self) -> &[T] {
+        self.inner.data()
+    }
+
+    /// Allocates a "spare" buffer of (at least) the specified `length`.
+    ///
+    /// Returns a `&mut[T]` slice of *all* of the free space. No guarantees
+    /// are provided about the *initial* contents of the buffer! The "spare"
+    /// buffer is **not** considered to be a valid part of the slice, until
+    /// the [`commit()`](Self::commit) function is called eventually.
+    ///
+    /// Not available with the **`safe-only`** feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `length` exceeds the free space.
+    #[cfg(not(feature = "safe-only"))]
+    pub fn allocate_spare(&mut self, length: NonZeroUsize) -> &mut[T] {
+        unsafe {
+            assume_init_mut(self.inner.spare_uninit(length))
+        }
+    }
+
+    /// Allocates a "spare" buffer of (at least) the specified `length`,
+    /// exactly like [`allocate_spare()`](Self::allocate_spare) does, but
+    /// returns it as a `&mut[MaybeUninit<T>]` slice. Available with the
+    /// **`safe-only`** feature.
+    ///
+    /// A "spare" buffer that was allocated this way can **not** be committed
+    /// by [`commit()`](Self::commit), because safe code may have
+    /// *de-initialized* its elements. Use
+    /// [`commit_written()`](Self::commit_written) instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `length` exceeds the free space.
+    pub fn allocate_spare_uninit(&mut self, length: NonZeroUsize) -> &mut[MaybeUninit<T>] {
+        self.inner.allocate_uninit(length)
+    }
+
+    /// Allocates a "spare" buffer of (at least) the specified `length`, and
+    /// lets `f` initialize it, as a `&mut[MaybeUninit<T>]` slice. Then
+    /// commits the elements that `f` *returns*, exactly like
+    /// [`SpareBuffer::commit_written()`](crate::SpareBuffer::commit_written)
+    /// does, **without** any `unsafe` code.
+    ///
+    /// Returns the number of elements that have been committed. If `f`
+    /// panics, **nothing** is committed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `length` exceeds the free space, or if the slice returned
+    /// by `f` is **not** a prefix of the "spare" buffer.
+    pub fn commit_written<F>(&mut self, length: NonZeroUsize, f: F) -> usize
+    where
+        F: for<'s> FnOnce(&'s mut [MaybeUninit<T>]) -> &'s [T]
+    {
+        self.inner.commit_written(length, f)
+    }
+
+    /// Commits the first `additional` elements of the "spare" buffer, i.e.
+    /// *appends* them to the slice **without** copying the data.
+    ///
+    /// All elements to be committed **must** have been initialized, exactly
+    /// like for [`SpareBuffer::commit()`](crate::SpareBuffer::commit). This
+    /// function always invalidates the current "spare" buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `additional` is greater than the free space, or if **no**
+    /// "spare" buffer was allocated before! Also panics, if `additional` is
+    /// **not** zero, but the "spare" buffer was allocated by
+    /// [`allocate_spare_uninit()`](Self::allocate_spare_uninit).
+    pub fn commit(&mut self, additional: usize) {
+        self.inner.commit(additional)
+    }
+
+    /// Removes *all* "committed" elements. The current "spare" buffer is
+    /// invalidated.
+    pub fn clear(&mut self) {
+        self.inner.clear()
+    }
+
+    /// Returns the "committed" elements, as a `&mut[T]` slice that borrows
+    /// the storage for the lifetime `'a`.
+    pub fn into_data(self) -> &'a mut [T] {
+        let (storage, length) = self.inner.into_parts();
+        unsafe {
+            assume_init_mut(&mut storage[..length])
+        }
+    }
+}
+
+impl<'a> SpareSlice<'a, u8> {
+    /// Reads up to `length` bytes from `reader` into the "spare" buffer, and
+    /// commits the data. Reads that fail with
+    /// [`ErrorKind::Interrupted`](std::io::ErrorKind::Interrupted) are
+    /// retried.
+    ///
+    /// Returns the number of bytes that have been committed, which is `0` at
+    /// the end of the stream.
+    ///
+    /// # Errors
+    ///
+    /// Fails with an error of kind
+    /// [`ErrorKind::OutOfMemory`](std::io::ErrorKind::OutOfMemory), if
+    /// `length` exceeds the free space. Errors returned by the `reader` are
+    /// passed through, and nothing is committed in that case.
+    pub fn read_from<R>(&mut self, reader: &mut R, length: NonZeroUsize) -> IoResult<usize>
+    where
+        R: Read
+    {
+        self.inner.read_from(reader, length, "The slice is full!")
+    }
+}
+
+unsafe impl<'a, T> SpareCapacity<T> for SpareSlice<'a, T>
+where
+    T: Primitive
+{
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    fn capacity(&self) -> usize {
+        self.inner.capacity()
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.inner.reserve(additional)
+    }
+
+    fn as_ptr(&self) -> *const T {
+        self.inner.as_ptr()
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut T {
+        self.inner.as_mut_ptr()
+    }
+
+    unsafe fn set_len(&mut self, new_len: usize) {
+        self.inner.set_len(new_len)
+    }
+}
@@ -0,0 +1,47 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::{Result as IoResult, Error as IoError, ErrorKind};
+use std::num::NonZeroUsize;
+
+use rusqlite::blob::Blob;
+
+use crate::SpareBuffer;
+
+impl<'a> SpareBuffer<'a, u8> {
+    /// Reads the whole contents of the given [`Blob`] into the "spare" buffer,
+    /// in chunks of at most `chunk_size` bytes, and commits the data.
+    ///
+    /// This avoids materializing the whole column as a separate `Vec<u8>`, as
+    /// would be the case with `query_row()`. The *positional* I/O API is used,
+    /// so the current position of the `Blob` is **not** modified.
+    ///
+    /// Returns the number of bytes that have been committed.
+    ///
+    /// # Errors
+    ///
+    /// If a length limit has been specified, then this function will fail,
+    /// *before* reading any data, if the size of the `Blob` would cause the
+    /// total length of the underlying vector to exceed the specified limit.
+    /// Errors reported by SQLite are passed through. The chunks that have been
+    /// read before the error occurred remain committed.
+    pub fn read_blob(&mut self, blob: &Blob<'_>, chunk_size: NonZeroUsize) -> IoResult<usize> {
+        let total = blob.len();
+        if !self.fits_limit(total) {
+            return Err(IoError::new(ErrorKind::OutOfMemory, "The size of the blob exceeds the specified limit!"));
+        }
+        let mut offset = 0usize;
+        while let Some(length) = NonZeroUsize::new(total - offset) {
+            let length = length.min(chunk_size);
+            let spare = &mut self.try_spare_mut(length)?[..length.get()];
+            if let Err(error) = blob.read_at_exact(spare, offset) {
+                self.discard_spare();
+                return Err(IoError::other(error));
+            }
+            self.commit(length.get())?;
+            offset += length.get();
+        }
+        Ok(total)
+    }
+}
@@ -0,0 +1,85 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+
+/// Statistics of a [**`SpareBuffer`**](crate::SpareBuffer), in order to tune
+/// chunk sizes and pre-allocation from real data, rather than guessing.
+///
+/// Statistics are *opt-in*, and are collected from the moment they have been
+/// enabled by [`with_stats()`](crate::SpareBuffer::with_stats) on. Use
+/// [`stats()`](crate::SpareBuffer::stats) to access the current statistics.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    allocations: usize,
+    reallocations: usize,
+    commits: usize,
+    committed: usize,
+    peak_capacity: usize,
+}
+
+impl Stats {
+    /// Creates new, empty **`Stats`** for an underlying vector of the given
+    /// `capacity`.
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            peak_capacity: capacity,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the number of "spare" buffers that have been allocated.
+    pub fn allocations(&self) -> usize {
+        self.allocations
+    }
+
+    /// Returns the number of times that the storage of the underlying vector
+    /// has been reallocated, i.e. grown, shrunk or replaced.
+    pub fn reallocations(&self) -> usize {
+        self.reallocations
+    }
+
+    /// Returns the number of commits of *at least* one element.
+    pub fn commits(&self) -> usize {
+        self.commits
+    }
+
+    /// Returns the *total* number of elements that have been committed.
+    pub fn committed(&self) -> usize {
+        self.committed
+    }
+
+    /// Returns the *average* number of elements per commit, or `0.0`, if
+    /// nothing has been committed yet.
+    pub fn average_commit(&self) -> f64 {
+        match self.commits {
+            0 => 0.0,
+            commits => self.committed as f64 / commits as f64,
+        }
+    }
+
+    /// Returns the *peak* capacity of the underlying vector, in elements.
+    pub fn peak_capacity(&self) -> usize {
+        self.peak_capacity
+    }
+
+    /// Counts the allocation of a "spare" buffer.
+    pub(crate) fn track_allocation(&mut self) {
+        self.allocations = self.allocations.saturating_add(1);
+    }
+
+    /// Counts a commit of `count` elements.
+    pub(crate) fn track_commit(&mut self, count: usize) {
+        self.commits = self.commits.saturating_add(1);
+        self.committed = self.committed.saturating_add(count);
+    }
+
+    /// Counts a reallocation, if the storage has been `reallocated`, and
+    /// updates the peak capacity.
+    pub(crate) fn track_storage(&mut self, reallocated: bool, capacity: usize) {
+        if reallocated {
+            self.reallocations = self.reallocations.saturating_add(1);
+        }
+        self.peak_capacity = self.peak_capacity.max(capacity);
+    }
+}
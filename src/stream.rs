@@ -0,0 +1,61 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::future::poll_fn;
+use std::io::{Result as IoResult, Error as IoError, ErrorKind};
+use std::num::NonZeroUsize;
+use std::pin::pin;
+
+use futures_core::Stream;
+
+use crate::SpareBuffer;
+
+impl<'a> SpareBuffer<'a, u8> {
+    /// Drains a *fallible* stream of byte chunks, such as the body of an HTTP
+    /// request or an S3 object, into the underlying vector.
+    ///
+    /// Every chunk is appended by copying it into a "spare" buffer, which is
+    /// committed right away. The chunk type `B` can be anything that derefs to
+    /// a `&[u8]` slice, e.g. `Bytes` or `Vec<u8>`.
+    ///
+    /// An *optional* `size_hint`, in bytes, can be specified, e.g. from the
+    /// `Content-Length` header, in which case the capacity is reserved up
+    /// front. The hint is clamped to the length limit.
+    ///
+    /// Returns the total number of bytes that have been committed.
+    ///
+    /// # Errors
+    ///
+    /// If a length limit has been specified, then this function will fail,
+    /// *before* committing a chunk, if that chunk would cause the total length
+    /// of the underlying vector to exceed the specified limit. Also fails with
+    /// an error of kind [`ErrorKind::OutOfMemory`], if the capacity for the
+    /// `size_hint` can **not** be reserved. Errors yielded by the stream are
+    /// passed through. Chunks that have been committed
+    /// before the error occurred remain in the underlying vector.
+    pub async fn collect_stream<S, B>(&mut self, stream: S, size_hint: Option<usize>) -> IoResult<usize>
+    where
+        S: Stream<Item = IoResult<B>>,
+        B: AsRef<[u8]>
+    {
+        if let Some(hint) = size_hint {
+            self.reserve_hint(hint)?;
+        }
+        let mut stream = pin!(stream);
+        let mut total = 0usize;
+        while let Some(chunk) = poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+            let chunk = chunk?;
+            let bytes = chunk.as_ref();
+            if let Some(length) = NonZeroUsize::new(bytes.len()) {
+                if !self.fits_limit(bytes.len()) {
+                    return Err(IoError::new(ErrorKind::OutOfMemory, "The stream length exceeds the specified limit!"));
+                }
+                self.try_spare_mut(length)?[..bytes.len()].copy_from_slice(bytes);
+                self.commit(bytes.len())?;
+                total += bytes.len();
+            }
+        }
+        Ok(total)
+    }
+}
@@ -0,0 +1,301 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::{BufRead, Read, Result as IoResult, Error as IoError, ErrorKind};
+use std::mem::MaybeUninit;
+use std::num::NonZeroUsize;
+use std::str::from_utf8;
+
+use crate::RetryPolicy;
+use crate::primitive::{assume_init, assume_init_mut, foreign_mut};
+use crate::sanitizer::{mark_init, mark_uninit};
+
+/// Maximum number of bytes of an *incomplete* UTF-8 sequence.
+const MAX_PENDING: usize = 3;
+
+/// A wrapper around [**`String`**](std::string::String) that provides access
+/// to the "spare" capacity of the string as a `&mut[u8]` slice, and that
+/// validates the UTF-8 encoding on commit.
+///
+/// This allows text protocols to be read *directly* into the string, without
+/// going through an intermediate `Vec<u8>` and a copy. In the *incremental*
+/// mode, which can be enabled by [`with_incremental()`](Self::with_incremental),
+/// a multi-byte sequence may be split across commits, e.g. because a read
+/// ended in the middle of a character: the incomplete sequence at the end is
+/// held back, and it is completed by the next commit.
+pub struct SpareString<'a> {
+    buffer: &'a mut String,
+    limit: Option<NonZeroUsize>,
+    allocated: bool,
+    incremental: bool,
+    pending: [u8; MAX_PENDING],
+    pending_len: usize,
+}
+
+impl<'a> SpareString<'a> {
+    /// Creates a new **`SpareString`** from an existing string.
+    ///
+    /// An *optional* `limit` for the length of the string, in bytes, can be
+    /// specified. The [`commit()`](Self::commit) fails, if it would exceed
+    /// this limit.
+    pub fn from(buffer: &'a mut String, limit: Option<NonZeroUsize>) -> Self {
+        Self {
+            buffer,
+            limit,
+            allocated: false,
+            incremental: false,
+            pending: [0u8; MAX_PENDING],
+            pending_len: 0,
+        }
+    }
+
+    /// Enables or disables the *incremental* mode. In the incremental mode,
+    /// an incomplete multi-byte sequence at the end of the committed bytes is
+    /// **not** an error, but it is held back until the next commit.
+    pub fn with_incremental(mut self, incremental: bool) -> Self {
+        self.incremental = incremental;
+        self
+    }
+
+    /// Returns `true`, if the *incremental* mode is enabled.
+    pub fn incremental(&self) -> bool {
+        self.incremental
+    }
+
+    /// Returns the current length of the underlying string, in bytes.
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Returns `true` if the underlying string is empty.
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    /// Returns the length limit, if a limit has been specified. Otherwise
+    /// `None` is returned.
+    pub fn limit(&self) -> Option<NonZeroUsize> {
+        self.limit
+    }
+
+    /// Returns the "committed" text, as a `&str` slice.
+    pub fn data(&self) -> &str {
+        self.buffer.as_str()
+    }
+
+    /// Returns the bytes of the *incomplete* multi-byte sequence that has
+    /// been held back by the last commit, in the incremental mode.
+    pub fn pending(&self) -> &[u8] {
+        &self.pending[..self.pending_len]
+    }
+
+    /// Allocates a "spare" buffer of the specified `length`, in bytes.
+    ///
+    /// Returns a `&mut[u8]` slice which allows the caller to access the
+    /// allocated "spare" buffer. No guarantees are provided about the
+    /// *initial* contents of the buffer! The "spare" buffer is **not**
+    /// considered to be a valid part of the underlying string, until the
+    /// [`commit()`](Self::commit) function is called eventually.
+    ///
+    /// Not available with the **`safe-only`** feature.
+    #[cfg(not(feature = "safe-only"))]
+    pub fn allocate_spare(&mut self, length: NonZeroUsize) -> &mut[u8] {
+        self.spare_mut(length)
+    }
+
+    /// Allocates a "spare" buffer of the specified `length`, for *internal*
+    /// use. See [`allocate_spare()`](Self::allocate_spare) for details.
+    ///
+    /// Any pending bytes are placed *before* the returned slice, so that the
+    /// commit validates them together with the new bytes.
+    fn spare_mut(&mut self, length: NonZeroUsize) -> &mut[u8] {
+        unsafe {
+            assume_init_mut(self.spare_uninit(length))
+        }
+    }
+
+    /// Allocates a "spare" buffer of the specified `length`, as a
+    /// `&mut[MaybeUninit<u8>]` slice, for *internal* use.
+    fn spare_uninit(&mut self, length: NonZeroUsize) -> &mut[MaybeUninit<u8>] {
+        let pending = self.pending_len;
+        let vec = unsafe {
+            self.buffer.as_mut_vec()
+        };
+        vec.reserve(length.get().checked_add(pending).expect("Numerical overflow! (length)"));
+        self.allocated = true;
+        let spare = vec.spare_capacity_mut();
+        for (slot, byte) in spare.iter_mut().zip(&self.pending[..pending]) {
+            slot.write(*byte);
+        }
+        let spare = &mut spare[pending..];
+        mark_uninit(spare.as_ptr(), spare.len());
+        spare
+    }
+
+    /// Commits the first `additional` bytes of the "spare" buffer, i.e.
+    /// *appends* them to the underlying string **without** copying the data,
+    /// provided that they are valid UTF-8.
+    ///
+    /// The same requirements as for
+    /// [`SpareBuffer::commit()`](crate::SpareBuffer::commit) apply, i.e. all
+    /// bytes to be committed **must** have been initialized. This function
+    /// always invalidates the current "spare" buffer.
+    ///
+    /// # Errors
+    ///
+    /// Fails with an error of kind [`ErrorKind::InvalidData`], if the bytes
+    /// are **not** valid UTF-8. In the incremental mode, an incomplete
+    /// multi-byte sequence at the end is **not** an error. Also fails, if
+    /// appending the bytes would cause the length of the underlying string to
+    /// exceed the specified limit. Nothing is committed in either case.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `additional` is greater than the available "spare" capacity,
+    /// or if **no** "spare" buffer was allocated before!
+    pub fn commit(&mut self, additional: usize) -> IoResult<()> {
+        assert!(std::mem::replace(&mut self.allocated, false), "No spare buffer allocated!");
+        let pending = self.pending_len;
+        let vec = unsafe {
+            self.buffer.as_mut_vec()
+        };
+        let length = vec.len();
+        let spare = vec.spare_capacity_mut();
+        assert!(additional <= spare.len() - pending, "Commit size exceeds available capacity!");
+        mark_init(spare[pending..].as_ptr(), additional);
+        let total = pending + additional;
+        let bytes = unsafe {
+            assume_init(&spare[..total])
+        };
+        let valid = match from_utf8(bytes) {
+            Ok(_) => total,
+            Err(error) if self.incremental && error.error_len().is_none() => error.valid_up_to(),
+            Err(_) => return Err(IoError::new(ErrorKind::InvalidData, "The committed data is not valid UTF-8!")),
+        };
+        let new_length = length.checked_add(valid).expect("Numerical overflow! (new_length)");
+        if new_length > self.limit.map_or(usize::MAX, NonZeroUsize::get) {
+            return Err(IoError::new(ErrorKind::OutOfMemory, "The new length exceeds the specified limit!"));
+        }
+        let incomplete = total - valid;
+        self.pending[..incomplete].copy_from_slice(&bytes[valid..]);
+        self.pending_len = incomplete;
+        unsafe {
+            vec.set_len(new_length)
+        }
+        Ok(())
+    }
+
+    /// Discards the current "spare" buffer, if any, **without** committing
+    /// anything.
+    fn discard(&mut self) {
+        self.allocated = false;
+    }
+
+    /// Checks that **no** incomplete multi-byte sequence is pending, e.g.
+    /// once the end of the stream has been reached.
+    ///
+    /// # Errors
+    ///
+    /// Fails with an error of kind [`ErrorKind::InvalidData`], if there are
+    /// pending bytes. The pending bytes are discarded in that case.
+    pub fn finish(&mut self) -> IoResult<()> {
+        match std::mem::replace(&mut self.pending_len, 0) {
+            0 => Ok(()),
+            _ => Err(IoError::new(ErrorKind::InvalidData, "Incomplete UTF-8 sequence at the end of the stream!")),
+        }
+    }
+
+    /// Reads up to `length` bytes from `reader` into the "spare" buffer, and
+    /// commits the data. Reads that fail with
+    /// [`ErrorKind::Interrupted`](std::io::ErrorKind::Interrupted) are
+    /// retried.
+    ///
+    /// Returns the number of bytes that have been read, which is `0` at the
+    /// end of the stream. In the incremental mode, some of these bytes may be
+    /// [pending](Self::pending).
+    ///
+    /// # Errors
+    ///
+    /// Errors returned by the `reader`, as well as errors returned by
+    /// [`commit()`](Self::commit), are passed through.
+    pub fn read_from<R>(&mut self, reader: &mut R, length: NonZeroUsize) -> IoResult<usize>
+    where
+        R: Read
+    {
+        let spare = foreign_mut(&mut self.spare_uninit(length)[..length.get()]);
+        match RetryPolicy::new().retry(|| reader.read(spare)) {
+            Ok(count) => self.commit(count).map(|_| count),
+            Err(error) => {
+                self.discard();
+                Err(error)
+            },
+        }
+    }
+
+    /// Reads one line from `reader`, up to and including the next newline
+    /// (`0x0A`) byte, *or* until the end of the stream, and appends it to the
+    /// underlying string via the "spare" buffer, like
+    /// [`BufRead::read_line()`] does.
+    ///
+    /// Only the *newly* appended bytes are validated. A multi-byte sequence
+    /// may be split across the internal buffer of the `reader`. Reads that
+    /// fail with [`ErrorKind::Interrupted`](std::io::ErrorKind::Interrupted)
+    /// are retried.
+    ///
+    /// Returns the number of bytes that have been read, which is `0` at the
+    /// end of the stream.
+    ///
+    /// # Errors
+    ///
+    /// Fails with the same errors as [`commit()`](Self::commit). Unless the
+    /// incremental mode is enabled, also fails with an error of kind
+    /// [`ErrorKind::InvalidData`], if the stream ends with an incomplete
+    /// multi-byte sequence. Errors returned by the `reader` are passed
+    /// through. The bytes of the line that have been appended up to that
+    /// point remain committed.
+    pub fn read_line_into<R>(&mut self, reader: &mut R) -> IoResult<usize>
+    where
+        R: BufRead
+    {
+        let incremental = std::mem::replace(&mut self.incremental, true);
+        let result = self.read_line_bytes(reader);
+        self.incremental = incremental;
+        let count = result?;
+        if !incremental {
+            self.finish()?;
+        }
+        Ok(count)
+    }
+
+    /// Reads one line from `reader`, for *internal* use. See
+    /// [`read_line_into()`](Self::read_line_into) for details.
+    fn read_line_bytes<R>(&mut self, reader: &mut R) -> IoResult<usize>
+    where
+        R: BufRead
+    {
+        let mut total = 0usize;
+        loop {
+            let available = match reader.fill_buf() {
+                Ok(available) => available,
+                Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+                Err(error) => return Err(error),
+            };
+            let (found, length) = match available.iter().position(|byte| *byte == b'\n') {
+                Some(position) => (true, position + 1),
+                None => (false, available.len()),
+            };
+            let count = match NonZeroUsize::new(length) {
+                Some(count) => count,
+                None => return Ok(total), /* EOF */
+            };
+            self.spare_mut(count)[..length].copy_from_slice(&available[..length]);
+            self.commit(length)?;
+            reader.consume(length);
+            total += length;
+            if found {
+                return Ok(total);
+            }
+        }
+    }
+}
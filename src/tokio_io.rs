@@ -0,0 +1,65 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::Result as IoResult;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tokio::io::{AsyncBufRead, AsyncRead, AsyncWrite, ReadBuf};
+
+use crate::{SpareBuffer, SpareCapacity};
+
+/// Writes by copying into the "spare" buffer, which is committed right away.
+///
+/// If a length limit has been specified, then a write is *shortened* to the
+/// remaining headroom, so that the limit is never exceeded. Once the limit
+/// has been reached, writing a non-empty slice fails with an error of kind
+/// [`ErrorKind::OutOfMemory`](std::io::ErrorKind::OutOfMemory). A write is
+/// shortened to the maximum allocation size as well.
+impl<'a, B> AsyncWrite for SpareBuffer<'a, u8, B>
+where
+    B: SpareCapacity<u8> + Unpin
+{
+    fn poll_write(self: Pin<&mut Self>, _cx: &mut Context<'_>, buf: &[u8]) -> Poll<IoResult<usize>> {
+        Poll::Ready(self.get_mut().append_partial(buf))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<IoResult<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<IoResult<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Reads the "committed" elements that have **not** been
+/// [consumed](crate::SpareBuffer::consumed) yet, exactly like the
+/// [`BufRead`](std::io::BufRead) implementation does. Never returns
+/// `Poll::Pending`.
+impl<'a, B> AsyncRead for SpareBuffer<'a, u8, B>
+where
+    B: SpareCapacity<u8> + Unpin
+{
+    fn poll_read(self: Pin<&mut Self>, _cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<IoResult<()>> {
+        let this = self.get_mut();
+        let count = buf.remaining().min(this.unconsumed().len());
+        buf.put_slice(&this.unconsumed()[..count]);
+        SpareBuffer::consume(this, count);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<'a, B> AsyncBufRead for SpareBuffer<'a, u8, B>
+where
+    B: SpareCapacity<u8> + Unpin
+{
+    fn poll_fill_buf(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<IoResult<&[u8]>> {
+        Poll::Ready(Ok(self.get_mut().unconsumed()))
+    }
+
+    fn consume(self: Pin<&mut Self>, amount: usize) {
+        SpareBuffer::consume(self.get_mut(), amount)
+    }
+}
@@ -0,0 +1,74 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::{Result as IoResult, Error as IoError};
+use std::num::NonZeroUsize;
+
+use crate::{SpareBuffer, SpareCapacity};
+
+impl<'a, B> SpareBuffer<'a, u8, B>
+where
+    B: SpareCapacity<u8>
+{
+    /// Allocates a "spare" buffer of the specified `length`, as the target of
+    /// an *io_uring* read, and returns the address and the length that are to
+    /// be put into the submission queue entry, e.g. of an `opcode::Read`.
+    ///
+    /// The length is limited to `u32::MAX` bytes. Once the completion queue
+    /// entry has been reaped, its result **must** be passed to
+    /// [`commit_completion()`](Self::commit_completion).
+    ///
+    /// The kernel writes to the "spare" buffer *asynchronously*, which the
+    /// borrow checker can **not** track. Hence, the **`SpareBuffer`** and its
+    /// underlying vector **must not** be used, moved or dropped, while the
+    /// read is in flight! For *fixed* buffers, register the storage of the
+    /// underlying vector up front, after reserving sufficient capacity, so
+    /// that the "spare" buffer is **never** reallocated.
+    ///
+    /// Only available on Linux. Not available with the **`safe-only`**
+    /// feature.
+    ///
+    /// # Errors
+    ///
+    /// Fails with
+    /// [`AllocationError::ExceedsMaxAllocation`](crate::AllocationError::ExceedsMaxAllocation),
+    /// converted to an error of kind
+    /// [`ErrorKind::OutOfMemory`](std::io::ErrorKind::OutOfMemory), if
+    /// `length` exceeds the maximum allocation size, or with
+    /// [`AllocationError::Reserve`](crate::AllocationError::Reserve), if the
+    /// capacity can **not** be reserved.
+    pub fn prepare_read(&mut self, length: NonZeroUsize) -> IoResult<(*mut u8, u32)> {
+        let length = NonZeroUsize::new(length.get().min(u32::MAX as usize)).unwrap();
+        let spare = self.try_spare_mut(length)?;
+        Ok((spare.as_mut_ptr(), length.get() as u32))
+    }
+
+    /// Commits the "spare" buffer that was prepared by
+    /// [`prepare_read()`](Self::prepare_read), according to the `result` of
+    /// the completion queue entry, i.e. commits `result` bytes, if it is
+    /// *non-negative*. Returns the number of bytes that have been committed.
+    ///
+    /// Only available on Linux. Not available with the **`safe-only`**
+    /// feature.
+    ///
+    /// # Errors
+    ///
+    /// If `result` is *negative*, fails with the corresponding OS error,
+    /// i.e. `-result` is the `errno` value. Nothing is committed in that
+    /// case. Also fails with the same errors as [`commit()`](Self::commit).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `result` is greater than the available "spare" capacity, or
+    /// if **no** "spare" buffer was prepared before!
+    pub fn commit_completion(&mut self, result: i32) -> IoResult<usize> {
+        match usize::try_from(result) {
+            Ok(count) => self.commit(count).map(|_| count),
+            Err(_) => {
+                self.discard_spare();
+                Err(IoError::from_raw_os_error(result.wrapping_neg()))
+            },
+        }
+    }
+}
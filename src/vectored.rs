@@ -0,0 +1,77 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::{IoSliceMut, Read, Result as IoResult};
+use std::num::NonZeroUsize;
+
+use crate::{SpareBuffer, SpareCapacity};
+
+impl<'a, B> SpareBuffer<'a, u8, B>
+where
+    B: SpareCapacity<u8>
+{
+    /// Allocates a "spare" buffer of `count` chunks of `chunk` bytes each,
+    /// and returns it as a vector of [`IoSliceMut`] slices, one per chunk,
+    /// which can be passed to [`Read::read_vectored()`].
+    ///
+    /// The chunks are *adjacent*, so that the *total* number of bytes that
+    /// have been read can simply be passed to [`commit()`](Self::commit).
+    /// See [`allocate_spare()`](Self::allocate_spare) for details.
+    ///
+    /// Not available with the **`safe-only`** feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the total length exceeds the maximum allocation size, as
+    /// specified by [`with_max_allocation()`](Self::with_max_allocation), or
+    /// if the capacity can **not** be reserved.
+    #[cfg(not(feature = "safe-only"))]
+    pub fn allocate_spare_vectored(&mut self, chunk: NonZeroUsize, count: NonZeroUsize) -> Vec<IoSliceMut<'_>> {
+        let length = chunk.checked_mul(count).expect("Numerical overflow! (length)");
+        self.spare_mut(length)[..length.get()].chunks_mut(chunk.get()).map(IoSliceMut::new).collect()
+    }
+
+    /// Reads from `reader` into up to `count` chunks of `chunk` bytes each,
+    /// with a *single* call to [`Read::read_vectored()`], and commits the
+    /// data. Reads that fail with
+    /// [`ErrorKind::Interrupted`](std::io::ErrorKind::Interrupted) are
+    /// retried, according to the [**`RetryPolicy`**](crate::RetryPolicy) of
+    /// this **`SpareBuffer`**.
+    ///
+    /// The total length is clamped to the length limit, and to the maximum
+    /// allocation size. Returns the number of bytes that have been committed,
+    /// which is `0` at the end of the stream, or if the length limit has been
+    /// reached.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`AllocationError::Reserve`](crate::AllocationError::Reserve),
+    /// converted to an error of kind
+    /// [`ErrorKind::OutOfMemory`](std::io::ErrorKind::OutOfMemory), if the
+    /// capacity can **not** be reserved. Errors returned by the `reader`, as
+    /// well as errors returned by [`commit()`](Self::commit), are passed
+    /// through.
+    pub fn read_vectored_from<R>(&mut self, reader: &mut R, chunk: NonZeroUsize, count: NonZeroUsize) -> IoResult<usize>
+    where
+        R: Read
+    {
+        let retry = self.retry_policy();
+        let max_allocation = self.max_allocation().map_or(usize::MAX, NonZeroUsize::get);
+        let requested = chunk.get().saturating_mul(count.get());
+        let length = match NonZeroUsize::new(requested.min(self.headroom()).min(max_allocation)) {
+            Some(length) => length,
+            None => return Ok(0),
+        };
+        let spare = &mut self.try_spare_mut(length)?[..length.get()];
+        let mut slices: Vec<IoSliceMut<'_>> = spare.chunks_mut(chunk.get()).map(IoSliceMut::new).collect();
+        let result = retry.retry(|| reader.read_vectored(&mut slices));
+        match result {
+            Ok(count) => self.commit(count).map(|_| count),
+            Err(error) => {
+                self.discard_spare();
+                Err(error)
+            },
+        }
+    }
+}
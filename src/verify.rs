@@ -0,0 +1,59 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use crate::{Primitive, SpareBuffer, SpareCapacity, VerifyError};
+
+impl<'a, T, B> SpareBuffer<'a, T, B>
+where
+    T: Primitive,
+    B: SpareCapacity<T>
+{
+    /// Verifies that the number of "committed" elements, including the
+    /// consumed ones, equals `expected_len`, e.g. the declared
+    /// `Content-Length` of a download, once the transfer has completed.
+    ///
+    /// This is the finishing step for helpers such as
+    /// [`read_file()`](crate::read_file), `collect_body()` or
+    /// `collect_stream()`, so that a truncated transfer, or one that is
+    /// longer than declared, can **not** be accepted silently.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`VerifyError::LengthMismatch`](crate::VerifyError::LengthMismatch),
+    /// if the length differs from `expected_len`.
+    pub fn finish_verified(&self, expected_len: usize) -> Result<(), VerifyError> {
+        let actual = self.len();
+        if actual != expected_len {
+            return Err(VerifyError::LengthMismatch { expected: expected_len, actual });
+        }
+        Ok(())
+    }
+
+    /// Verifies the length, exactly like
+    /// [`finish_verified()`](Self::finish_verified) does, and then verifies
+    /// that the running CRC-32 checksum, as enabled by
+    /// [`with_crc32()`](Self::with_crc32), equals `expected_crc32`.
+    ///
+    /// Only available with the **`crc32fast`** feature.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`VerifyError::LengthMismatch`](crate::VerifyError::LengthMismatch),
+    /// if the length differs, or with
+    /// [`VerifyError::ChecksumMismatch`](crate::VerifyError::ChecksumMismatch),
+    /// if the checksum differs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the checksum has **not** been enabled!
+    #[cfg(feature = "crc32fast")]
+    pub fn finish_verified_crc32(&self, expected_len: usize, expected_crc32: u32) -> Result<(), VerifyError> {
+        self.finish_verified(expected_len)?;
+        let actual = self.checksum().expect("CRC-32 checksum not enabled!");
+        if actual != expected_crc32 {
+            return Err(VerifyError::ChecksumMismatch { expected: expected_crc32, actual });
+        }
+        Ok(())
+    }
+}
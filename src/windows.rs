@@ -0,0 +1,211 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+#[cfg(not(feature = "safe-only"))]
+use std::ffi::c_void;
+use std::io::{Result as IoResult, Error as IoError};
+use std::num::NonZeroUsize;
+use std::os::windows::io::{AsHandle, AsRawHandle};
+#[cfg(not(feature = "safe-only"))]
+use std::ptr::NonNull;
+use std::ptr::null_mut;
+
+use windows_sys::Win32::Foundation::{ERROR_BROKEN_PIPE, ERROR_HANDLE_EOF, HANDLE};
+use windows_sys::Win32::Storage::FileSystem::ReadFile;
+#[cfg(not(feature = "safe-only"))]
+use windows_sys::Win32::System::IO::OVERLAPPED;
+
+use crate::{SpareBuffer, SpareCapacity};
+
+/// An *overlapped* read into the "spare" buffer of a
+/// [**`SpareBuffer`**](crate::SpareBuffer), e.g. for an I/O completion port
+/// (IOCP), which owns the `OVERLAPPED` structure of the read.
+///
+/// The `OVERLAPPED` structure is allocated on the heap, so that its address
+/// remains valid until the read has completed, even if the
+/// **`OverlappedRead`** is moved, e.g. into the per-operation state of a
+/// completion loop. Once the completion has been dequeued, e.g. via
+/// `GetQueuedCompletionStatus()`, the **`OverlappedRead`** **must** be passed
+/// to [`SpareBuffer::commit_overlapped()`](crate::SpareBuffer::commit_overlapped),
+/// which commits the transferred length and frees the `OVERLAPPED`
+/// structure. If it is dropped instead, the `OVERLAPPED` structure is
+/// *leaked*, because the system may still write to it.
+///
+/// Use [`SpareBuffer::prepare_overlapped()`](crate::SpareBuffer::prepare_overlapped)
+/// to create a new **`OverlappedRead`**.
+///
+/// Only available on Windows. Not available with the **`safe-only`**
+/// feature.
+#[cfg(not(feature = "safe-only"))]
+pub struct OverlappedRead {
+    overlapped: NonNull<OVERLAPPED>,
+    buffer: *mut u8,
+    length: u32,
+}
+
+#[cfg(not(feature = "safe-only"))]
+unsafe impl Send for OverlappedRead {}
+
+#[cfg(not(feature = "safe-only"))]
+impl OverlappedRead {
+    /// Returns a pointer to the `OVERLAPPED` structure, which is to be passed
+    /// to `ReadFile()` or `WSARecv()`, as a `*mut c_void`, so that it can be
+    /// cast to the `OVERLAPPED` type of any Windows bindings.
+    pub fn overlapped_ptr(&self) -> *mut c_void {
+        self.overlapped.as_ptr().cast()
+    }
+
+    /// Returns the address and the length of the "spare" buffer, which are to
+    /// be passed to `ReadFile()`, or put into the `WSABUF` of `WSARecv()`.
+    pub fn buffer(&self) -> (*mut u8, u32) {
+        (self.buffer, self.length)
+    }
+}
+
+impl<'a, B> SpareBuffer<'a, u8, B>
+where
+    B: SpareCapacity<u8>
+{
+    /// Reads up to `length` bytes from the Win32 `handle` into the "spare"
+    /// buffer, by calling [`ReadFile()`](https://learn.microsoft.com/en-us/windows/win32/api/fileapi/nf-fileapi-readfile)
+    /// *directly*, and commits the number of bytes transferred, e.g. for
+    /// pipes or devices that are **not** accessed via
+    /// [`File`](std::fs::File).
+    ///
+    /// The read is performed *synchronously*, i.e. **without** an
+    /// `OVERLAPPED` structure. A single read is limited to `u32::MAX` bytes.
+    /// A broken pipe is treated as the end of the stream. Transient errors
+    /// are handled according to the [**`RetryPolicy`**](crate::RetryPolicy)
+    /// of this **`SpareBuffer`**. Returns the number of bytes that have been
+    /// committed, which is `0` at the end of the stream.
+    ///
+    /// Only available on Windows.
+    ///
+    /// # Errors
+    ///
+    /// Fails with
+    /// [`AllocationError::ExceedsMaxAllocation`](crate::AllocationError::ExceedsMaxAllocation),
+    /// converted to an error of kind
+    /// [`ErrorKind::OutOfMemory`](std::io::ErrorKind::OutOfMemory), if
+    /// `length` exceeds the maximum allocation size. Errors returned by
+    /// `ReadFile()`, as well as errors returned by [`commit()`](Self::commit),
+    /// are passed through.
+    pub fn read_handle<H>(&mut self, handle: &H, length: NonZeroUsize) -> IoResult<usize>
+    where
+        H: AsHandle
+    {
+        let handle = handle.as_handle().as_raw_handle() as HANDLE;
+        let length = NonZeroUsize::new(length.get().min(u32::MAX as usize)).unwrap();
+        let retry = self.retry_policy();
+        let spare = &mut self.try_spare_mut(length)?[..length.get()];
+        let result = retry.retry(|| {
+            let mut transferred = 0u32;
+            if unsafe { ReadFile(handle, spare.as_mut_ptr(), spare.len() as u32, &mut transferred, null_mut()) } != 0 {
+                return Ok(transferred as usize);
+            }
+            let error = IoError::last_os_error();
+            match error.raw_os_error().map(|code| code as u32) {
+                Some(ERROR_BROKEN_PIPE) | Some(ERROR_HANDLE_EOF) => Ok(0),
+                _ => Err(error),
+            }
+        });
+        match result {
+            Ok(count) => self.commit(count).map(|_| count),
+            Err(error) => {
+                self.discard_spare();
+                Err(error)
+            },
+        }
+    }
+
+    /// Allocates a "spare" buffer of the specified `length`, as the target of
+    /// an *overlapped* `ReadFile()` or `WSARecv()`, and returns an
+    /// [**`OverlappedRead`**](crate::OverlappedRead), which owns the
+    /// `OVERLAPPED` structure of the read. The `offset` is put into the
+    /// `OVERLAPPED` structure, for files; it is ignored for sockets and
+    /// pipes. This mirrors `prepare_read()` on Linux.
+    ///
+    /// The length is limited to `u32::MAX` bytes. Once the completion has
+    /// been dequeued, the **`OverlappedRead`** **must** be passed to
+    /// [`commit_overlapped()`](Self::commit_overlapped).
+    ///
+    /// The system writes to the "spare" buffer *asynchronously*, which the
+    /// borrow checker can **not** track. Hence, the **`SpareBuffer`** and its
+    /// underlying vector **must not** be used, moved or dropped, while the
+    /// read is in flight!
+    ///
+    /// Only available on Windows. Not available with the **`safe-only`**
+    /// feature.
+    ///
+    /// # Errors
+    ///
+    /// Fails with
+    /// [`AllocationError::ExceedsMaxAllocation`](crate::AllocationError::ExceedsMaxAllocation),
+    /// converted to an error of kind
+    /// [`ErrorKind::OutOfMemory`](std::io::ErrorKind::OutOfMemory), if
+    /// `length` exceeds the maximum allocation size, or with
+    /// [`AllocationError::Reserve`](crate::AllocationError::Reserve), if the
+    /// capacity can **not** be reserved.
+    #[cfg(not(feature = "safe-only"))]
+    pub fn prepare_overlapped(&mut self, length: NonZeroUsize, offset: u64) -> IoResult<OverlappedRead> {
+        let length = NonZeroUsize::new(length.get().min(u32::MAX as usize)).unwrap();
+        let buffer = self.try_spare_mut(length)?.as_mut_ptr();
+        let mut overlapped = Box::new(OVERLAPPED::default());
+        overlapped.Anonymous.Anonymous.Offset = offset as u32;
+        overlapped.Anonymous.Anonymous.OffsetHigh = (offset >> 32) as u32;
+        Ok(OverlappedRead {
+            overlapped: NonNull::from(Box::leak(overlapped)),
+            buffer,
+            length: length.get() as u32,
+        })
+    }
+
+    /// Commits the "spare" buffer of the *completed* `read`, that was
+    /// prepared by [`prepare_overlapped()`](Self::prepare_overlapped),
+    /// according to the `result` of the completion, i.e. commits the number
+    /// of bytes transferred, and frees the `OVERLAPPED` structure. Returns
+    /// the number of bytes that have been committed.
+    ///
+    /// A broken pipe, or the end of the file, is treated as a completion of
+    /// `0` bytes. This is the *completion routine* of the read, which may be
+    /// called from the completion loop, or from a `FileIOCompletionRoutine`.
+    ///
+    /// Only available on Windows. Not available with the **`safe-only`**
+    /// feature.
+    ///
+    /// # Errors
+    ///
+    /// Errors in `result` are passed through. Nothing is committed in that
+    /// case. Also fails with the same errors as [`commit()`](Self::commit).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of bytes transferred exceeds the length of the
+    /// "spare" buffer, or if **no** "spare" buffer was prepared before!
+    #[cfg(not(feature = "safe-only"))]
+    pub fn commit_overlapped(&mut self, read: OverlappedRead, result: IoResult<u32>) -> IoResult<usize> {
+        let length = read.length;
+        drop(unsafe {
+            Box::from_raw(read.overlapped.as_ptr())
+        });
+        let result = match result {
+            Ok(transferred) => Ok(transferred),
+            Err(error) => match error.raw_os_error().map(|code| code as u32) {
+                Some(ERROR_BROKEN_PIPE) | Some(ERROR_HANDLE_EOF) => Ok(0),
+                _ => Err(error),
+            },
+        };
+        match result {
+            Ok(transferred) if transferred <= length => self.commit(transferred as usize).map(|_| transferred as usize),
+            Ok(_) => {
+                self.discard_spare();
+                panic!("Read size exceeds the spare buffer!")
+            },
+            Err(error) => {
+                self.discard_spare();
+                Err(error)
+            },
+        }
+    }
+}
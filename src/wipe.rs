@@ -0,0 +1,28 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::mem::size_of;
+use std::sync::atomic::{compiler_fence, Ordering};
+
+use crate::{Primitive, SpareCapacity};
+
+/// Overwrites the *whole* storage of `buffer`, i.e. the initialized elements
+/// as well as the "spare" capacity, with zeros.
+///
+/// The writes are *volatile*, followed by a compiler fence, so that they can
+/// **not** be optimized away, even though the memory is about to be freed.
+pub(crate) fn wipe<T, B>(buffer: &mut B)
+where
+    T: Primitive,
+    B: SpareCapacity<T>
+{
+    let size = buffer.capacity() * size_of::<T>();
+    let ptr = buffer.as_mut_ptr() as *mut u8;
+    for offset in 0..size {
+        unsafe {
+            ptr.add(offset).write_volatile(0u8);
+        }
+    }
+    compiler_fence(Ordering::SeqCst);
+}
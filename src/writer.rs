@@ -0,0 +1,194 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::Result as IoResult;
+use std::num::NonZeroUsize;
+use std::slice::from_raw_parts;
+
+use crate::{Primitive, SpareBuffer, SpareCapacity};
+use crate::buffer::log_event;
+
+/// A *safe* writer for the "spare" buffer of a
+/// [**`SpareBuffer`**](crate::SpareBuffer).
+///
+/// The writer keeps track of how many elements of the "spare" buffer have
+/// been *initialized*. Only these elements can be inspected and committed, so
+/// that uninitialized memory is never exposed, and **no** `unsafe` code is
+/// required at the call site.
+///
+/// If the writer is dropped while *unwinding* from a panic, the "spare"
+/// buffer is discarded, so that the partially written data can **not** be
+/// committed later. A writer that was created with
+/// [`SpareBuffer::auto_writer()`](crate::SpareBuffer::auto_writer)
+/// *commits* the written elements, when it is dropped normally. Any other
+/// writer *discards* the "spare" buffer, when it is dropped, unless it has
+/// been committed, so that unwritten elements can **never** be committed via
+/// [`SpareBuffer::commit()`](crate::SpareBuffer::commit) afterwards.
+///
+/// If the **`log`** or the **`tracing`** feature is enabled, a warning is
+/// logged, if such a writer is dropped normally with written elements that
+/// have been neither committed nor explicitly [discarded](Self::discard), so
+/// that the silent loss of data, e.g. because of an early return, can be
+/// tracked down. A failed commit on drop is logged as well.
+///
+/// Use [`SpareBuffer::writer()`](crate::SpareBuffer::writer) to create a new
+/// **`SpareWriter`**.
+pub struct SpareWriter<'b, 'a, T, B = Vec<T>>
+where
+    T: Primitive,
+    B: SpareCapacity<T>
+{
+    buffer: &'b mut SpareBuffer<'a, T, B>,
+    length: usize,
+    filled: usize,
+    auto_commit: bool,
+    finished: bool,
+}
+
+impl<'a, T, B> SpareBuffer<'a, T, B>
+where
+    T: Primitive,
+    B: SpareCapacity<T>
+{
+    /// Allocates a "spare" buffer of the specified `length`, and returns a
+    /// [**`SpareWriter`**](crate::SpareWriter) that fills it.
+    ///
+    /// See [`allocate_spare()`](Self::allocate_spare) for details.
+    pub fn writer(&mut self, length: NonZeroUsize) -> SpareWriter<'_, 'a, T, B> {
+        self.spare_mut(length);
+        SpareWriter {
+            buffer: self,
+            length: length.get(),
+            filled: 0,
+            auto_commit: false,
+            finished: false,
+        }
+    }
+
+    /// Allocates a "spare" buffer of the specified `length`, and returns a
+    /// [**`SpareWriter`**](crate::SpareWriter) that fills it, exactly like
+    /// [`writer()`](Self::writer) does, but the writer *automatically*
+    /// commits all elements that have been written, when it is dropped.
+    ///
+    /// This way, forgetting the final [`commit()`](SpareWriter::commit) can
+    /// **not** lose data. If the writer is dropped while *unwinding* from a
+    /// panic, the "spare" buffer is still discarded. Note that errors of the
+    /// automatic commit, e.g. because the length limit would be exceeded, are
+    /// *ignored*; call [`commit()`](SpareWriter::commit) explicitly in order
+    /// to handle them.
+    pub fn auto_writer(&mut self, length: NonZeroUsize) -> SpareWriter<'_, 'a, T, B> {
+        let mut writer = self.writer(length);
+        writer.auto_commit = true;
+        writer
+    }
+}
+
+impl<'b, 'a, T, B> SpareWriter<'b, 'a, T, B>
+where
+    T: Primitive,
+    B: SpareCapacity<T>
+{
+    /// Returns the total length of the "spare" buffer.
+    pub fn capacity(&self) -> usize {
+        self.length
+    }
+
+    /// Returns the number of elements that have been written so far.
+    pub fn filled(&self) -> usize {
+        self.filled
+    }
+
+    /// Returns the number of elements that can still be written.
+    pub fn remaining(&self) -> usize {
+        self.length - self.filled
+    }
+
+    /// Returns `true` if the whole "spare" buffer has been written.
+    pub fn is_full(&self) -> bool {
+        self.filled >= self.length
+    }
+
+    /// Appends a single element. Returns `false`, if the "spare" buffer is
+    /// full already.
+    pub fn push(&mut self, value: T) -> bool {
+        if self.is_full() {
+            return false;
+        }
+        self.buffer.spare_uninit()[self.filled].write(value);
+        self.filled += 1;
+        true
+    }
+
+    /// Appends as many elements from `values` as fit into the remaining
+    /// "spare" buffer. Returns the number of elements that have been written.
+    pub fn write(&mut self, values: &[T]) -> usize {
+        let count = values.len().min(self.remaining());
+        let target = &mut self.buffer.spare_uninit()[self.filled..self.filled + count];
+        for (slot, value) in target.iter_mut().zip(values) {
+            slot.write(*value);
+        }
+        self.filled += count;
+        count
+    }
+
+    /// Returns a `&[T]` slice of *all* elements that have been written so
+    /// far, but have **not** been committed yet.
+    pub fn written(&self) -> &[T] {
+        self.peek_filled(self.filled).unwrap_or_default()
+    }
+
+    /// Returns a `&[T]` slice of the first `n` elements that have been
+    /// written so far, but have **not** been committed yet, e.g. to sniff a
+    /// header before deciding whether to commit or discard. Returns `None`,
+    /// if fewer than `n` elements have been written.
+    pub fn peek_filled(&self, n: usize) -> Option<&[T]> {
+        if n > self.filled {
+            return None;
+        }
+        Some(unsafe {
+            from_raw_parts(self.buffer.spare_ptr(), n)
+        })
+    }
+
+    /// Commits all elements that have been written. Returns the number of
+    /// elements that have been committed.
+    ///
+    /// # Errors
+    ///
+    /// Fails with the same errors as
+    /// [`SpareBuffer::commit()`](crate::SpareBuffer::commit).
+    pub fn commit(mut self) -> IoResult<usize> {
+        self.finished = true;
+        self.buffer.commit(self.filled)?;
+        Ok(self.filled)
+    }
+
+    /// Discards all elements that have been written, **without** committing
+    /// them.
+    pub fn discard(mut self) {
+        self.finished = true;
+        self.buffer.discard_spare();
+    }
+}
+
+impl<'b, 'a, T, B> Drop for SpareWriter<'b, 'a, T, B>
+where
+    T: Primitive,
+    B: SpareCapacity<T>
+{
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            self.buffer.discard_if_panicking();
+        } else if self.auto_commit && !self.finished {
+            if let Err(_error) = self.buffer.commit(self.filled) {
+                log_event!(warn, "A dropped `SpareWriter` failed to commit {} written elements: {}", self.filled, _error);
+            }
+        } else if !self.finished {
+            if self.filled > 0 {
+                log_event!(warn, "A `SpareWriter` with {} written elements was dropped without being committed or discarded!", self.filled);
+            }
+            self.buffer.discard_spare();
+        }
+    }
+}
@@ -0,0 +1,82 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+#![cfg(feature = "allocator-api2")]
+
+use std::cell::Cell;
+use std::io::ErrorKind;
+use std::num::NonZeroUsize;
+use std::ptr::NonNull;
+
+use allocator_api2::alloc::{AllocError, Allocator, Global, Layout};
+use allocator_api2::vec::Vec;
+use spare_buffer::SpareBufferIn;
+
+mod common;
+use common::{check_reader_error_commits_nothing, nz};
+
+/// Counts the allocations, and forwards them to the global allocator.
+#[derive(Clone, Copy)]
+struct Counting<'c>(&'c Cell<usize>);
+
+unsafe impl Allocator for Counting<'_> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.0.set(self.0.get() + 1);
+        Global.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        unsafe {
+            Global.deallocate(ptr, layout)
+        }
+    }
+}
+
+#[test]
+fn test_spare_buffer_is_reserved_from_the_allocator() {
+    let allocations = Cell::new(0);
+    let mut vec = Vec::new_in(Counting(&allocations));
+    let mut buffer = SpareBufferIn::from(&mut vec, None);
+    assert_eq!(buffer.allocator().0.get(), 0);
+    assert_eq!(buffer.read_from(&mut &b"abc"[..], nz(8)).unwrap(), 3);
+    assert_eq!(allocations.get(), 1);
+    assert_eq!((buffer.len(), buffer.data()), (3, &b"abc"[..]));
+    assert_eq!(vec.as_slice(), b"abc");
+}
+
+#[test]
+fn test_commit_beyond_the_limit_fails() {
+    let mut vec = Vec::new_in(Global);
+    let mut buffer = SpareBufferIn::from(&mut vec, NonZeroUsize::new(2));
+    assert_eq!(buffer.limit(), NonZeroUsize::new(2));
+    let error = buffer.read_from(&mut &b"abc"[..], nz(4)).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::OutOfMemory);
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_reader_error_commits_nothing() {
+    let mut vec = Vec::new_in(Global);
+    let mut buffer = SpareBufferIn::from(&mut vec, None);
+    check_reader_error_commits_nothing(&mut buffer, |buffer, reader| buffer.read_from(reader, nz(4)), |buffer| buffer.data().to_vec());
+}
+
+#[cfg(not(feature = "safe-only"))]
+#[test]
+fn test_allocate_spare_and_commit() {
+    let mut vec = Vec::new_in(Global);
+    vec.push(1u32);
+    let mut buffer = SpareBufferIn::from(&mut vec, None);
+    buffer.allocate_spare(nz(2))[..2].copy_from_slice(&[2, 3]);
+    buffer.commit(2).unwrap();
+    assert_eq!(buffer.data(), &[1, 2, 3]);
+}
+
+#[test]
+#[should_panic(expected = "The written slice is not a prefix of the spare buffer!")]
+fn test_commit_written_checks_the_prefix() {
+    let mut vec = Vec::new_in(Global);
+    let mut buffer = SpareBufferIn::<u8, _>::from(&mut vec, None);
+    let _ = buffer.commit_written(nz(4), |spare| spare[1..2].write_copy_of_slice(b"x"));
+}
@@ -0,0 +1,182 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::ErrorKind;
+use std::num::NonZeroUsize;
+
+use spare_buffer::{SpareArray, SpareBuffer};
+
+mod common;
+use common::{check_reader_error_commits_nothing, nz, OverreportingReader};
+
+#[test]
+fn test_read_until_full() {
+    let mut array = SpareArray::<u8, 8>::new();
+    let mut reader: &[u8] = b"0123456789";
+    assert_eq!(array.read_from(&mut reader, nz(5)).unwrap(), 5);
+    assert_eq!(array.free(), 3);
+    assert_eq!(array.read_from(&mut reader, nz(3)).unwrap(), 3);
+    assert!(array.is_full());
+    assert_eq!(array.data(), b"01234567");
+    let error = array.read_from(&mut reader, nz(1)).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::OutOfMemory);
+    assert_eq!(array.len(), 8);
+}
+
+#[test]
+fn test_read_at_the_end_of_the_stream() {
+    let mut array = SpareArray::<u8, 4>::default();
+    let mut reader: &[u8] = b"";
+    assert_eq!(array.read_from(&mut reader, nz(4)).unwrap(), 0);
+    assert!(array.is_empty());
+}
+
+#[test]
+fn test_read_exceeding_the_free_space_fails() {
+    let mut array = SpareArray::<u8, 4>::new();
+    let mut reader: &[u8] = b"abc";
+    array.read_from(&mut reader, nz(2)).unwrap();
+    let error = array.read_from(&mut reader, nz(3)).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::OutOfMemory);
+    assert_eq!(array.data(), b"ab");
+}
+
+#[test]
+fn test_reader_error_commits_nothing() {
+    let mut array = SpareArray::<u8, 4>::new();
+    check_reader_error_commits_nothing(&mut array, |array, reader| array.read_from(reader, nz(2)), |array| array.data().to_vec());
+}
+
+#[test]
+#[should_panic(expected = "Read size exceeds the spare buffer!")]
+fn test_reader_overreporting_its_count_panics() {
+    let mut array = SpareArray::<u8, 8>::new();
+    let _ = array.read_from(&mut OverreportingReader, nz(4));
+}
+
+#[test]
+fn test_clear() {
+    let mut array = SpareArray::<u8, 4>::new();
+    let mut reader: &[u8] = b"abcd";
+    array.read_from(&mut reader, nz(4)).unwrap();
+    array.clear();
+    assert!(array.is_empty());
+    assert_eq!(array.free(), array.capacity());
+}
+
+#[test]
+fn test_commit_written() {
+    let mut array = SpareArray::<u32, 4>::new();
+    let count = array.commit_written(nz(2), |spare| {
+        assert_eq!(spare.len(), 4);
+        spare[..2].write_copy_of_slice(&[7, 9])
+    });
+    assert_eq!(count, 2);
+    assert_eq!(array.data(), &[7, 9]);
+}
+
+#[test]
+#[should_panic(expected = "Spare buffer was allocated uninitialized, use commit_written()!")]
+fn test_commit_of_uninit_allocation_panics() {
+    let mut array = SpareArray::<u8, 4>::new();
+    array.allocate_spare_uninit(nz(2))[0].write(1);
+    array.commit(1);
+}
+
+#[test]
+fn test_commit_written_panic_commits_nothing() {
+    let mut array = SpareArray::<u8, 4>::new();
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        array.commit_written(nz(2), |_| panic!("Injected panic!"))
+    }));
+    assert!(result.is_err());
+    assert!(array.is_empty());
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| array.commit(0)));
+    assert!(result.is_err());
+}
+
+#[cfg(not(feature = "safe-only"))]
+#[test]
+fn test_allocate_and_commit() {
+    let mut array = SpareArray::<u16, 4>::new();
+    array.allocate_spare(nz(1))[..3].copy_from_slice(&[1, 2, 3]);
+    array.commit(3);
+    array.allocate_spare(nz(1))[0] = 4;
+    array.commit(1);
+    assert_eq!(array.data(), &[1, 2, 3, 4]);
+}
+
+#[test]
+#[should_panic(expected = "Spare buffer exceeds the fixed capacity!")]
+fn test_allocate_exceeding_the_capacity_panics() {
+    let mut array = SpareArray::<u8, 4>::new();
+    array.allocate_spare_uninit(nz(5));
+}
+
+#[cfg(not(feature = "safe-only"))]
+#[test]
+#[should_panic(expected = "Commit size exceeds available capacity!")]
+fn test_commit_exceeding_the_capacity_panics() {
+    let mut array = SpareArray::<u8, 4>::new();
+    array.allocate_spare(nz(1));
+    array.commit(5);
+}
+
+#[test]
+#[should_panic(expected = "No spare buffer allocated!")]
+fn test_commit_without_allocation_panics() {
+    let mut array = SpareArray::<u8, 4>::new();
+    array.commit(0);
+}
+
+#[test]
+#[should_panic(expected = "No spare buffer allocated!")]
+fn test_clear_invalidates_the_spare_buffer() {
+    let mut array = SpareArray::<u8, 4>::new();
+    array.allocate_spare_uninit(nz(1));
+    array.clear();
+    array.commit(0);
+}
+
+#[test]
+fn test_as_backend_of_spare_buffer() {
+    let mut array = SpareArray::<u8, 8>::new();
+    let mut buffer = SpareBuffer::from(&mut array, NonZeroUsize::new(6));
+    buffer.commit_written(nz(4), |spare| spare[..4].write_copy_of_slice(b"0123")).unwrap();
+    assert_eq!(buffer.data(), b"0123");
+    assert_eq!(array.data(), b"0123");
+}
+
+#[cfg(not(feature = "safe-only"))]
+#[test]
+fn test_backend_limit_is_enforced() {
+    let mut array = SpareArray::<u8, 8>::new();
+    let mut buffer = SpareBuffer::from(&mut array, NonZeroUsize::new(6));
+    buffer.try_allocate_spare(nz(4)).unwrap()[..4].copy_from_slice(b"0123");
+    buffer.commit(4).unwrap();
+    buffer.try_allocate_spare(nz(4)).unwrap()[..4].copy_from_slice(b"4567");
+    let error = buffer.commit(4).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::OutOfMemory);
+    assert_eq!(buffer.data(), b"0123");
+}
+
+#[test]
+#[should_panic(expected = "Spare buffer exceeds the fixed capacity!")]
+fn test_backend_reserve_exceeding_the_capacity_panics() {
+    let mut array = SpareArray::<u8, 4>::new();
+    let mut buffer = SpareBuffer::from(&mut array, None);
+    let _ = buffer.allocate_spare_uninit(nz(8));
+}
+
+#[test]
+fn test_writer_on_array_backend() {
+    let mut array = SpareArray::<u8, 8>::new();
+    let mut buffer = SpareBuffer::from(&mut array, None);
+    let mut writer = buffer.writer(nz(4));
+    assert_eq!(writer.write(b"abc"), 3);
+    assert!(writer.push(b'd'));
+    assert_eq!(writer.commit().unwrap(), 4);
+    buffer.writer(nz(2)).discard();
+    assert_eq!(array.data(), b"abcd");
+}
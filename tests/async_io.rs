@@ -0,0 +1,96 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+#![cfg(feature = "futures-io")]
+
+use std::io::{Read, Result as IoResult, ErrorKind};
+use std::num::NonZeroUsize;
+use std::pin::Pin;
+use std::task::{Context, Poll, Waker};
+
+use futures_io::{AsyncBufRead, AsyncRead, AsyncWrite};
+use spare_buffer::SpareBuffer;
+
+mod common;
+use common::{block_on, nz, ChunkedReader};
+
+/// Alternates between `Poll::Pending` and reading from the inner reader,
+/// waking the task right away.
+struct PendingReader<R> {
+    inner: R,
+    pending: bool,
+}
+
+impl<R> PendingReader<R> {
+    fn new(inner: R) -> Self {
+        Self { inner, pending: true }
+    }
+}
+
+impl<R> AsyncRead for PendingReader<R>
+where
+    R: Read + Unpin
+{
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<IoResult<usize>> {
+        let this = self.get_mut();
+        this.pending = !this.pending;
+        if !this.pending {
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+        Poll::Ready(this.inner.read(buf))
+    }
+}
+
+#[test]
+fn test_read_async() {
+    let mut vec = b"ab".to_vec();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let mut reader = PendingReader::new(&b"cdefgh"[..]);
+    assert_eq!(block_on(buffer.read_async(&mut reader, nz(4))).unwrap(), 4);
+    assert_eq!(buffer.data(), b"abcdef");
+}
+
+#[test]
+fn test_fill_from_async_stops_at_the_limit() {
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, NonZeroUsize::new(5));
+    let mut reader = PendingReader::new(ChunkedReader::new(b"0123456789", 2));
+    assert_eq!(block_on(buffer.fill_from_async(&mut reader, nz(3))).unwrap(), 5);
+    assert_eq!(buffer.data(), b"01234");
+}
+
+#[test]
+fn test_reader_error_keeps_the_committed_bytes() {
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let mut reader = PendingReader::new(ChunkedReader::failing(b"abc", 2, ErrorKind::ConnectionReset));
+    let error = block_on(buffer.fill_from_async(&mut reader, nz(4))).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::ConnectionReset);
+    assert!(!buffer.discard_spare());
+    assert_eq!(buffer.data(), b"abc");
+}
+
+#[test]
+fn test_async_write_and_read() {
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, NonZeroUsize::new(4));
+    let mut cx = Context::from_waker(Waker::noop());
+    assert!(matches!(Pin::new(&mut buffer).poll_write(&mut cx, b"abc"), Poll::Ready(Ok(3))));
+    assert!(matches!(Pin::new(&mut buffer).poll_write(&mut cx, b"de"), Poll::Ready(Ok(1))));
+    match Pin::new(&mut buffer).poll_write(&mut cx, b"f") {
+        Poll::Ready(Err(error)) => assert_eq!(error.kind(), ErrorKind::OutOfMemory),
+        _ => panic!("The write beyond the limit did not fail!"),
+    }
+    assert!(Pin::new(&mut buffer).poll_close(&mut cx).is_ready());
+    let mut output = [0u8; 3];
+    assert!(matches!(Pin::new(&mut buffer).poll_read(&mut cx, &mut output), Poll::Ready(Ok(3))));
+    assert_eq!(&output, b"abc");
+    match Pin::new(&mut buffer).poll_fill_buf(&mut cx) {
+        Poll::Ready(Ok(data)) => assert_eq!(data, b"d"),
+        _ => panic!("The buffer is not ready!"),
+    }
+    Pin::new(&mut buffer).consume(1);
+    assert!(buffer.unconsumed().is_empty());
+}
@@ -0,0 +1,98 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+#![cfg(feature = "http-body")]
+
+use std::collections::VecDeque;
+use std::io::{Error as IoError, ErrorKind};
+use std::num::NonZeroUsize;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use http::{HeaderMap, StatusCode};
+use http_body::{Body, Frame, SizeHint};
+use spare_buffer::{BodyError, SpareBuffer};
+
+mod common;
+use common::block_on;
+
+/// Yields the given frames, and reports the given size hint.
+struct FrameBody {
+    frames: VecDeque<Result<Frame<Bytes>, IoError>>,
+    hint: SizeHint,
+}
+
+impl FrameBody {
+    fn new(chunks: &[&'static [u8]]) -> Self {
+        let frames = chunks.iter().map(|chunk| Ok(Frame::data(Bytes::from_static(chunk)))).collect();
+        Self { frames, hint: SizeHint::default() }
+    }
+
+    fn with_exact(mut self, length: u64) -> Self {
+        self.hint = SizeHint::with_exact(length);
+        self
+    }
+}
+
+impl Body for FrameBody {
+    type Data = Bytes;
+    type Error = IoError;
+
+    fn poll_frame(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Result<Frame<Bytes>, IoError>>> {
+        Poll::Ready(self.get_mut().frames.pop_front())
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.hint
+    }
+}
+
+#[test]
+fn test_collect_body_ignores_trailers() {
+    let mut body = FrameBody::new(&[b"hello ", b"world"]).with_exact(11);
+    body.frames.push_back(Ok(Frame::trailers(HeaderMap::new())));
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    assert_eq!(block_on(buffer.collect_body(body)).unwrap(), 11);
+    assert_eq!(buffer.data(), b"hello world");
+    assert!(buffer.capacity() >= 11);
+}
+
+#[test]
+fn test_size_hint_beyond_the_limit_fails_up_front() {
+    let body = FrameBody::new(&[b"small"]).with_exact(1 << 20);
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, NonZeroUsize::new(1024));
+    let error = block_on(buffer.collect_body(body)).unwrap_err();
+    assert!(matches!(error, BodyError::TooLarge));
+    assert_eq!(error.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_frame_beyond_the_limit_fails() {
+    let body = FrameBody::new(&[b"abc", b"defg", b"h"]);
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, NonZeroUsize::new(5));
+    let error = block_on(buffer.collect_body(body)).unwrap_err();
+    assert!(matches!(error, BodyError::TooLarge));
+    assert_eq!(buffer.data(), b"abc");
+}
+
+#[test]
+fn test_body_error_is_passed_through() {
+    let mut body = FrameBody::new(&[b"abc"]);
+    body.frames.push_back(Err(IoError::from(ErrorKind::ConnectionReset)));
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    match block_on(buffer.collect_body(body)) {
+        Err(error @ BodyError::Body(_)) => {
+            assert_eq!(error.status(), StatusCode::BAD_REQUEST);
+            assert!(std::error::Error::source(&error).is_some());
+        },
+        _ => panic!("The body error was not passed through!"),
+    }
+    assert_eq!(buffer.data(), b"abc");
+}
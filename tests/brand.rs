@@ -0,0 +1,90 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+#![cfg(not(feature = "safe-only"))]
+
+use std::io::ErrorKind;
+use std::num::NonZeroUsize;
+
+use spare_buffer::SpareBuffer;
+
+mod common;
+use common::nz;
+
+#[test]
+fn test_allocate_and_commit_with_tokens() {
+    let mut vec = b"ab".to_vec();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let length = buffer.branded(|mut branded, idle| {
+        let (allocated, spare) = branded.allocate_spare(idle, nz(4));
+        spare[..2].copy_from_slice(b"cd");
+        let length = allocated.len();
+        let (idle, result) = branded.commit(allocated, 2);
+        result.unwrap();
+        let (allocated, spare) = branded.allocate_spare(idle, nz(1));
+        spare[0] = b'e';
+        branded.commit(allocated, 1).1.unwrap();
+        assert_eq!((branded.len(), branded.data()), (5, &b"abcde"[..]));
+        length
+    });
+    assert_eq!(length, nz(4));
+    assert_eq!(buffer.data(), b"abcde");
+}
+
+#[test]
+fn test_discard_returns_an_idle_token() {
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    buffer.branded(|mut branded, idle| {
+        let (allocated, spare) = branded.allocate_spare(idle, nz(4));
+        spare.fill(b'x');
+        let idle = branded.discard(allocated);
+        assert!(branded.is_empty());
+        let (allocated, _) = branded.allocate_spare(idle, nz(4));
+        branded.discard(allocated);
+    });
+    assert!(!buffer.discard_spare());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_failed_commit_returns_an_idle_token() {
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, NonZeroUsize::new(2));
+    buffer.branded(|mut branded, idle| {
+        let (allocated, _) = branded.allocate_spare(idle, nz(4));
+        let (idle, result) = branded.commit(allocated, 3);
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::OutOfMemory);
+        let (allocated, spare) = branded.allocate_spare(idle, nz(2));
+        spare.copy_from_slice(b"ok");
+        branded.commit(allocated, 2).1.unwrap();
+    });
+    assert_eq!(buffer.data(), b"ok");
+}
+
+#[test]
+fn test_panic_in_the_scope_discards_the_spare_buffer() {
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        buffer.branded(|mut branded, idle| {
+            branded.allocate_spare(idle, nz(4)).1.fill(b'x');
+            panic!("Injected panic!");
+        })
+    }));
+    assert!(result.is_err());
+    assert!(!buffer.discard_spare());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Commit size exceeds the length of the allocation!")]
+fn test_commit_beyond_the_allocation_panics() {
+    let mut vec = Vec::<u8>::with_capacity(64);
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    buffer.branded(|mut branded, idle| {
+        let (allocated, _) = branded.allocate_spare(idle, nz(4));
+        let _ = branded.commit(allocated, 5);
+    });
+}
@@ -0,0 +1,96 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::{BufRead, Read, Write, ErrorKind};
+use std::num::NonZeroUsize;
+
+use spare_buffer::SpareBuffer;
+
+#[test]
+fn test_read_consumes_the_committed_bytes() {
+    let mut vec = b"hello world".to_vec();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let mut word = [0u8; 6];
+    buffer.read_exact(&mut word).unwrap();
+    assert_eq!(&word, b"hello ");
+    assert_eq!(buffer.consumed(), 6);
+    assert_eq!(buffer.unconsumed(), b"world");
+    let mut rest = Vec::new();
+    assert_eq!(buffer.read_to_end(&mut rest).unwrap(), 5);
+    assert_eq!(rest, b"world");
+    assert_eq!(buffer.read(&mut word).unwrap(), 0);
+    assert_eq!(buffer.data(), b"hello world");
+}
+
+#[test]
+fn test_lines_are_read_while_filling() {
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    buffer.write_all(b"first\nsec").unwrap();
+    let mut line = String::new();
+    buffer.read_line(&mut line).unwrap();
+    assert_eq!(line, "first\n");
+    assert_eq!(buffer.fill_buf().unwrap(), b"sec");
+    buffer.write_all(b"ond\n").unwrap();
+    line.clear();
+    buffer.read_line(&mut line).unwrap();
+    assert_eq!(line, "second\n");
+    buffer.compact();
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_write_is_shortened_at_the_limit() {
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, NonZeroUsize::new(4));
+    assert_eq!(buffer.write(b"abc").unwrap(), 3);
+    assert_eq!(buffer.write(b"def").unwrap(), 1);
+    assert_eq!(buffer.write(b"").unwrap(), 0);
+    assert_eq!(buffer.write(b"g").unwrap_err().kind(), ErrorKind::OutOfMemory);
+    assert_eq!(buffer.data(), b"abcd");
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn test_buf_mut() {
+    use bytes::BufMut;
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, NonZeroUsize::new(8));
+    assert_eq!(buffer.remaining_mut(), 8);
+    buffer.put_slice(b"abc");
+    buffer.put_u16(0x6465);
+    assert_eq!(buffer.remaining_mut(), 3);
+    assert_eq!(buffer.data(), b"abcde");
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+#[should_panic(expected = "advance out of bounds")]
+fn test_buf_mut_beyond_the_limit_panics() {
+    use bytes::BufMut;
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, NonZeroUsize::new(2));
+    buffer.put_slice(b"abc");
+}
+
+#[cfg(feature = "tokio")]
+#[test]
+fn test_tokio_async_buf_read() {
+    use std::pin::Pin;
+    use std::task::{Context, Poll, Waker};
+    use tokio::io::{AsyncBufRead, AsyncRead, ReadBuf};
+    let mut vec = b"abcdef".to_vec();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let mut cx = Context::from_waker(Waker::noop());
+    match Pin::new(&mut buffer).poll_fill_buf(&mut cx) {
+        Poll::Ready(Ok(data)) => assert_eq!(data, b"abcdef"),
+        _ => panic!("The buffer is not ready!"),
+    }
+    Pin::new(&mut buffer).consume(2);
+    let mut output = [0u8; 3];
+    let mut read_buf = ReadBuf::new(&mut output);
+    assert!(Pin::new(&mut buffer).poll_read(&mut cx, &mut read_buf).is_ready());
+    assert_eq!(read_buf.filled(), b"cde");
+    assert_eq!(buffer.unconsumed(), b"f");
+}
@@ -0,0 +1,80 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::ErrorKind;
+use std::num::NonZeroUsize;
+
+use spare_buffer::SpareBuffer;
+
+mod common;
+use common::{nz, FailingReader, OverreportingReader};
+
+#[test]
+fn test_fill_from_until_the_end_of_the_stream() {
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    assert_eq!(buffer.fill_from(&mut &b"0123456789"[..], nz(3)).unwrap(), 10);
+    assert_eq!(buffer.data(), b"0123456789");
+}
+
+#[test]
+fn test_fill_from_stops_at_the_limit() {
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, NonZeroUsize::new(4));
+    assert_eq!(buffer.fill_from(&mut &b"0123456789"[..], nz(3)).unwrap(), 4);
+    assert_eq!(buffer.data(), b"0123");
+}
+
+#[test]
+fn test_read_all_from_fails_beyond_the_limit() {
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, NonZeroUsize::new(4));
+    let error = buffer.read_all_from(&mut &b"0123456789"[..]).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::OutOfMemory);
+    assert_eq!(buffer.data(), b"0123");
+}
+
+#[test]
+fn test_reader_error_discards_the_spare_buffer() {
+    let mut vec = b"ab".to_vec();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let error = buffer.fill_from(&mut FailingReader(ErrorKind::ConnectionReset), nz(4)).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::ConnectionReset);
+    assert!(!buffer.discard_spare());
+    let error = buffer.read_all_from(&mut FailingReader(ErrorKind::TimedOut)).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::TimedOut);
+    assert!(!buffer.discard_spare());
+    assert_eq!(buffer.data(), b"ab");
+}
+
+#[test]
+#[should_panic(expected = "Read size exceeds the spare buffer!")]
+fn test_reader_overreporting_its_count_panics() {
+    let mut vec = Vec::with_capacity(64);
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let _ = buffer.fill_from(&mut OverreportingReader, nz(4));
+}
+
+#[test]
+fn test_read_exact_into() {
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    buffer.read_exact_into(&mut &b"abcdef"[..], nz(4)).unwrap();
+    assert_eq!(buffer.data(), b"abcd");
+    let error = buffer.read_exact_into(&mut &b"xy"[..], nz(4)).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+    assert!(!buffer.discard_spare());
+    assert_eq!(buffer.data(), b"abcd");
+}
+
+#[test]
+fn test_fill_until() {
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let mut reader: &[u8] = b"key: value\nrest";
+    assert!(buffer.fill_until(&mut reader, b'\n').unwrap());
+    assert_eq!(buffer.data(), b"key: value\n");
+    assert!(!buffer.fill_until(&mut reader, b'\n').unwrap());
+    assert_eq!(buffer.data(), b"key: value\nrest");
+}
@@ -0,0 +1,139 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+#![allow(dead_code)]
+
+//! Helpers that are shared by the integration tests. Not every test uses
+//! every helper.
+
+use std::future::Future;
+use std::io::{Read, Result as IoResult, Error as IoError, ErrorKind};
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::pin::{pin, Pin};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::Thread;
+
+pub fn nz(value: usize) -> NonZeroUsize {
+    NonZeroUsize::new(value).unwrap()
+}
+
+/// Writes `data` to a new temporary file, which is removed when dropped.
+pub struct TempFile(pub PathBuf);
+
+impl TempFile {
+    pub fn new(name: &str, data: &[u8]) -> Self {
+        let path = std::env::temp_dir().join(format!("spare_buffer_{}_{}", name, std::process::id()));
+        std::fs::write(&path, data).unwrap();
+        Self(path)
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+/// Always fails with the given kind of error.
+pub struct FailingReader(pub ErrorKind);
+
+impl Read for FailingReader {
+    fn read(&mut self, _buf: &mut [u8]) -> IoResult<usize> {
+        Err(IoError::new(self.0, "Injected error!"))
+    }
+}
+
+/// Reports more bytes than fit into the given buffer.
+pub struct OverreportingReader;
+
+impl Read for OverreportingReader {
+    fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
+        Ok(buf.len() + 1)
+    }
+}
+
+/// Yields the given data in chunks of at most `chunk` bytes, then fails with
+/// `error`, if any, or reports the end of the stream.
+pub struct ChunkedReader {
+    data: Vec<u8>,
+    pub position: usize,
+    chunk: usize,
+    pub error: Option<ErrorKind>,
+}
+
+impl ChunkedReader {
+    pub fn new(data: &[u8], chunk: usize) -> Self {
+        Self { data: data.to_vec(), position: 0, chunk, error: None }
+    }
+
+    /// Yields the given data in chunks, then fails with `error`.
+    pub fn failing(data: &[u8], chunk: usize, error: ErrorKind) -> Self {
+        Self { error: Some(error), ..Self::new(data, chunk) }
+    }
+}
+
+impl Read for ChunkedReader {
+    fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
+        let count = buf.len().min(self.chunk).min(self.data.len() - self.position);
+        if count == 0 {
+            if let Some(kind) = self.error.take() {
+                return Err(IoError::new(kind, "Injected error!"));
+            }
+        }
+        buf[..count].copy_from_slice(&self.data[self.position..self.position + count]);
+        self.position += count;
+        Ok(count)
+    }
+}
+
+/// The *template* of the `test_reader_error_commits_nothing()` tests: reads
+/// `b"a"` into `buffer` via `read`, then reads from a failing reader, and
+/// checks that the error is passed through and that `data` still returns
+/// *only* `b"a"`.
+pub fn check_reader_error_commits_nothing<S, R, D>(buffer: &mut S, mut read: R, data: D)
+where
+    R: FnMut(&mut S, &mut ChunkedReader) -> IoResult<usize>,
+    D: Fn(&S) -> Vec<u8>
+{
+    assert_eq!(read(buffer, &mut ChunkedReader::new(b"a", 1)).unwrap(), 1);
+    let error = read(buffer, &mut ChunkedReader::failing(b"", 1, ErrorKind::BrokenPipe)).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::BrokenPipe);
+    assert_eq!(data(buffer), b"a");
+}
+
+/// Counts how often it has been woken up.
+pub struct CountingWaker(pub AtomicUsize);
+
+impl Wake for CountingWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+pub fn poll_once<F: Future>(future: Pin<&mut F>, waker: &Waker) -> Poll<F::Output> {
+    future.poll(&mut Context::from_waker(waker))
+}
+
+/// Unparks the waiting thread.
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let waker = Waker::from(Arc::new(ThreadWaker(std::thread::current())));
+    let mut future = pin!(future);
+    loop {
+        match future.as_mut().poll(&mut Context::from_waker(&waker)) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => std::thread::park(),
+        }
+    }
+}
@@ -0,0 +1,36 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::ErrorKind;
+use std::num::NonZeroUsize;
+
+use spare_buffer::SpareBuffer;
+
+#[test]
+fn test_concat_into() {
+    let mut vec = b"ab".to_vec();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    assert_eq!(buffer.concat_into(&[&b"cd"[..], b"", b"efg"]).unwrap(), 5);
+    assert_eq!(buffer.data(), b"abcdefg");
+    assert_eq!(buffer.concat_into::<&[u8]>(&[]).unwrap(), 0);
+    assert_eq!(buffer.data(), b"abcdefg");
+}
+
+#[test]
+fn test_concat_into_checks_the_limit_up_front() {
+    let mut vec = b"ab".to_vec();
+    let mut buffer = SpareBuffer::from(&mut vec, NonZeroUsize::new(5));
+    let error = buffer.concat_into(&[&b"cd"[..], b"ef"]).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::OutOfMemory);
+    assert!(!buffer.discard_spare());
+    assert_eq!(buffer.data(), b"ab");
+}
+
+#[test]
+fn test_merge() {
+    let mut vec = vec![1u32];
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    assert_eq!(buffer.merge([vec![2, 3], vec![], vec![4]]).unwrap(), 3);
+    assert_eq!(buffer.data(), &[1, 2, 3, 4]);
+}
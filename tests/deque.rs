@@ -0,0 +1,90 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::collections::VecDeque;
+use std::io::{BufRead, Read, ErrorKind};
+use std::num::NonZeroUsize;
+
+use spare_buffer::SpareDeque;
+
+mod common;
+use common::{check_reader_error_commits_nothing, nz};
+
+fn joined(deque: &SpareDeque<u8>) -> Vec<u8> {
+    let (first, second) = deque.data();
+    [first, second].concat()
+}
+
+#[test]
+fn test_commit_and_consume() {
+    let mut deque = VecDeque::from(b"ab".to_vec());
+    let mut spare = SpareDeque::from(&mut deque, None);
+    let (first, _) = spare.allocate_spare(nz(4));
+    first[..3].copy_from_slice(b"cde");
+    spare.commit(3).unwrap();
+    assert_eq!(joined(&spare), b"abcde");
+    spare.consume(2);
+    assert_eq!(joined(&spare), b"cde");
+    spare.consume(16);
+    assert!(spare.is_empty());
+}
+
+#[test]
+fn test_uncommitted_elements_are_removed_on_drop() {
+    let mut deque = VecDeque::from(b"ab".to_vec());
+    let mut spare = SpareDeque::from(&mut deque, None);
+    spare.allocate_spare(nz(8));
+    drop(spare);
+    assert_eq!(deque, b"ab");
+}
+
+#[test]
+fn test_wraparound() {
+    let mut deque = VecDeque::with_capacity(8);
+    let capacity = deque.capacity();
+    let mut spare = SpareDeque::from(&mut deque, None);
+    spare.allocate_spare(NonZeroUsize::new(capacity - 2).unwrap()).0.fill(b'x');
+    spare.commit(capacity - 2).unwrap();
+    spare.consume(capacity - 3);
+    let (first, second) = spare.allocate_spare(nz(4));
+    assert_eq!((first.len(), second.len()), (2, 2));
+    first.copy_from_slice(b"ab");
+    second.copy_from_slice(b"cd");
+    spare.commit(4).unwrap();
+    assert_eq!(spare.data(), (&b"xab"[..], &b"cd"[..]));
+    let mut output = Vec::new();
+    spare.read_to_end(&mut output).unwrap();
+    assert_eq!(output, b"xabcd");
+    assert!(spare.is_empty());
+}
+
+#[test]
+fn test_commit_beyond_the_limit_fails() {
+    let mut deque = VecDeque::new();
+    let mut spare = SpareDeque::from(&mut deque, NonZeroUsize::new(4));
+    assert_eq!(spare.read_from(&mut &b"abc"[..], nz(3)).unwrap(), 3);
+    let error = spare.read_from(&mut &b"de"[..], nz(2)).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::OutOfMemory);
+    assert_eq!(joined(&spare), b"abc");
+    assert_eq!(spare.fill_buf().unwrap(), b"abc");
+}
+
+#[test]
+fn test_reader_error_commits_nothing() {
+    let mut deque = VecDeque::new();
+    let mut spare = SpareDeque::from(&mut deque, None);
+    check_reader_error_commits_nothing(&mut spare, |spare, reader| spare.read_from(reader, nz(4)), joined);
+    drop(spare);
+    assert_eq!(deque, b"a");
+}
+
+#[test]
+#[should_panic(expected = "No spare buffer allocated!")]
+fn test_commit_without_allocation_panics() {
+    let mut deque = VecDeque::<u8>::new();
+    let mut spare = SpareDeque::from(&mut deque, None);
+    spare.allocate_spare(nz(4));
+    spare.consume(0);
+    let _ = spare.commit(1);
+}
@@ -0,0 +1,55 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::error::Error;
+use std::io::{Error as IoError, ErrorKind};
+
+use spare_buffer::{AllocationError, SpareBuffer};
+
+mod common;
+use common::nz;
+
+fn allocation_error(error: &IoError) -> &AllocationError {
+    assert_eq!(error.kind(), ErrorKind::OutOfMemory);
+    error.get_ref().and_then(|inner| inner.downcast_ref()).expect("Not an allocation error!")
+}
+
+#[test]
+fn test_exceeding_the_max_allocation() {
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None).with_max_allocation(Some(nz(4)));
+    assert_eq!(buffer.max_allocation(), Some(nz(4)));
+    let error = buffer.read_exact_into(&mut &b"abcde"[..], nz(5)).unwrap_err();
+    let expected = AllocationError::ExceedsMaxAllocation { requested: nz(5), max_allocation: nz(4) };
+    assert_eq!(allocation_error(&error), &expected);
+    assert_eq!(expected.to_string(), "The allocation size 5 exceeds the specified maximum of 4!");
+    assert!(expected.source().is_none());
+    assert_eq!(buffer.capacity(), 0);
+    buffer.read_exact_into(&mut &b"abcd"[..], nz(4)).unwrap();
+    assert_eq!(buffer.data(), b"abcd");
+}
+
+#[test]
+fn test_capacity_overflow_is_reported() {
+    let mut vec = b"a".to_vec();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let error = buffer.read_exact_into(&mut &b""[..], nz(isize::MAX as usize)).unwrap_err();
+    let inner = allocation_error(&error);
+    assert!(matches!(inner, AllocationError::Reserve(_)));
+    assert!(inner.source().is_some());
+    assert!(inner.to_string().starts_with("Failed to reserve the capacity: "));
+    assert!(!buffer.discard_spare());
+    assert_eq!(buffer.data(), b"a");
+}
+
+#[cfg(not(feature = "safe-only"))]
+#[test]
+fn test_try_allocate_spare() {
+    let mut vec = Vec::<u8>::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None).with_max_allocation(Some(nz(4)));
+    assert!(matches!(buffer.try_allocate_spare(nz(5)), Err(AllocationError::ExceedsMaxAllocation { .. })));
+    assert!(!buffer.discard_spare());
+    assert!(buffer.try_allocate_spare(nz(4)).unwrap().len() >= 4);
+    assert!(buffer.discard_spare());
+}
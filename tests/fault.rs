@@ -0,0 +1,57 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+#![cfg(feature = "test-util")]
+
+use std::io::{Error as IoError, ErrorKind};
+
+use spare_buffer::{AllocationError, FaultInjector, SpareBuffer};
+
+mod common;
+use common::nz;
+
+fn is_injected(error: &IoError) -> bool {
+    error.kind() == ErrorKind::OutOfMemory
+        && error.get_ref().and_then(|inner| inner.downcast_ref::<AllocationError>()) == Some(&AllocationError::Injected)
+}
+
+#[test]
+fn test_fail_nth_fails_once() {
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None).with_fault_injector(FaultInjector::fail_nth(nz(2)));
+    buffer.read_exact_into(&mut &b"ab"[..], nz(2)).unwrap();
+    let error = buffer.read_exact_into(&mut &b"cd"[..], nz(2)).unwrap_err();
+    assert!(is_injected(&error));
+    assert!(!buffer.discard_spare());
+    buffer.read_exact_into(&mut &b"ef"[..], nz(2)).unwrap();
+    assert_eq!(buffer.fault_injector().unwrap().calls(), 3);
+    assert_eq!(buffer.data(), b"abef");
+}
+
+#[test]
+fn test_fail_from_fails_persistently() {
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None).with_fault_injector(FaultInjector::fail_from(nz(2)));
+    buffer.read_exact_into(&mut &b"ab"[..], nz(2)).unwrap();
+    for _ in 0..3 {
+        assert!(is_injected(&buffer.read_exact_into(&mut &b"cd"[..], nz(2)).unwrap_err()));
+    }
+    assert_eq!(buffer.fault_injector().unwrap().calls(), 4);
+    assert_eq!(buffer.data(), b"ab");
+}
+
+#[test]
+fn test_fill_keeps_the_data_before_the_fault() {
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None).with_fault_injector(FaultInjector::fail_nth(nz(3)));
+    let error = buffer.fill_from(&mut &b"0123456789"[..], nz(2)).unwrap_err();
+    assert!(is_injected(&error));
+    assert_eq!(buffer.data(), b"0123");
+}
+
+#[test]
+fn test_no_injector_by_default() {
+    let mut vec = Vec::<u8>::new();
+    assert!(SpareBuffer::from(&mut vec, None).fault_injector().is_none());
+}
@@ -0,0 +1,62 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+#![cfg(all(unix, not(miri)))]
+
+use std::fs::{File, OpenOptions};
+use std::io::{ErrorKind, Write};
+
+use spare_buffer::SpareBuffer;
+
+mod common;
+use common::{nz, TempFile};
+
+#[test]
+fn test_read_fd_until_the_end_of_the_file() {
+    let file = TempFile::new("read_fd", b"0123456789");
+    let fd = File::open(&file.0).unwrap();
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    assert_eq!(buffer.read_fd(&fd, nz(4)).unwrap(), 4);
+    assert_eq!(buffer.read_fd(&fd, nz(8)).unwrap(), 6);
+    assert_eq!(buffer.read_fd(&fd, nz(8)).unwrap(), 0);
+    assert_eq!(buffer.data(), b"0123456789");
+}
+
+#[test]
+fn test_read_fd_from_a_pipe() {
+    let (reader, mut writer) = std::io::pipe().unwrap();
+    writer.write_all(b"piped").unwrap();
+    drop(writer);
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    assert_eq!(buffer.read_fd(&reader, nz(16)).unwrap(), 5);
+    assert_eq!(buffer.read_fd(&reader, nz(16)).unwrap(), 0);
+    assert_eq!(buffer.data(), b"piped");
+}
+
+#[test]
+fn test_pread_fd_does_not_move_the_offset() {
+    let file = TempFile::new("pread_fd", b"0123456789");
+    let fd = File::open(&file.0).unwrap();
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    assert_eq!(buffer.pread_fd(&fd, nz(3), 6).unwrap(), 3);
+    assert_eq!(buffer.read_fd(&fd, nz(2)).unwrap(), 2);
+    assert_eq!(buffer.data(), b"67801");
+    let error = buffer.pread_fd(&fd, nz(2), u64::MAX).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidInput);
+}
+
+#[test]
+fn test_os_error_commits_nothing() {
+    let file = TempFile::new("read_fd_error", b"data");
+    let fd = OpenOptions::new().write(true).open(&file.0).unwrap();
+    let mut vec = b"ab".to_vec();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let error = buffer.read_fd(&fd, nz(4)).unwrap_err();
+    assert!(error.raw_os_error().is_some());
+    assert!(!buffer.discard_spare());
+    assert_eq!(buffer.data(), b"ab");
+}
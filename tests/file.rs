@@ -0,0 +1,47 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+#![cfg(not(miri))]
+
+use std::io::ErrorKind;
+use std::num::NonZeroUsize;
+
+use spare_buffer::read_file;
+
+mod common;
+use common::TempFile;
+
+#[test]
+fn test_read_file() {
+    let data: Vec<u8> = (0..100_000u32).map(|value| value as u8).collect();
+    let file = TempFile::new("read_file", &data);
+    let vec = read_file(&file.0, None).unwrap();
+    assert_eq!(vec, data);
+    assert_eq!(vec.capacity(), data.len());
+}
+
+#[test]
+fn test_read_empty_file() {
+    let file = TempFile::new("read_empty_file", b"");
+    assert!(read_file(&file.0, None).unwrap().is_empty());
+}
+
+#[test]
+fn test_read_file_within_the_limit() {
+    let file = TempFile::new("read_file_within_the_limit", b"0123");
+    assert_eq!(read_file(&file.0, NonZeroUsize::new(4)).unwrap(), b"0123");
+}
+
+#[test]
+fn test_read_file_exceeding_the_limit_fails() {
+    let file = TempFile::new("read_file_exceeding_the_limit", b"0123456789");
+    let error = read_file(&file.0, NonZeroUsize::new(4)).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::OutOfMemory);
+}
+
+#[test]
+fn test_read_missing_file_fails() {
+    let error = read_file(std::env::temp_dir().join("spare_buffer_missing_file"), None).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::NotFound);
+}
@@ -0,0 +1,50 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use spare_buffer::SpareBuffer;
+
+mod common;
+use common::nz;
+
+#[test]
+fn test_freeze_splits_off_the_prefix() {
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    buffer.fill_from(&mut &b"frame|rest"[..], nz(4)).unwrap();
+    let frozen = buffer.freeze(6);
+    assert_eq!(&frozen[..], b"frame|");
+    assert_eq!(buffer.data(), b"rest");
+    buffer.fill_from(&mut &b"!"[..], nz(4)).unwrap();
+    assert_eq!(buffer.data(), b"rest!");
+}
+
+#[test]
+fn test_freeze_counts_consumed_elements() {
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    buffer.fill_from(&mut &b"abcdef"[..], nz(4)).unwrap();
+    buffer.consume(3);
+    assert_eq!(&buffer.freeze(2)[..], b"ab");
+    assert_eq!(buffer.consumed(), 1);
+    assert_eq!(buffer.unconsumed(), b"def");
+}
+
+#[test]
+#[should_panic(expected = "Freeze size exceeds the length!")]
+fn test_freeze_beyond_the_length_panics() {
+    let mut vec = b"ab".to_vec();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    buffer.freeze(3);
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn test_freeze_bytes_and_split_to() {
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    buffer.fill_from(&mut &b"one|two|three"[..], nz(4)).unwrap();
+    assert_eq!(buffer.freeze_bytes(4), &b"one|"[..]);
+    assert_eq!(buffer.split_to(4).unwrap(), &b"two|"[..]);
+    assert_eq!(buffer.data(), b"three");
+}
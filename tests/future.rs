@@ -0,0 +1,87 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+#![cfg(not(feature = "safe-only"))]
+
+use std::io::{Error as IoError, ErrorKind};
+use std::pin::{pin, Pin};
+use std::task::{Context, Poll, Waker};
+
+use spare_buffer::SpareBuffer;
+
+mod common;
+use common::{block_on, nz, poll_once};
+
+#[test]
+fn test_poll_fill_commits_once_ready() {
+    let mut vec = b"ab".to_vec();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut ready = false;
+    let mut poll_fn = |_: &mut Context<'_>, spare: &mut [u8]| {
+        assert_eq!(spare.len(), 4);
+        if !std::mem::replace(&mut ready, true) {
+            return Poll::Pending;
+        }
+        spare[..2].copy_from_slice(b"cd");
+        Poll::Ready(Ok(2))
+    };
+    assert!(buffer.poll_fill(&mut cx, nz(4), &mut poll_fn).is_pending());
+    assert_eq!(buffer.data(), b"ab");
+    assert!(matches!(buffer.poll_fill(&mut cx, nz(4), &mut poll_fn), Poll::Ready(Ok(2))));
+    assert_eq!(buffer.data(), b"abcd");
+}
+
+#[test]
+fn test_interrupted_is_retried() {
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let mut failures = 2;
+    let result = block_on(buffer.fill_with(nz(4), |_, spare| {
+        if failures > 0 {
+            failures -= 1;
+            return Poll::Ready(Err(IoError::from(ErrorKind::Interrupted)));
+        }
+        spare[0] = b'x';
+        Poll::Ready(Ok(1))
+    }));
+    assert_eq!(result.unwrap(), 1);
+    assert_eq!(buffer.data(), b"x");
+}
+
+#[test]
+fn test_error_commits_nothing() {
+    let mut vec = b"ab".to_vec();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let future = buffer.fill_with(nz(4), |_, spare: &mut [u8]| {
+        spare.fill(b'x');
+        Poll::Ready(Err(IoError::from(ErrorKind::WouldBlock)))
+    });
+    match poll_once(pin!(future), Waker::noop()) {
+        Poll::Ready(Err(error)) => assert_eq!(error.kind(), ErrorKind::WouldBlock),
+        _ => panic!("The error was not passed through!"),
+    }
+    assert!(!buffer.discard_spare());
+    assert_eq!(buffer.data(), b"ab");
+}
+
+#[test]
+fn test_fill_future_can_be_moved_between_polls() {
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let mut polls = 0;
+    let mut future = buffer.fill_with(nz(2), move |_, spare: &mut [u8]| {
+        polls += 1;
+        if polls < 3 {
+            return Poll::Pending;
+        }
+        spare.copy_from_slice(b"ok");
+        Poll::Ready(Ok(2))
+    });
+    assert!(poll_once(Pin::new(&mut future), Waker::noop()).is_pending());
+    let mut moved = future;
+    assert!(poll_once(Pin::new(&mut moved), Waker::noop()).is_pending());
+    assert!(matches!(poll_once(Pin::new(&mut moved), Waker::noop()), Poll::Ready(Ok(2))));
+    assert_eq!(buffer.data(), b"ok");
+}
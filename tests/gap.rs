@@ -0,0 +1,103 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::ErrorKind;
+use std::num::NonZeroUsize;
+
+use spare_buffer::SpareGap;
+
+mod common;
+use common::FailingReader;
+
+#[test]
+fn test_insert_at_the_cursor() {
+    let mut gap = SpareGap::from_slice(b"held");
+    gap.set_cursor(2);
+    gap.insert(b"LLO wor");
+    assert_eq!(gap.cursor(), 9);
+    assert_eq!(gap.data(), (&b"heLLO wor"[..], &b"ld"[..]));
+    assert_eq!(gap.to_vec(), b"heLLO world");
+}
+
+#[test]
+fn test_move_the_cursor_back_and_forth() {
+    let mut gap = SpareGap::from_slice(b"abcdef");
+    gap.set_cursor(1);
+    assert_eq!(gap.data(), (&b"a"[..], &b"bcdef"[..]));
+    gap.set_cursor(5);
+    assert_eq!(gap.data(), (&b"abcde"[..], &b"f"[..]));
+    gap.set_cursor(0);
+    assert_eq!(gap.data(), (&b""[..], &b"abcdef"[..]));
+    gap.set_cursor(6);
+    assert_eq!(gap.data(), (&b"abcdef"[..], &b""[..]));
+}
+
+#[test]
+fn test_grow_keeps_the_data_after_the_cursor() {
+    let mut gap = SpareGap::<u8>::with_capacity(0);
+    gap.insert(b"xy");
+    gap.set_cursor(1);
+    let data = vec![b'-'; 1000];
+    gap.insert(&data);
+    assert!(gap.capacity() >= 1002);
+    let (before, after) = gap.data();
+    assert_eq!(before.len(), 1001);
+    assert_eq!(after, b"y");
+}
+
+#[test]
+fn test_remove_is_clamped() {
+    let mut gap = SpareGap::from_slice(b"abcdef");
+    gap.set_cursor(3);
+    assert_eq!(gap.remove_before(10), 3);
+    assert_eq!(gap.remove_after(2), 2);
+    assert_eq!(gap.remove_after(10), 1);
+    assert!(gap.is_empty());
+    gap.insert(b"z");
+    assert_eq!(gap.to_vec(), b"z");
+}
+
+#[test]
+fn test_read_from() {
+    let mut gap = SpareGap::from_slice(b"[]");
+    gap.set_cursor(1);
+    let length = NonZeroUsize::new(16).unwrap();
+    assert_eq!(gap.read_from(&mut &b"data"[..], length).unwrap(), 4);
+    assert_eq!(gap.to_vec(), b"[data]");
+    let error = gap.read_from(&mut FailingReader(ErrorKind::BrokenPipe), length).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::BrokenPipe);
+    assert_eq!(gap.to_vec(), b"[data]");
+    assert_eq!(gap.cursor(), 5);
+}
+
+#[test]
+#[should_panic(expected = "No spare buffer allocated!")]
+fn test_reader_error_discards_the_spare_buffer() {
+    let mut gap = SpareGap::<u8>::with_capacity(8);
+    assert!(gap.read_from(&mut FailingReader(ErrorKind::BrokenPipe), NonZeroUsize::new(4).unwrap()).is_err());
+    gap.commit(0);
+}
+
+#[cfg(not(feature = "safe-only"))]
+#[test]
+fn test_spare_buffer_at_the_cursor() {
+    let mut gap = SpareGap::from_slice(b"ac");
+    gap.set_cursor(1);
+    let spare = gap.allocate_spare(NonZeroUsize::new(1).unwrap());
+    spare[0] = b'b';
+    gap.commit(1);
+    assert_eq!(gap.to_vec(), b"abc");
+}
+
+#[test]
+#[should_panic(expected = "Cursor position exceeds the length!")]
+fn test_cursor_beyond_the_length_panics() {
+    SpareGap::from_slice(b"ab").set_cursor(3);
+}
+
+#[test]
+#[should_panic(expected = "No spare buffer allocated!")]
+fn test_commit_without_spare_panics() {
+    SpareGap::<u8>::with_capacity(8).commit(1);
+}
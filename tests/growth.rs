@@ -0,0 +1,52 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use spare_buffer::{GrowthPolicy, SpareBuffer};
+
+mod common;
+use common::nz;
+
+/// Appends the elements of `data` one allocation at a time, and returns the
+/// capacity after each allocation.
+fn capacities(policy: GrowthPolicy, data: &[&[u32]]) -> Vec<usize> {
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None).with_growth_policy(policy);
+    assert_eq!(buffer.growth_policy(), policy);
+    data.iter().map(|data| {
+        buffer.concat_into(&[data]).unwrap();
+        buffer.capacity()
+    }).collect()
+}
+
+#[test]
+fn test_doubling_is_the_default() {
+    let mut vec = Vec::<u8>::new();
+    assert_eq!(SpareBuffer::from(&mut vec, None).growth_policy(), GrowthPolicy::Doubling);
+    let result = capacities(GrowthPolicy::Doubling, &[&[0; 10], &[0; 1]]);
+    assert!(result[0] >= 10);
+    assert!(result[1] >= 2 * result[0]);
+}
+
+#[test]
+fn test_exact() {
+    assert_eq!(capacities(GrowthPolicy::Exact, &[&[0; 10], &[0; 1], &[0; 5]]), [10, 11, 16]);
+}
+
+#[test]
+fn test_increment() {
+    assert_eq!(capacities(GrowthPolicy::Increment(nz(8)), &[&[0; 10], &[0; 6], &[0; 1]]), [16, 16, 24]);
+}
+
+#[test]
+fn test_page_rounded() {
+    assert_eq!(capacities(GrowthPolicy::PageRounded(nz(64)), &[&[0; 10], &[0; 7]]), [16, 32]);
+}
+
+#[test]
+fn test_spare_capacity_is_used_first() {
+    let mut vec = Vec::<u32>::with_capacity(32);
+    let mut buffer = SpareBuffer::from(&mut vec, None).with_growth_policy(GrowthPolicy::Exact);
+    buffer.concat_into(&[[0; 20]]).unwrap();
+    assert_eq!(buffer.capacity(), 32);
+}
@@ -0,0 +1,60 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+#![cfg(not(feature = "safe-only"))]
+
+use spare_buffer::SpareBuffer;
+
+mod common;
+use common::nz;
+
+#[test]
+fn test_commit_consumes_the_handle() {
+    let mut vec = b"ab".to_vec();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let mut handle = buffer.allocate_handle(nz(4));
+    assert_eq!(handle.len(), nz(4));
+    assert_eq!(handle.len().get(), handle.as_ref().len());
+    handle[..2].copy_from_slice(b"cd");
+    handle.commit(2).unwrap();
+    assert!(!buffer.discard_spare());
+    assert_eq!(buffer.data(), b"abcd");
+}
+
+#[test]
+fn test_discarded_handle_commits_nothing() {
+    let mut vec = b"ab".to_vec();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let mut handle = buffer.allocate_handle(nz(4));
+    handle.fill(b'x');
+    handle.discard();
+    assert!(!buffer.discard_spare());
+    assert_eq!(buffer.data(), b"ab");
+}
+
+#[test]
+fn test_dropped_handle_discards_the_spare_buffer() {
+    let mut vec = b"ab".to_vec();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    buffer.allocate_handle(nz(4)).fill(b'x');
+    assert!(!buffer.discard_spare());
+    assert_eq!(buffer.data(), b"ab");
+}
+
+#[test]
+fn test_commit_beyond_the_limit_fails() {
+    let mut vec = Vec::<u8>::new();
+    let mut buffer = SpareBuffer::from(&mut vec, Some(nz(2)));
+    let error = buffer.allocate_handle(nz(4)).commit(3).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::OutOfMemory);
+    assert!(buffer.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Commit size exceeds the length of the allocation!")]
+fn test_commit_beyond_the_handle_panics() {
+    let mut vec = Vec::<u8>::with_capacity(64);
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let _ = buffer.allocate_handle(nz(4)).commit(5);
+}
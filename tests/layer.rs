@@ -0,0 +1,64 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+#![cfg(feature = "tower")]
+
+use std::convert::Infallible;
+use std::future::{ready, Ready};
+use std::num::NonZeroUsize;
+use std::task::{Context, Poll};
+
+use http::{Request, Response, StatusCode};
+use spare_buffer::BufferBodyLayer;
+use tower_layer::Layer;
+use tower_service::Service;
+
+mod common;
+use common::block_on;
+
+/// Responds with the buffered body of the request, in uppercase.
+#[derive(Clone)]
+struct Uppercase;
+
+impl Service<Request<Vec<u8>>> for Uppercase {
+    type Response = Response<Vec<u8>>;
+    type Error = Infallible;
+    type Future = Ready<Result<Self::Response, Infallible>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request<Vec<u8>>) -> Self::Future {
+        ready(Ok(Response::new(request.into_body().to_ascii_uppercase())))
+    }
+}
+
+fn post(body: &str) -> Request<String> {
+    Request::post("/upload").body(body.to_owned()).unwrap()
+}
+
+#[test]
+fn test_body_is_buffered_for_the_inner_service() {
+    let mut service = BufferBodyLayer::new(NonZeroUsize::new(16)).layer(Uppercase);
+    let response = block_on(service.call(post("hello"))).unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.into_body(), b"HELLO");
+}
+
+#[test]
+fn test_body_beyond_the_limit_is_rejected() {
+    let mut service = BufferBodyLayer::new(NonZeroUsize::new(4)).layer(Uppercase);
+    let response = block_on(service.call(post("hello"))).unwrap();
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    assert!(response.into_body().is_empty());
+}
+
+#[test]
+fn test_no_limit() {
+    let mut service = BufferBodyLayer::new(None).layer(Uppercase);
+    let body = "x".repeat(100_000);
+    let response = block_on(service.call(post(&body))).unwrap();
+    assert_eq!(response.into_body().len(), body.len());
+}
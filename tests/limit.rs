@@ -0,0 +1,40 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::num::NonZeroUsize;
+
+use spare_buffer::SpareBuffer;
+
+#[test]
+fn test_remaining_without_limit() {
+    let mut vec = b"abc".to_vec();
+    let buffer = SpareBuffer::from(&mut vec, None);
+    assert_eq!(buffer.remaining(), None);
+    assert!(!buffer.is_full());
+}
+
+#[test]
+fn test_remaining_with_limit() {
+    let mut vec = b"abc".to_vec();
+    let mut buffer = SpareBuffer::from(&mut vec, NonZeroUsize::new(5));
+    assert_eq!(buffer.remaining(), Some(2));
+    buffer.set_limit(NonZeroUsize::new(3)).unwrap();
+    assert_eq!(buffer.remaining(), Some(0));
+    assert!(buffer.is_full());
+    assert!(buffer.set_limit(NonZeroUsize::new(2)).is_err());
+    buffer.clear_limit();
+    assert_eq!(buffer.limit(), None);
+    assert_eq!(buffer.remaining(), None);
+}
+
+#[cfg(feature = "sysinfo")]
+#[test]
+fn test_remaining_with_memory_limit_only() {
+    use spare_buffer::MemoryLimit;
+
+    let mut vec = Vec::<u8>::new();
+    let buffer = SpareBuffer::from(&mut vec, None).with_memory_limit(MemoryLimit::new(50).with_cap(NonZeroUsize::new(4096)));
+    assert_eq!(buffer.limit(), None);
+    assert_eq!(buffer.remaining(), Some(4096));
+}
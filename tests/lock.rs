@@ -0,0 +1,42 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+#![cfg(all(any(unix, windows), not(miri)))]
+
+use spare_buffer::SpareBuffer;
+
+mod common;
+use common::nz;
+
+#[test]
+fn test_lock_and_unlock() {
+    let mut vec = Vec::<u8>::with_capacity(64);
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    assert!(!buffer.is_memory_locked());
+    buffer.lock_memory().unwrap();
+    assert!(buffer.is_memory_locked());
+    buffer.lock_memory().unwrap();
+    buffer.unlock_memory();
+    assert!(!buffer.is_memory_locked());
+    buffer.unlock_memory();
+}
+
+#[test]
+fn test_lock_follows_the_storage() {
+    let mut buffer = SpareBuffer::owned(Vec::with_capacity(16), None);
+    buffer.lock_memory().unwrap();
+    buffer.read_exact_into(&mut &[7u8; 4096][..], nz(4096)).unwrap();
+    assert!(buffer.capacity() >= 4096);
+    assert!(buffer.is_memory_locked());
+    assert_eq!(buffer.data(), &[7u8; 4096][..]);
+}
+
+#[test]
+fn test_empty_storage_can_be_locked() {
+    let mut buffer = SpareBuffer::owned(Vec::<u8>::new(), None);
+    buffer.lock_memory().unwrap();
+    assert!(buffer.is_memory_locked());
+    buffer.fill_from(&mut &b"secret"[..], nz(4)).unwrap();
+    assert_eq!(buffer.data(), b"secret");
+}
@@ -0,0 +1,54 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+#![cfg(all(feature = "sysinfo", not(miri)))]
+
+use std::io::ErrorKind;
+use std::time::Duration;
+
+use spare_buffer::{MemoryLimit, SpareBuffer};
+
+mod common;
+use common::nz;
+
+#[test]
+fn test_settings() {
+    let limit = MemoryLimit::new(25).with_cap(Some(nz(1 << 30))).with_interval(Duration::from_millis(10));
+    assert_eq!(limit.percent(), 25);
+    assert_eq!(limit.cap(), Some(nz(1 << 30)));
+    assert_eq!(limit.interval(), Duration::from_millis(10));
+    assert_eq!(limit.current_bytes(), None);
+}
+
+#[test]
+fn test_limit_is_evaluated_when_installed() {
+    let mut vec = Vec::<u8>::new();
+    let buffer = SpareBuffer::from(&mut vec, None).with_memory_limit(MemoryLimit::new(50).with_cap(Some(nz(4096))));
+    let bytes = buffer.memory_limit().unwrap().current_bytes().unwrap();
+    assert!(bytes > 0 && bytes <= 4096);
+}
+
+#[test]
+fn test_cap_limits_the_length_in_elements() {
+    let mut vec = Vec::<u32>::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None).with_memory_limit(MemoryLimit::new(100).with_cap(Some(nz(16))));
+    assert_eq!(buffer.concat_into(&[[1, 2, 3, 4]]).unwrap(), 4);
+    let error = buffer.concat_into(&[[5]]).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::OutOfMemory);
+    assert_eq!(buffer.data(), &[1, 2, 3, 4]);
+}
+
+#[test]
+fn test_fixed_limit_still_applies() {
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, Some(nz(3))).with_memory_limit(MemoryLimit::new(100).with_cap(Some(nz(16))));
+    assert_eq!(buffer.fill_from(&mut &b"abcdef"[..], nz(4)).unwrap(), 3);
+    assert_eq!(buffer.data(), b"abc");
+}
+
+#[test]
+#[should_panic(expected = "The percentage must be in the range from 1 to 100!")]
+fn test_invalid_percentage_panics() {
+    MemoryLimit::new(101);
+}
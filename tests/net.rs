@@ -0,0 +1,56 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+#![cfg(not(miri))]
+
+use std::io::ErrorKind;
+use std::net::UdpSocket;
+use std::num::NonZeroUsize;
+
+use spare_buffer::SpareBuffer;
+
+/// Returns a receiving and a sending socket on the loopback interface.
+fn sockets() -> (UdpSocket, UdpSocket) {
+    let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let sender = UdpSocket::bind("127.0.0.1:0").unwrap();
+    sender.connect(receiver.local_addr().unwrap()).unwrap();
+    (receiver, sender)
+}
+
+#[test]
+fn test_datagrams_are_appended() {
+    let (receiver, sender) = sockets();
+    sender.send(b"first").unwrap();
+    sender.send(b"second").unwrap();
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let (count, peer) = buffer.recv_from_into(&receiver).unwrap();
+    assert_eq!((count, peer), (5, sender.local_addr().unwrap()));
+    assert_eq!(buffer.recv_from_into(&receiver).unwrap().0, 6);
+    assert_eq!(buffer.data(), b"firstsecond");
+}
+
+#[test]
+fn test_datagram_is_truncated_to_the_limit() {
+    let (receiver, sender) = sockets();
+    sender.send(b"truncated").unwrap();
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, NonZeroUsize::new(5));
+    assert_eq!(buffer.recv_from_into(&receiver).unwrap().0, 5);
+    assert_eq!(buffer.data(), b"trunc");
+    let error = buffer.recv_from_into(&receiver).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::OutOfMemory);
+}
+
+#[test]
+fn test_socket_error_commits_nothing() {
+    let (receiver, _sender) = sockets();
+    receiver.set_nonblocking(true).unwrap();
+    let mut vec = b"ab".to_vec();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let error = buffer.recv_from_into(&receiver).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::WouldBlock);
+    assert!(!buffer.discard_spare());
+    assert_eq!(buffer.data(), b"ab");
+}
@@ -0,0 +1,90 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::ErrorKind;
+use std::num::NonZeroUsize;
+
+use spare_buffer::{PcmReader, SpareBuffer};
+
+mod common;
+use common::{nz, ChunkedReader};
+
+fn samples(values: &[u16]) -> Vec<u8> {
+    values.iter().flat_map(|value| value.to_ne_bytes()).collect()
+}
+
+#[test]
+fn test_partial_samples_are_carried_over() {
+    let values: Vec<u16> = (0..100).collect();
+    let mut reader = PcmReader::<_, u16>::new(ChunkedReader::new(&samples(&values), 3));
+    let mut vec: Vec<u16> = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    while reader.read_into(&mut buffer, nz(16)).unwrap() > 0 {
+        assert!(reader.pending_len() < 2);
+    }
+    assert_eq!(reader.pending_len(), 0);
+    drop(buffer);
+    assert_eq!(vec, values);
+}
+
+#[test]
+fn test_incomplete_sample_at_eof() {
+    let mut data = samples(&[1, 2]);
+    data.push(0xFF);
+    let mut reader = PcmReader::<_, u16>::new(ChunkedReader::new(&data, 5));
+    let mut vec: Vec<u16> = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    assert_eq!(reader.read_into(&mut buffer, nz(16)).unwrap(), 2);
+    assert_eq!(reader.pending_len(), 1);
+    let error = reader.read_into(&mut buffer, nz(16)).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+    assert_eq!(buffer.data(), &[1, 2]);
+}
+
+#[test]
+fn test_limit_clamps_the_read() {
+    let values: Vec<u16> = (0..10).collect();
+    let mut reader = PcmReader::<_, u16>::new(ChunkedReader::new(&samples(&values), 64));
+    let mut vec: Vec<u16> = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, NonZeroUsize::new(4));
+    assert_eq!(reader.read_into(&mut buffer, nz(16)).unwrap(), 4);
+    assert_eq!(reader.pending_len(), 0);
+    let error = reader.read_into(&mut buffer, nz(16)).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::OutOfMemory);
+    assert_eq!(buffer.data(), &[0, 1, 2, 3]);
+    assert_eq!(reader.get_ref().position, 8);
+}
+
+#[test]
+fn test_reader_error_keeps_the_pending_sample() {
+    let mut data = samples(&[7]);
+    data.push(0xAB);
+    let mut source = ChunkedReader::new(&data, 3);
+    source.error = Some(ErrorKind::BrokenPipe);
+    let mut reader = PcmReader::<_, u16>::new(source);
+    let mut vec: Vec<u16> = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    assert_eq!(reader.read_into(&mut buffer, nz(16)).unwrap(), 1);
+    assert_eq!(reader.pending_len(), 1);
+    let error = reader.read_into(&mut buffer, nz(16)).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::BrokenPipe);
+    assert_eq!(reader.pending_len(), 1);
+    assert_eq!(buffer.data(), &[7]);
+}
+
+#[test]
+fn test_failed_commit_keeps_the_pending_sample() {
+    let mut data = samples(&[1]);
+    data.push(0x01);
+    data.extend_from_slice(&[0x02, 0x03, 0x04]);
+    let mut reader = PcmReader::<_, u16>::new(ChunkedReader::new(&data, 3));
+    let mut vec: Vec<u16> = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None).with_dynamic_limit(|length: usize, _: usize| length < 1);
+    assert_eq!(reader.read_into(&mut buffer, nz(16)).unwrap(), 1);
+    assert_eq!(reader.pending_len(), 1);
+    let error = reader.read_into(&mut buffer, nz(16)).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::OutOfMemory);
+    assert_eq!(reader.pending_len(), 1);
+    assert_eq!(buffer.data(), &[1]);
+}
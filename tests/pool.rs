@@ -0,0 +1,135 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::Write;
+use std::num::NonZeroUsize;
+use std::pin::pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::task::{Poll, Waker};
+use std::time::Duration;
+
+use spare_buffer::{SparePool, SpareVec};
+
+mod common;
+use common::{block_on, poll_once, CountingWaker};
+
+/// Gets a buffer from the `pool`, and writes `data` into it.
+fn filled(pool: &SparePool<u8>, data: &[u8]) -> SpareVec<u8> {
+    let mut buffer = pool.get(None);
+    buffer.write_all(data).unwrap();
+    buffer
+}
+
+#[test]
+fn test_capacity_is_preserved() {
+    let pool = SparePool::<u8>::new(2);
+    let mut buffer = pool.get(None);
+    buffer.write_all(&[1u8; 100]).unwrap();
+    let capacity = buffer.capacity();
+    pool.put(buffer);
+    assert_eq!(pool.len(), 1);
+    let buffer = pool.get(None);
+    assert!(buffer.is_empty());
+    assert_eq!(buffer.capacity(), capacity);
+    assert!(pool.is_empty());
+}
+
+#[test]
+fn test_full_pool_frees_vectors() {
+    let pool = SparePool::<u8>::new(1);
+    let buffers: Vec<_> = (0..3).map(|_| filled(&pool, b"data")).collect();
+    for buffer in buffers {
+        pool.put(buffer);
+    }
+    assert_eq!(pool.len(), 1);
+    pool.put(pool.get(None));
+    assert_eq!(pool.len(), 1);
+}
+
+#[test]
+fn test_oversized_vectors_are_shrunk() {
+    let pool = SparePool::<u8>::new(2).with_max_capacity(NonZeroUsize::new(64).unwrap());
+    assert_eq!(pool.max_capacity(), NonZeroUsize::new(64));
+    let mut buffer = pool.get(None);
+    buffer.write_all(&[0u8; 4096]).unwrap();
+    pool.put(buffer);
+    let buffer = pool.get(None);
+    assert!(buffer.capacity() >= 64);
+    assert!(buffer.capacity() < 4096);
+}
+
+#[test]
+fn test_trim_frees_idle_vectors() {
+    let pool = SparePool::<u8>::new(4);
+    let first = filled(&pool, b"first");
+    let second = filled(&pool, b"second");
+    pool.put(first);
+    pool.put(second);
+    assert_eq!(pool.trim(Duration::from_secs(3600)), 0);
+    assert_eq!(pool.len(), 2);
+    assert_eq!(pool.trim(Duration::ZERO), 2);
+    assert!(pool.is_empty());
+}
+
+#[test]
+fn test_acquire_waits_for_a_returned_buffer() {
+    let pool = SparePool::<u8>::new(2).with_max_outstanding(NonZeroUsize::new(1).unwrap());
+    let counter = Arc::new(CountingWaker(AtomicUsize::new(0)));
+    let waker = Waker::from(counter.clone());
+    let mut first = pool.try_acquire(None).unwrap();
+    first.write_all(b"first").unwrap();
+    assert!(pool.try_acquire(None).is_none());
+    let mut acquire = pin!(pool.acquire(None));
+    assert!(poll_once(acquire.as_mut(), &waker).is_pending());
+    assert!(poll_once(acquire.as_mut(), &waker).is_pending());
+    drop(first);
+    assert!(counter.0.load(Ordering::SeqCst) > 0);
+    match poll_once(acquire.as_mut(), &waker) {
+        Poll::Ready(second) => assert!(second.is_empty() && second.capacity() >= 5),
+        Poll::Pending => panic!("No buffer acquired!"),
+    }
+    assert!(pool.try_acquire(None).is_some());
+}
+
+#[test]
+fn test_acquire_without_a_maximum_never_waits() {
+    let pool = SparePool::<u8>::new(2);
+    let waker = Waker::from(Arc::new(CountingWaker(AtomicUsize::new(0))));
+    let mut buffers: Vec<_> = (0..4).map(|_| pool.try_acquire(None).unwrap()).collect();
+    assert!(poll_once(pin!(pool.acquire(None)), &waker).is_ready());
+    buffers.iter_mut().for_each(|buffer| buffer.write_all(b"data").unwrap());
+    drop(buffers);
+    assert_eq!(pool.len(), 2);
+}
+
+#[test]
+fn test_into_inner_releases_the_permit() {
+    let pool = SparePool::<u8>::new(2).with_max_outstanding(NonZeroUsize::new(1).unwrap());
+    let mut buffer = pool.try_acquire(NonZeroUsize::new(16)).unwrap().into_inner();
+    assert_eq!(buffer.limit(), NonZeroUsize::new(16));
+    buffer.write_all(b"kept").unwrap();
+    assert!(pool.try_acquire(None).is_some());
+    assert!(pool.is_empty());
+    assert_eq!(buffer.data(), b"kept");
+}
+
+#[test]
+fn test_acquire_from_many_threads() {
+    let pool = SparePool::<u8>::new(4).with_max_outstanding(NonZeroUsize::new(2).unwrap());
+    let outstanding = AtomicUsize::new(0);
+    std::thread::scope(|scope| {
+        for _ in 0..8 {
+            scope.spawn(|| {
+                for _ in 0..50 {
+                    let mut buffer = block_on(pool.acquire(None));
+                    assert!(outstanding.fetch_add(1, Ordering::SeqCst) < 2);
+                    buffer.write_all(b"data").unwrap();
+                    outstanding.fetch_sub(1, Ordering::SeqCst);
+                }
+            });
+        }
+    });
+    assert!(pool.len() <= 2);
+}
@@ -0,0 +1,125 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::ErrorKind;
+use std::num::NonZeroUsize;
+
+use spare_buffer::{GrowthPolicy, SpareBuffer};
+
+#[test]
+fn test_prepend() {
+    let mut vec = b"payload".to_vec();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    buffer.prepend(b"header:").unwrap();
+    buffer.prepend(b"").unwrap();
+    assert_eq!(buffer.data(), b"header:payload");
+}
+
+#[test]
+fn test_prepend_removes_consumed_elements() {
+    let mut vec = b"xxxdata".to_vec();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    buffer.consume(3);
+    buffer.prepend(b"> ").unwrap();
+    assert_eq!(buffer.consumed(), 0);
+    assert_eq!(buffer.data(), b"> data");
+}
+
+#[test]
+fn test_prepend_exceeding_the_limit_restores_the_data() {
+    let mut vec = b"abc".to_vec();
+    let mut buffer = SpareBuffer::from(&mut vec, NonZeroUsize::new(5));
+    let error = buffer.prepend(b"123").unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::OutOfMemory);
+    assert_eq!(buffer.data(), b"abc");
+    buffer.prepend(b"12").unwrap();
+    assert_eq!(buffer.data(), b"12abc");
+}
+
+#[test]
+fn test_prepend_exceeding_the_limit_keeps_the_consumed_elements() {
+    let mut vec = b"xyabc".to_vec();
+    vec.shrink_to_fit();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    buffer.consume(2);
+    buffer.set_limit(NonZeroUsize::new(5)).unwrap();
+    let error = buffer.prepend(b"123").unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::OutOfMemory);
+    assert_eq!(buffer.consumed(), 2);
+    assert_eq!(buffer.data(), b"xyabc");
+    assert_eq!(buffer.capacity(), 5);
+}
+
+#[test]
+fn test_prepend_rejected_by_the_dynamic_limit_fails() {
+    let mut vec = b"xyabc".to_vec();
+    let mut buffer = SpareBuffer::from(&mut vec, None).with_dynamic_limit(|length, additional| length + additional <= 4);
+    buffer.consume(2);
+    let error = buffer.prepend(b"12").unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::OutOfMemory);
+    assert_eq!(buffer.consumed(), 2);
+    assert_eq!(buffer.data(), b"xyabc");
+    buffer.prepend(b"1").unwrap();
+    assert_eq!(buffer.data(), b"1abc");
+}
+
+#[test]
+fn test_prepend_exceeding_the_max_allocation_fails() {
+    let mut vec = b"abc".to_vec();
+    let mut buffer = SpareBuffer::from(&mut vec, None).with_max_allocation(NonZeroUsize::new(2));
+    let error = buffer.prepend(b"123").unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::OutOfMemory);
+    assert_eq!(buffer.data(), b"abc");
+}
+
+#[test]
+fn test_failed_reservation_keeps_the_consumed_elements() {
+    let mut vec = b"xyabc".to_vec();
+    vec.shrink_to_fit();
+    let policy = GrowthPolicy::Increment(NonZeroUsize::new(usize::MAX).unwrap());
+    let mut buffer = SpareBuffer::from(&mut vec, None).with_growth_policy(policy);
+    buffer.consume(2);
+    let error = buffer.prepend(b"1234").unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::OutOfMemory);
+    assert_eq!(buffer.consumed(), 2);
+    assert_eq!(buffer.data(), b"xyabc");
+}
+
+#[cfg(not(feature = "safe-only"))]
+#[test]
+fn test_partial_prepend_moves_the_data_back() {
+    let mut vec = b"data".to_vec();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let count = buffer.prepend_with(NonZeroUsize::new(8).unwrap(), |front| {
+        front[..3].copy_from_slice(b"ab:");
+        3
+    }).unwrap();
+    assert_eq!(count, 3);
+    assert_eq!(buffer.data(), b"ab:data");
+}
+
+#[cfg(not(feature = "safe-only"))]
+#[test]
+fn test_panic_in_prepend_restores_the_data() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let mut vec = b"data".to_vec();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        buffer.prepend_with(NonZeroUsize::new(4).unwrap(), |_| panic!("Injected panic!"))
+    }));
+    assert!(result.is_err());
+    assert_eq!(buffer.data(), b"data");
+    buffer.prepend(b"1").unwrap();
+    assert_eq!(buffer.data(), b"1data");
+}
+
+#[cfg(not(feature = "safe-only"))]
+#[test]
+#[should_panic(expected = "Commit size exceeds available capacity!")]
+fn test_prepend_count_exceeding_the_length_panics() {
+    let mut vec = b"data".to_vec();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let _ = buffer.prepend_with(NonZeroUsize::new(2).unwrap(), |_| 3);
+}
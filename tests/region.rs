@@ -0,0 +1,58 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+#![cfg(not(feature = "safe-only"))]
+
+use std::thread;
+
+use spare_buffer::SpareBuffer;
+
+mod common;
+use common::nz;
+
+#[test]
+fn test_region_is_filled_on_another_thread() {
+    let mut vec = b"ab".to_vec();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let (worker, filled) = buffer.lend_spare(nz(4), |mut region, committed| {
+        assert_eq!(committed, b"ab");
+        assert_eq!(region.len(), 4);
+        thread::spawn(move || {
+            region.as_mut_slice()[..3].copy_from_slice(b"cde");
+            region.complete(3);
+        })
+    }).unwrap();
+    worker.join().unwrap();
+    assert_eq!(filled, 3);
+    assert_eq!(buffer.data(), b"abcde");
+}
+
+#[test]
+fn test_dropped_region_commits_nothing() {
+    let mut vec = b"ab".to_vec();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let ((), filled) = buffer.lend_spare(nz(4), |mut region, _| {
+        region.as_mut_slice().fill(b'x');
+        drop(region);
+    }).unwrap();
+    assert_eq!(filled, 0);
+    assert_eq!(buffer.data(), b"ab");
+}
+
+#[test]
+fn test_commit_beyond_the_limit_fails() {
+    let mut vec = Vec::<u8>::new();
+    let mut buffer = SpareBuffer::from(&mut vec, Some(nz(2)));
+    let error = buffer.lend_spare(nz(4), |region, _| region.complete(3)).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::OutOfMemory);
+    assert!(buffer.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Completed size exceeds the length of the spare buffer!")]
+fn test_complete_beyond_the_region_panics() {
+    let mut vec = Vec::<u8>::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let _ = buffer.lend_spare(nz(4), |region, _| region.complete(5));
+}
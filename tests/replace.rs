@@ -0,0 +1,40 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use spare_buffer::SpareBuffer;
+
+#[test]
+fn test_replace_returns_the_previous_vector() {
+    let mut buffer = SpareBuffer::owned(b"abcdef".to_vec(), None);
+    buffer.consume(2);
+    let previous = buffer.replace(b"stale".to_vec()).unwrap();
+    assert_eq!(previous, b"abcdef");
+    assert!(buffer.is_empty());
+    assert_eq!(buffer.consumed(), 0);
+    assert_eq!(buffer.take(), b"");
+}
+
+#[cfg(any(unix, windows))]
+#[test]
+fn test_replace_moves_the_lock() {
+    let mut buffer = SpareBuffer::owned(Vec::<u8>::with_capacity(64), None);
+    buffer.lock_memory().unwrap();
+    buffer.replace(Vec::with_capacity(128)).unwrap();
+    assert!(buffer.is_memory_locked());
+    assert!(buffer.capacity() >= 128);
+    buffer.take();
+    assert!(buffer.is_memory_locked());
+    buffer.unlock_memory();
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn test_split_to_keeps_the_remainder() {
+    let mut buffer = SpareBuffer::owned(b"headbody".to_vec(), None);
+    buffer.consume(1);
+    let front = buffer.split_to(4).unwrap();
+    assert_eq!(&front[..], b"head");
+    assert_eq!(buffer.data(), b"body");
+    assert_eq!(buffer.consumed(), 0);
+}
@@ -0,0 +1,64 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::ErrorKind;
+use std::num::NonZeroUsize;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use spare_buffer::SpareBuffer;
+
+/// Returns `true`, if a "spare" buffer is still allocated, i.e. if a commit
+/// of nothing succeeds.
+fn has_spare(buffer: &mut SpareBuffer<u8>) -> bool {
+    catch_unwind(AssertUnwindSafe(|| buffer.commit(0))).is_ok()
+}
+
+#[test]
+fn test_read_bulk_string() {
+    let mut vec = b"+".to_vec();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    buffer.read_bulk_string(&mut &b"hello\r\n"[..], 5).unwrap();
+    buffer.read_bulk_string(&mut &b"\r\n"[..], 0).unwrap();
+    assert_eq!(buffer.data(), b"+hello");
+}
+
+#[test]
+fn test_declared_length_exceeding_the_limit_fails() {
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, NonZeroUsize::new(4));
+    let error = buffer.read_bulk_string(&mut &b"hello\r\n"[..], 5).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::OutOfMemory);
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_short_payload_discards_the_spare_buffer() {
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let error = buffer.read_bulk_string(&mut &b"hel"[..], 5).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+    assert!(buffer.is_empty());
+    assert!(!has_spare(&mut buffer));
+}
+
+#[test]
+fn test_missing_terminator_discards_the_spare_buffer() {
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let error = buffer.read_bulk_string(&mut &b"hello\r"[..], 5).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+    assert!(!has_spare(&mut buffer));
+}
+
+#[test]
+fn test_invalid_terminator_discards_the_spare_buffer() {
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let error = buffer.read_bulk_string(&mut &b"hello\n\n"[..], 5).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    assert!(buffer.is_empty());
+    assert!(!has_spare(&mut buffer));
+    buffer.read_bulk_string(&mut &b"world\r\n"[..], 5).unwrap();
+    assert_eq!(buffer.data(), b"world");
+}
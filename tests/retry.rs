@@ -0,0 +1,98 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::{Read, Result as IoResult, Error as IoError, ErrorKind};
+use std::time::Duration;
+
+use spare_buffer::{RetryPolicy, SpareBuffer, WouldBlockPolicy};
+
+mod common;
+use common::nz;
+
+/// Fails `failures` times with the given kind of error, then reads `data`.
+struct FlakyReader {
+    data: &'static [u8],
+    kind: ErrorKind,
+    failures: usize,
+    attempts: usize,
+}
+
+impl FlakyReader {
+    fn new(data: &'static [u8], kind: ErrorKind, failures: usize) -> Self {
+        Self { data, kind, failures, attempts: 0 }
+    }
+}
+
+impl Read for FlakyReader {
+    fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
+        self.attempts += 1;
+        if self.failures > 0 {
+            self.failures -= 1;
+            return Err(IoError::new(self.kind, "Injected error!"));
+        }
+        self.data.read(buf)
+    }
+}
+
+fn fill(policy: RetryPolicy, reader: &mut FlakyReader) -> IoResult<Vec<u8>> {
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None).with_retry_policy(policy);
+    buffer.fill_from(reader, nz(4))?;
+    Ok(vec)
+}
+
+#[test]
+fn test_default_policy() {
+    let policy = RetryPolicy::default();
+    assert_eq!(policy, RetryPolicy::new());
+    assert!(policy.retries_interrupted());
+    assert_eq!(policy.would_block(), WouldBlockPolicy::Fail);
+    assert_eq!(policy.attempts(), None);
+    let mut reader = FlakyReader::new(b"abc", ErrorKind::Interrupted, 3);
+    assert_eq!(fill(policy, &mut reader).unwrap(), b"abc");
+    let mut reader = FlakyReader::new(b"abc", ErrorKind::WouldBlock, 1);
+    assert_eq!(fill(policy, &mut reader).unwrap_err().kind(), ErrorKind::WouldBlock);
+    assert_eq!(reader.attempts, 1);
+}
+
+#[test]
+fn test_never_retries() {
+    let policy = RetryPolicy::never();
+    assert!(!policy.retries_interrupted());
+    let mut reader = FlakyReader::new(b"abc", ErrorKind::Interrupted, 1);
+    assert_eq!(fill(policy, &mut reader).unwrap_err().kind(), ErrorKind::Interrupted);
+    assert_eq!(reader.attempts, 1);
+}
+
+#[test]
+fn test_would_block_is_retried() {
+    let spin = RetryPolicy::new().on_would_block(WouldBlockPolicy::Spin);
+    let mut reader = FlakyReader::new(b"abc", ErrorKind::WouldBlock, 2);
+    assert_eq!(fill(spin, &mut reader).unwrap(), b"abc");
+    let backoff = RetryPolicy::new().on_would_block(WouldBlockPolicy::Backoff {
+        initial: Duration::from_micros(1),
+        max: Duration::from_micros(2),
+    });
+    let mut reader = FlakyReader::new(b"abc", ErrorKind::WouldBlock, 3);
+    assert_eq!(fill(backoff, &mut reader).unwrap(), b"abc");
+}
+
+#[test]
+fn test_max_attempts() {
+    let policy = RetryPolicy::new().max_attempts(Some(nz(3)));
+    assert_eq!(policy.attempts(), Some(nz(3)));
+    let mut reader = FlakyReader::new(b"abc", ErrorKind::Interrupted, 2);
+    assert_eq!(fill(policy, &mut reader).unwrap(), b"abc");
+    let mut reader = FlakyReader::new(b"abc", ErrorKind::Interrupted, 3);
+    assert_eq!(fill(policy, &mut reader).unwrap_err().kind(), ErrorKind::Interrupted);
+    assert_eq!(reader.attempts, 3);
+}
+
+#[test]
+fn test_other_errors_are_passed_through() {
+    let policy = RetryPolicy::new().on_would_block(WouldBlockPolicy::Spin);
+    let mut reader = FlakyReader::new(b"abc", ErrorKind::ConnectionReset, 1);
+    assert_eq!(fill(policy, &mut reader).unwrap_err().kind(), ErrorKind::ConnectionReset);
+    assert_eq!(reader.attempts, 1);
+}
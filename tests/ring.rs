@@ -0,0 +1,115 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::{BufRead, Read, ErrorKind};
+use std::num::NonZeroUsize;
+
+use spare_buffer::SpareRing;
+
+mod common;
+use common::{check_reader_error_commits_nothing, FailingReader};
+
+fn ring(capacity: usize) -> SpareRing<u8> {
+    SpareRing::new(NonZeroUsize::new(capacity).unwrap())
+}
+
+#[test]
+fn test_read_and_consume() {
+    let mut ring = ring(8);
+    assert_eq!(ring.read_from(&mut &b"hello"[..]).unwrap(), 5);
+    assert_eq!(ring.len(), 5);
+    assert_eq!(ring.free(), 3);
+    assert_eq!(ring.data(), (&b"hello"[..], &b""[..]));
+    ring.consume(2);
+    assert_eq!(ring.data(), (&b"llo"[..], &b""[..]));
+}
+
+#[test]
+fn test_wraparound() {
+    let mut ring = ring(8);
+    ring.read_from(&mut &b"abcdef"[..]).unwrap();
+    ring.consume(4);
+    assert_eq!(ring.read_from(&mut &b"ghij"[..]).unwrap(), 2);
+    assert_eq!(ring.read_from(&mut &b"ij"[..]).unwrap(), 2);
+    assert_eq!(ring.data(), (&b"efgh"[..], &b"ij"[..]));
+    let mut output = Vec::new();
+    ring.read_to_end(&mut output).unwrap();
+    assert_eq!(output, b"efghij");
+    assert!(ring.is_empty());
+}
+
+#[test]
+fn test_bufread_returns_the_contiguous_part() {
+    let mut ring = ring(4);
+    ring.read_from(&mut &b"abc"[..]).unwrap();
+    ring.consume(2);
+    assert_eq!(ring.read_from(&mut &b"de"[..]).unwrap(), 1);
+    assert_eq!(ring.read_from(&mut &b"ef"[..]).unwrap(), 2);
+    assert_eq!(ring.fill_buf().unwrap(), b"cd");
+    BufRead::consume(&mut ring, 2);
+    assert_eq!(ring.fill_buf().unwrap(), b"ef");
+}
+
+#[test]
+fn test_full_ring() {
+    let mut ring = ring(4);
+    assert_eq!(ring.read_from(&mut &b"abcdef"[..]).unwrap(), 4);
+    assert!(ring.is_full());
+    let error = ring.read_from(&mut &b"gh"[..]).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::OutOfMemory);
+    assert_eq!(ring.data(), (&b"abcd"[..], &b""[..]));
+}
+
+#[test]
+fn test_reader_error_commits_nothing() {
+    let mut ring = ring(4);
+    check_reader_error_commits_nothing(&mut ring, |ring, reader| ring.read_from(reader), |ring| [ring.data().0, ring.data().1].concat());
+}
+
+#[test]
+#[should_panic(expected = "No spare buffer allocated!")]
+fn test_reader_error_discards_the_spare_buffer() {
+    let mut ring = ring(4);
+    assert!(ring.read_from(&mut FailingReader(ErrorKind::BrokenPipe)).is_err());
+    ring.commit(0);
+}
+
+#[test]
+fn test_clear_resets_the_head() {
+    let mut ring = ring(4);
+    ring.read_from(&mut &b"abc"[..]).unwrap();
+    ring.clear();
+    assert!(ring.is_empty());
+    assert_eq!(ring.read_from(&mut &b"wxyz"[..]).unwrap(), 4);
+    assert_eq!(ring.data(), (&b"wxyz"[..], &b""[..]));
+}
+
+#[cfg(not(feature = "safe-only"))]
+#[test]
+fn test_spare_buffer_wraps_around() {
+    let mut ring = ring(6);
+    ring.read_from(&mut &b"abcd"[..]).unwrap();
+    ring.consume(3);
+    let (first, second) = ring.allocate_spare();
+    assert_eq!((first.len(), second.len()), (2, 3));
+    first.copy_from_slice(b"ef");
+    second[..2].copy_from_slice(b"gh");
+    ring.commit(4);
+    assert_eq!(ring.data(), (&b"def"[..], &b"gh"[..]));
+}
+
+#[test]
+#[should_panic(expected = "No spare buffer allocated!")]
+fn test_commit_without_spare_panics() {
+    ring(4).commit(1);
+}
+
+#[cfg(not(feature = "safe-only"))]
+#[test]
+#[should_panic(expected = "Commit size exceeds available capacity!")]
+fn test_commit_exceeding_free_space_panics() {
+    let mut ring = ring(4);
+    ring.allocate_spare();
+    ring.commit(5);
+}
@@ -0,0 +1,111 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::{BufRead, Read, ErrorKind};
+use std::num::NonZeroUsize;
+
+use spare_buffer::SpareRope;
+
+mod common;
+use common::{check_reader_error_commits_nothing, nz, FailingReader};
+
+fn fill(rope: &mut SpareRope<u8>, data: &[u8], chunk: usize) {
+    let mut source = data;
+    while rope.read_from(&mut source, nz(chunk)).unwrap() > 0 {}
+}
+
+#[test]
+fn test_segments_are_never_moved() {
+    let mut rope = SpareRope::new(nz(8), None);
+    rope.read_from(&mut &b"abcdefgh"[..], nz(8)).unwrap();
+    let first = rope.segments().next().unwrap().as_ptr();
+    fill(&mut rope, &[b'x'; 100], 5);
+    assert_eq!(rope.segments().next().unwrap().as_ptr(), first);
+    assert_eq!(rope.len(), 108);
+    assert!(rope.segments().all(|segment| !segment.is_empty()));
+    assert_eq!(rope.iter().count(), 108);
+}
+
+#[test]
+fn test_large_allocation_opens_a_larger_segment() {
+    let mut rope = SpareRope::new(nz(4), None);
+    rope.read_from(&mut &b"ab"[..], nz(2)).unwrap();
+    rope.read_from(&mut &b"0123456789"[..], nz(10)).unwrap();
+    let lengths: Vec<usize> = rope.segments().map(<[u8]>::len).collect();
+    assert_eq!(lengths, [2, 10]);
+}
+
+#[test]
+fn test_into_contiguous() {
+    let mut rope = SpareRope::new(nz(3), None);
+    fill(&mut rope, b"hello world", 2);
+    assert!(rope.segments().count() > 1);
+    assert_eq!(rope.into_contiguous(), b"hello world");
+
+    let mut rope = SpareRope::new(nz(64), None);
+    fill(&mut rope, b"single", 4);
+    let ptr = rope.segments().next().unwrap().as_ptr();
+    let vec = rope.into_contiguous();
+    assert_eq!(vec, b"single");
+    assert_eq!(vec.as_ptr(), ptr);
+
+    assert!(SpareRope::<u8>::new(nz(4), None).into_contiguous().is_empty());
+}
+
+#[test]
+fn test_reader_spans_all_segments() {
+    let mut rope = SpareRope::new(nz(4), None);
+    fill(&mut rope, b"the quick brown fox", 3);
+    let mut output = String::new();
+    rope.reader().read_to_string(&mut output).unwrap();
+    assert_eq!(output, "the quick brown fox");
+    let mut reader = rope.reader();
+    assert_eq!(reader.fill_buf().unwrap(), b"the");
+    reader.consume(10);
+    assert_eq!(reader.fill_buf().unwrap(), b" qu");
+}
+
+#[test]
+fn test_limit() {
+    let mut rope = SpareRope::new(nz(4), NonZeroUsize::new(6));
+    fill(&mut rope, b"abcd", 4);
+    let error = rope.read_from(&mut &b"efgh"[..], nz(4)).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::OutOfMemory);
+    assert_eq!(rope.len(), 4);
+    assert_eq!(rope.read_from(&mut &b"ef"[..], nz(2)).unwrap(), 2);
+    assert_eq!(rope.into_contiguous(), b"abcdef");
+}
+
+#[test]
+fn test_reader_error_commits_nothing() {
+    let mut rope = SpareRope::new(nz(4), None);
+    check_reader_error_commits_nothing(&mut rope, |rope, reader| rope.read_from(reader, nz(4)), |rope| {
+        let mut data = Vec::new();
+        rope.reader().read_to_end(&mut data).unwrap();
+        data
+    });
+}
+
+#[test]
+#[should_panic(expected = "No spare buffer allocated!")]
+fn test_reader_error_discards_the_spare_buffer() {
+    let mut rope = SpareRope::<u8>::new(nz(4), None);
+    assert!(rope.read_from(&mut FailingReader(ErrorKind::BrokenPipe), nz(4)).is_err());
+    let _ = rope.commit(0);
+}
+
+#[cfg(not(feature = "safe-only"))]
+#[test]
+#[should_panic(expected = "Commit size exceeds available capacity!")]
+fn test_commit_exceeding_the_segment_panics() {
+    let mut rope = SpareRope::<u8>::new(nz(4), None);
+    rope.allocate_spare(nz(4));
+    let _ = rope.commit(5);
+}
+
+#[test]
+#[should_panic(expected = "No spare buffer allocated!")]
+fn test_commit_without_spare_panics() {
+    let _ = SpareRope::<u8>::new(nz(4), None).commit(1);
+}
@@ -0,0 +1,71 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::ErrorKind;
+use std::num::NonZeroUsize;
+
+use spare_buffer::SpareBuffer;
+
+mod common;
+use common::nz;
+
+#[test]
+fn test_decode_rows_checks_the_limit_up_front() {
+    let mut vec = b"ab".to_vec();
+    let mut buffer = SpareBuffer::from(&mut vec, NonZeroUsize::new(8));
+    let error = buffer.decode_rows(nz(3), 3).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::OutOfMemory);
+    let error = buffer.decode_rows(nz(usize::MAX), 2).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::OutOfMemory);
+    let decoder = buffer.decode_rows(nz(3), 2).unwrap();
+    assert_eq!((decoder.row_len(), decoder.rows(), decoder.rows_decoded()), (nz(3), 2, 0));
+    assert!(decoder.decoded().is_empty());
+}
+
+#[test]
+fn test_empty_image_is_complete() {
+    let mut vec = Vec::<u8>::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    assert!(buffer.decode_rows(nz(4), 0).unwrap().is_complete());
+}
+
+#[cfg(not(feature = "safe-only"))]
+#[test]
+fn test_rows_are_committed_one_by_one() {
+    let mut vec = b"ab".to_vec();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let mut decoder = buffer.decode_rows(nz(2), 2).unwrap();
+    decoder.next_row().unwrap().copy_from_slice(b"cd");
+    assert_eq!(decoder.commit_row().unwrap(), b"cd");
+    assert_eq!(decoder.decoded(), b"cd");
+    decoder.next_row().unwrap().copy_from_slice(b"ef");
+    assert_eq!(decoder.commit_row().unwrap(), b"ef");
+    assert!(decoder.is_complete());
+    assert!(decoder.next_row().is_none());
+    assert_eq!(decoder.decoded(), b"cdef");
+    assert_eq!(buffer.data(), b"abcdef");
+}
+
+#[cfg(feature = "png")]
+#[test]
+fn test_decode_png() {
+    let pixels: Vec<u8> = (0..12).collect();
+    let mut image = Vec::new();
+    let mut encoder = png::Encoder::new(&mut image, 4, 3);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().unwrap();
+    writer.write_image_data(&pixels).unwrap();
+    writer.finish().unwrap();
+
+    let mut reader = png::Decoder::new(std::io::Cursor::new(image)).read_info().unwrap();
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let mut decoder = buffer.decode_png(&reader).unwrap();
+    assert_eq!((decoder.row_len(), decoder.rows()), (nz(4), 3));
+    assert_eq!(decoder.read_png_row(&mut reader).unwrap(), Some(&pixels[..4]));
+    while decoder.read_png_row(&mut reader).unwrap().is_some() {}
+    assert!(decoder.is_complete());
+    assert_eq!(buffer.data(), pixels);
+}
@@ -0,0 +1,100 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+#![cfg(feature = "safe-only")]
+
+//! With the **`safe-only`** feature, a safe `Read` implementation may read the
+//! slice that it is handed, before writing it. Run under Miri, in order to
+//! detect reads of uninitialized memory:
+//!
+//! `cargo +nightly miri test --features safe-only --test safe_only`
+
+use std::io::{Read, Result as IoResult};
+
+use spare_buffer::{SpareArray, SpareBuffer, SpareGap, SpareRing, SpareRope, SpareSlice, SpareString};
+
+mod common;
+use common::nz;
+
+/// Reads the *whole* slice that it is handed, which must be zero-filled, and
+/// only then writes the data.
+struct SnoopingReader<'a>(&'a [u8]);
+
+impl Read for SnoopingReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
+        assert!(buf.iter().all(|byte| *byte == 0), "The slice is not zero-filled!");
+        self.0.read(buf)
+    }
+}
+
+#[test]
+fn test_fill_from() {
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    assert_eq!(buffer.fill_from(&mut SnoopingReader(b"0123456789"), nz(4)).unwrap(), 10);
+    assert_eq!(buffer.data(), b"0123456789");
+}
+
+#[test]
+fn test_read_all_from() {
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    assert_eq!(buffer.read_all_from(&mut SnoopingReader(b"abc")).unwrap(), 3);
+    assert_eq!(buffer.data(), b"abc");
+}
+
+#[test]
+fn test_read_exact_into() {
+    let mut vec = Vec::with_capacity(64);
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    buffer.read_exact_into(&mut SnoopingReader(b"abcd"), nz(4)).unwrap();
+    assert_eq!(buffer.data(), b"abcd");
+}
+
+#[test]
+fn test_read_vectored_from() {
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    assert_eq!(buffer.read_vectored_from(&mut SnoopingReader(b"abcdef"), nz(2), nz(2)).unwrap(), 2);
+    assert_eq!(buffer.data(), b"ab");
+}
+
+#[test]
+fn test_fixed_capacity_buffers() {
+    let mut array = SpareArray::<u8, 8>::new();
+    assert_eq!(array.read_from(&mut SnoopingReader(b"abc"), nz(4)).unwrap(), 3);
+    assert_eq!(array.data(), b"abc");
+    let mut memory = vec![std::mem::MaybeUninit::uninit(); 8];
+    let mut slice = SpareSlice::from(&mut memory);
+    assert_eq!(slice.read_from(&mut SnoopingReader(b"abc"), nz(4)).unwrap(), 3);
+    assert_eq!(slice.data(), b"abc");
+}
+
+#[test]
+fn test_ring_rope_and_gap() {
+    let mut ring = SpareRing::<u8>::new(nz(8));
+    assert_eq!(ring.read_from(&mut SnoopingReader(b"abc")).unwrap(), 3);
+    let mut rope = SpareRope::<u8>::new(nz(8), None);
+    assert_eq!(rope.read_from(&mut SnoopingReader(b"abc"), nz(4)).unwrap(), 3);
+    let mut gap = SpareGap::<u8>::with_capacity(8);
+    assert_eq!(gap.read_from(&mut SnoopingReader(b"abc"), nz(4)).unwrap(), 3);
+}
+
+#[test]
+fn test_string() {
+    let mut string = String::new();
+    let mut spare = SpareString::from(&mut string, None);
+    assert_eq!(spare.read_from(&mut SnoopingReader(b"abc"), nz(4)).unwrap(), 3);
+    assert_eq!(string, "abc");
+}
+
+#[test]
+#[cfg_attr(miri, ignore = "Miri isolates the file system")]
+fn test_read_file() {
+    let path = std::env::temp_dir().join(format!("spare_buffer_safe_only_{}", std::process::id()));
+    std::fs::write(&path, b"file contents").unwrap();
+    let data = spare_buffer::read_file(&path, None);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(data.unwrap(), b"file contents");
+}
@@ -0,0 +1,54 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+
+use spare_buffer::{ShrinkPolicy, SpareBuffer};
+
+mod common;
+use common::nz;
+
+fn append(buffer: &mut SpareBuffer<u8>, data: &[u8]) {
+    let count = buffer.commit_written(nz(data.len()), |spare| spare[..data.len()].write_copy_of_slice(data)).unwrap();
+    assert_eq!(count, data.len());
+}
+
+fn policy() -> ShrinkPolicy {
+    ShrinkPolicy::new(16, nz(256), nz(2))
+}
+
+#[test]
+fn test_idle_commits_shrink_the_capacity() {
+    let mut vec: Vec<u8> = Vec::with_capacity(4096);
+    let mut buffer = SpareBuffer::from(&mut vec, None).with_shrink_policy(policy());
+    append(&mut buffer, &[1]);
+    assert_eq!(buffer.capacity(), 4096);
+    append(&mut buffer, &[2]);
+    assert!(buffer.capacity() <= 2 + 16 + 16);
+    assert_eq!(buffer.data(), &[1, 2]);
+}
+
+#[test]
+fn test_large_commit_resets_the_idle_count() {
+    let mut vec: Vec<u8> = Vec::with_capacity(4096);
+    let mut buffer = SpareBuffer::from(&mut vec, None).with_shrink_policy(policy());
+    append(&mut buffer, &[1]);
+    append(&mut buffer, &[0; 64]);
+    append(&mut buffer, &[2]);
+    assert_eq!(buffer.capacity(), 4096);
+}
+
+#[cfg(all(unix, not(miri)))]
+#[test]
+fn test_shrinking_locked_memory_commits() {
+    let mut vec: Vec<u8> = Vec::with_capacity(1 << 16);
+    let mut buffer = SpareBuffer::from(&mut vec, None).with_shrink_policy(policy());
+    if buffer.lock_memory().is_err() {
+        return; /* RLIMIT_MEMLOCK */
+    }
+    append(&mut buffer, &[1]);
+    append(&mut buffer, &[2]);
+    assert!(buffer.capacity() < 1 << 16);
+    assert_eq!(buffer.data(), &[1, 2]);
+    buffer.unlock_memory();
+}
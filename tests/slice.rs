@@ -0,0 +1,173 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::ErrorKind;
+use std::mem::MaybeUninit;
+
+use spare_buffer::{SpareBuffer, SpareSlice};
+
+mod common;
+use common::{check_reader_error_commits_nothing, nz};
+
+fn storage<T>(length: usize) -> Vec<MaybeUninit<T>> {
+    (0..length).map(|_| MaybeUninit::uninit()).collect()
+}
+
+#[test]
+fn test_read_until_full() {
+    let mut memory = storage::<u8>(8);
+    let mut slice = SpareSlice::from(&mut memory);
+    let mut reader: &[u8] = b"0123456789";
+    assert_eq!(slice.read_from(&mut reader, nz(5)).unwrap(), 5);
+    assert_eq!(slice.free(), 3);
+    assert_eq!(slice.read_from(&mut reader, nz(3)).unwrap(), 3);
+    assert!(slice.is_full());
+    assert_eq!(slice.data(), b"01234567");
+    let error = slice.read_from(&mut reader, nz(1)).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::OutOfMemory);
+    assert_eq!(slice.len(), 8);
+}
+
+#[test]
+fn test_empty_storage() {
+    let mut memory = storage::<u8>(0);
+    let mut slice = SpareSlice::from(&mut memory);
+    assert!(slice.is_empty());
+    assert!(slice.is_full());
+    let error = slice.read_from(&mut &b"abc"[..], nz(1)).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::OutOfMemory);
+}
+
+#[test]
+fn test_reader_error_commits_nothing() {
+    let mut memory = storage::<u8>(4);
+    let mut slice = SpareSlice::from(&mut memory);
+    check_reader_error_commits_nothing(&mut slice, |slice, reader| slice.read_from(reader, nz(2)), |slice| slice.data().to_vec());
+    assert_eq!(slice.read_from(&mut &b""[..], nz(3)).unwrap(), 0);
+    assert_eq!(slice.data(), b"a");
+}
+
+#[test]
+fn test_previous_contents_are_ignored() {
+    let mut memory: Vec<MaybeUninit<u8>> = b"stale".iter().map(|byte| MaybeUninit::new(*byte)).collect();
+    let mut slice = SpareSlice::from(&mut memory);
+    assert!(slice.is_empty());
+    slice.read_from(&mut &b"new"[..], nz(3)).unwrap();
+    assert_eq!(slice.data(), b"new");
+}
+
+#[test]
+fn test_clear_and_into_data() {
+    let mut memory = storage::<u8>(4);
+    let mut slice = SpareSlice::from(&mut memory);
+    slice.read_from(&mut &b"abcd"[..], nz(4)).unwrap();
+    slice.clear();
+    assert_eq!(slice.free(), slice.capacity());
+    slice.read_from(&mut &b"xy"[..], nz(2)).unwrap();
+    let data = slice.into_data();
+    data[0] = b'X';
+    assert_eq!(data, b"Xy");
+}
+
+#[test]
+fn test_commit_written() {
+    let mut memory = storage::<u64>(3);
+    let mut slice = SpareSlice::from(&mut memory);
+    let count = slice.commit_written(nz(1), |spare| {
+        assert_eq!(spare.len(), 3);
+        std::slice::from_ref(spare[0].write(u64::MAX))
+    });
+    assert_eq!(count, 1);
+    assert_eq!(slice.data(), &[u64::MAX]);
+}
+
+#[test]
+#[should_panic(expected = "Spare buffer was allocated uninitialized, use commit_written()!")]
+fn test_commit_of_uninit_allocation_panics() {
+    let mut memory = storage::<u8>(4);
+    let mut slice = SpareSlice::from(&mut memory);
+    slice.allocate_spare_uninit(nz(1))[0] = MaybeUninit::uninit();
+    slice.commit(1);
+}
+
+#[test]
+#[should_panic(expected = "The written slice is not a prefix of the spare buffer!")]
+fn test_commit_written_of_foreign_slice_panics() {
+    let mut memory = storage::<u8>(4);
+    let mut slice = SpareSlice::from(&mut memory);
+    slice.commit_written(nz(1), |_| b"foreign");
+}
+
+#[cfg(not(feature = "safe-only"))]
+#[test]
+fn test_allocate_and_commit() {
+    let mut memory = storage::<i32>(4);
+    let mut slice = SpareSlice::from(&mut memory);
+    slice.allocate_spare(nz(2))[..2].copy_from_slice(&[-1, 1]);
+    slice.commit(2);
+    slice.allocate_spare(nz(2))[..2].copy_from_slice(&[-2, 2]);
+    slice.commit(2);
+    assert_eq!(slice.data(), &[-1, 1, -2, 2]);
+}
+
+#[test]
+#[should_panic(expected = "Spare buffer exceeds the fixed capacity!")]
+fn test_allocate_exceeding_the_capacity_panics() {
+    let mut memory = storage::<u8>(4);
+    let mut slice = SpareSlice::from(&mut memory);
+    slice.allocate_spare_uninit(nz(5));
+}
+
+#[cfg(not(feature = "safe-only"))]
+#[test]
+#[should_panic(expected = "Commit size exceeds available capacity!")]
+fn test_commit_exceeding_the_capacity_panics() {
+    let mut memory = storage::<u8>(4);
+    let mut slice = SpareSlice::from(&mut memory);
+    slice.allocate_spare(nz(1));
+    slice.commit(5);
+}
+
+#[test]
+#[should_panic(expected = "No spare buffer allocated!")]
+fn test_commit_twice_panics() {
+    let mut memory = storage::<u8>(4);
+    let mut slice = SpareSlice::from(&mut memory);
+    slice.allocate_spare_uninit(nz(1));
+    slice.commit(0);
+    slice.commit(0);
+}
+
+#[test]
+fn test_as_backend_of_spare_buffer() {
+    let mut memory = storage::<u8>(8);
+    let mut slice = SpareSlice::from(&mut memory);
+    let mut buffer = SpareBuffer::from(&mut slice, None);
+    buffer.commit_written(nz(4), |spare| spare[..4].write_copy_of_slice(b"0123")).unwrap();
+    assert_eq!(buffer.data(), b"0123");
+    assert_eq!(slice.data(), b"0123");
+}
+
+#[test]
+#[should_panic(expected = "Spare buffer exceeds the fixed capacity!")]
+fn test_backend_reserve_exceeding_the_capacity_panics() {
+    let mut memory = storage::<u8>(4);
+    let mut slice = SpareSlice::from(&mut memory);
+    let mut buffer = SpareBuffer::from(&mut slice, None);
+    let _ = buffer.allocate_spare_uninit(nz(8));
+}
+
+#[cfg(not(feature = "safe-only"))]
+#[test]
+fn test_from_raw_parts() {
+    let mut mapped = storage::<u32>(4);
+    let mut slice = unsafe {
+        SpareSlice::from_raw_parts(mapped.as_mut_ptr(), mapped.len())
+    };
+    let mut buffer = SpareBuffer::from(&mut slice, None);
+    buffer.commit_written(nz(2), |spare| spare[..2].write_copy_of_slice(&[7, 8])).unwrap();
+    assert_eq!(buffer.data(), &[7, 8]);
+    assert_eq!(slice.free(), 2);
+    assert_eq!(slice.into_data(), &[7, 8]);
+}
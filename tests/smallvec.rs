@@ -0,0 +1,54 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+#![cfg(feature = "smallvec")]
+
+use smallvec::SmallVec;
+use spare_buffer::{SpareBuffer, SpareCapacity};
+
+mod common;
+use common::nz;
+
+fn fill(buffer: &mut SpareBuffer<u8, SmallVec<[u8; 8]>>, data: &[u8]) {
+    let count = buffer.commit_written(nz(data.len()), |spare| spare[..data.len()].write_copy_of_slice(data)).unwrap();
+    assert_eq!(count, data.len());
+}
+
+#[test]
+fn test_spill_to_the_heap() {
+    let mut vec = SmallVec::<[u8; 8]>::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    fill(&mut buffer, b"0123");
+    fill(&mut buffer, b"456789");
+    assert_eq!(buffer.data(), b"0123456789");
+    assert!(vec.spilled());
+    assert!(!vec.is_inline());
+}
+
+#[test]
+fn test_try_reserve_fails_on_overflow() {
+    let mut vec = SmallVec::<[u8; 8]>::new();
+    assert!(SpareCapacity::try_reserve(&mut vec, usize::MAX).is_err());
+    assert!(SpareCapacity::try_reserve_exact(&mut vec, usize::MAX).is_err());
+    assert!(vec.is_inline());
+}
+
+#[cfg(not(feature = "safe-only"))]
+#[test]
+fn test_moving_an_owned_inline_buffer() {
+    let mut buffer = SpareBuffer::owned(SmallVec::<[u8; 8]>::new(), None);
+    buffer.allocate_spare(nz(2))[..2].copy_from_slice(b"ab");
+    let mut moved = Box::new(buffer);
+    moved.commit(2).unwrap();
+    assert_eq!(moved.data(), b"ab");
+}
+
+#[cfg(any(unix, windows))]
+#[test]
+fn test_owned_inline_buffer_can_not_be_locked() {
+    let mut buffer = SpareBuffer::owned(SmallVec::<[u8; 8]>::new(), None);
+    let error = buffer.lock_memory().unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::Unsupported);
+    assert!(!buffer.is_memory_locked());
+}
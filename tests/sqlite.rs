@@ -0,0 +1,66 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+#![cfg(all(feature = "rusqlite", not(miri)))]
+
+use std::io::ErrorKind;
+use std::num::NonZeroUsize;
+
+use rusqlite::Connection;
+use spare_buffer::SpareBuffer;
+
+mod common;
+use common::nz;
+
+fn connection(data: &[u8]) -> Connection {
+    let connection = Connection::open_in_memory().unwrap();
+    connection.execute("CREATE TABLE blobs (data BLOB)", ()).unwrap();
+    connection.execute("INSERT INTO blobs (data) VALUES (?1)", (data,)).unwrap();
+    connection
+}
+
+#[test]
+fn test_read_blob_in_chunks() {
+    let data: Vec<u8> = (0..100u8).collect();
+    let connection = connection(&data);
+    let blob = connection.blob_open("main", "blobs", "data", 1, true).unwrap();
+    let mut vec = b">".to_vec();
+    let mut buffer = SpareBuffer::from(&mut vec, None).with_stats();
+    assert_eq!(buffer.read_blob(&blob, nz(32)).unwrap(), 100);
+    assert_eq!(&buffer.data()[1..], &data[..]);
+    assert_eq!(buffer.stats().unwrap().commits(), 4);
+}
+
+#[test]
+fn test_read_empty_blob() {
+    let connection = connection(b"");
+    let blob = connection.blob_open("main", "blobs", "data", 1, true).unwrap();
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    assert_eq!(buffer.read_blob(&blob, nz(32)).unwrap(), 0);
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_blob_beyond_the_limit_fails_up_front() {
+    let connection = connection(&[0u8; 64]);
+    let blob = connection.blob_open("main", "blobs", "data", 1, true).unwrap();
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, NonZeroUsize::new(63));
+    let error = buffer.read_blob(&blob, nz(16)).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::OutOfMemory);
+    assert_eq!(buffer.capacity(), 0);
+}
+
+#[test]
+fn test_sqlite_error_commits_nothing() {
+    let connection = connection(b"stale");
+    let blob = connection.blob_open("main", "blobs", "data", 1, true).unwrap();
+    connection.execute("UPDATE blobs SET data = ?1", (&b"fresh"[..],)).unwrap();
+    let mut vec = b"ab".to_vec();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    assert!(buffer.read_blob(&blob, nz(16)).is_err());
+    assert!(!buffer.discard_spare());
+    assert_eq!(buffer.data(), b"ab");
+}
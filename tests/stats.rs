@@ -0,0 +1,53 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use spare_buffer::SpareBuffer;
+
+mod common;
+use common::nz;
+
+#[test]
+fn test_stats_are_opt_in() {
+    let mut vec = Vec::<u8>::new();
+    let buffer = SpareBuffer::from(&mut vec, None);
+    assert_eq!(buffer.stats(), None);
+}
+
+#[test]
+fn test_allocations_and_commits_are_counted() {
+    let mut vec = Vec::with_capacity(8);
+    let mut buffer = SpareBuffer::from(&mut vec, None).with_stats();
+    let stats = *buffer.stats().unwrap();
+    assert_eq!((stats.allocations(), stats.commits(), stats.peak_capacity()), (0, 0, 8));
+    assert_eq!(stats.average_commit(), 0.0);
+    buffer.read_exact_into(&mut &b"abcd"[..], nz(4)).unwrap();
+    buffer.read_exact_into(&mut &b"ef"[..], nz(2)).unwrap();
+    let stats = buffer.stats().unwrap();
+    assert_eq!(stats.allocations(), 2);
+    assert_eq!(stats.reallocations(), 0);
+    assert_eq!(stats.commits(), 2);
+    assert_eq!(stats.committed(), 6);
+    assert_eq!(stats.average_commit(), 3.0);
+}
+
+#[test]
+fn test_empty_commits_are_not_counted() {
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None).with_stats();
+    assert_eq!(buffer.fill_from(&mut &b""[..], nz(4)).unwrap(), 0);
+    let stats = buffer.stats().unwrap();
+    assert_eq!((stats.allocations(), stats.commits(), stats.committed()), (1, 0, 0));
+}
+
+#[test]
+fn test_reallocations_and_peak_capacity() {
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None).with_stats();
+    buffer.read_exact_into(&mut &[0u8; 16][..], nz(16)).unwrap();
+    buffer.read_exact_into(&mut &[0u8; 64][..], nz(64)).unwrap();
+    let stats = buffer.stats().unwrap();
+    assert!(stats.reallocations() >= 2);
+    assert!(stats.peak_capacity() >= 80);
+    assert_eq!(stats.peak_capacity(), buffer.capacity());
+}
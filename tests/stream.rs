@@ -0,0 +1,74 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+#![cfg(feature = "futures-core")]
+
+use std::collections::VecDeque;
+use std::io::{Error as IoError, Result as IoResult, ErrorKind};
+use std::num::NonZeroUsize;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+use spare_buffer::SpareBuffer;
+
+mod common;
+use common::block_on;
+
+/// Yields the given chunks, then ends.
+struct ChunkStream(VecDeque<IoResult<Vec<u8>>>);
+
+impl ChunkStream {
+    fn new(chunks: &[&[u8]]) -> Self {
+        Self(chunks.iter().map(|chunk| Ok(chunk.to_vec())).collect())
+    }
+}
+
+impl Stream for ChunkStream {
+    type Item = IoResult<Vec<u8>>;
+
+    fn poll_next(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Poll::Ready(self.get_mut().0.pop_front())
+    }
+}
+
+#[test]
+fn test_collect_stream() {
+    let mut vec = b">".to_vec();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let stream = ChunkStream::new(&[b"abc", b"", b"de"]);
+    assert_eq!(block_on(buffer.collect_stream(stream, Some(5))).unwrap(), 5);
+    assert_eq!(buffer.data(), b">abcde");
+}
+
+#[test]
+fn test_size_hint_is_clamped_to_the_limit() {
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, NonZeroUsize::new(8));
+    let stream = ChunkStream::new(&[b"abc"]);
+    assert_eq!(block_on(buffer.collect_stream(stream, Some(usize::MAX))).unwrap(), 3);
+    assert!(buffer.capacity() < 1024);
+}
+
+#[test]
+fn test_chunk_beyond_the_limit_fails() {
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, NonZeroUsize::new(4));
+    let stream = ChunkStream::new(&[b"abc", b"de"]);
+    let error = block_on(buffer.collect_stream(stream, None)).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::OutOfMemory);
+    assert_eq!(buffer.data(), b"abc");
+}
+
+#[test]
+fn test_stream_error_keeps_the_committed_chunks() {
+    let mut stream = ChunkStream::new(&[b"abc"]);
+    stream.0.push_back(Err(IoError::from(ErrorKind::ConnectionAborted)));
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let error = block_on(buffer.collect_stream(stream, None)).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::ConnectionAborted);
+    assert!(!buffer.discard_spare());
+    assert_eq!(buffer.data(), b"abc");
+}
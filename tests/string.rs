@@ -0,0 +1,178 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::{BufReader, ErrorKind};
+use std::num::NonZeroUsize;
+
+use spare_buffer::SpareString;
+
+mod common;
+use common::{nz, ChunkedReader};
+
+#[test]
+fn test_read_valid_text() {
+    let mut string = String::from("> ");
+    let mut spare = SpareString::from(&mut string, None);
+    let mut reader = ChunkedReader::new("Grüße".as_bytes(), 64);
+    assert_eq!(spare.read_from(&mut reader, nz(64)).unwrap(), 7);
+    assert_eq!(spare.read_from(&mut reader, nz(64)).unwrap(), 0);
+    spare.finish().unwrap();
+    assert_eq!(string, "> Grüße");
+}
+
+#[test]
+fn test_split_sequence_is_an_error_without_incremental_mode() {
+    let mut string = String::new();
+    let mut spare = SpareString::from(&mut string, None);
+    let mut reader = ChunkedReader::new("aü".as_bytes(), 2);
+    let error = spare.read_from(&mut reader, nz(64)).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    assert!(spare.is_empty());
+    assert!(spare.pending().is_empty());
+}
+
+#[test]
+fn test_split_sequence_is_carried_in_incremental_mode() {
+    let text = "a€b😀";
+    let mut string = String::new();
+    let mut spare = SpareString::from(&mut string, None).with_incremental(true);
+    let mut reader = ChunkedReader::new(text.as_bytes(), 1);
+    let mut total = 0usize;
+    loop {
+        match spare.read_from(&mut reader, nz(8)).unwrap() {
+            0 => break,
+            count => total += count,
+        }
+        assert!(spare.pending().len() <= 3);
+    }
+    assert_eq!(total, text.len());
+    spare.finish().unwrap();
+    assert_eq!(string, text);
+}
+
+#[test]
+fn test_pending_bytes_are_exposed() {
+    let bytes = "€".as_bytes();
+    let mut string = String::new();
+    let mut spare = SpareString::from(&mut string, None).with_incremental(true);
+    let mut reader = ChunkedReader::new(&bytes[..2], 64);
+    assert_eq!(spare.read_from(&mut reader, nz(64)).unwrap(), 2);
+    assert_eq!(spare.pending(), &bytes[..2]);
+    assert!(spare.is_empty());
+    let mut reader = ChunkedReader::new(&bytes[2..], 64);
+    assert_eq!(spare.read_from(&mut reader, nz(64)).unwrap(), 1);
+    assert!(spare.pending().is_empty());
+    assert_eq!(spare.data(), "€");
+}
+
+#[test]
+fn test_finish_with_pending_bytes_fails() {
+    let mut string = String::new();
+    let mut spare = SpareString::from(&mut string, None).with_incremental(true);
+    let mut reader = ChunkedReader::new(&"ö".as_bytes()[..1], 64);
+    spare.read_from(&mut reader, nz(64)).unwrap();
+    let error = spare.finish().unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    assert!(spare.pending().is_empty());
+    spare.finish().unwrap();
+}
+
+#[test]
+fn test_invalid_sequence_fails_in_incremental_mode() {
+    let mut string = String::from("ok");
+    let mut spare = SpareString::from(&mut string, None).with_incremental(true);
+    let mut reader = ChunkedReader::new(b"\xFFabc", 64);
+    let error = spare.read_from(&mut reader, nz(64)).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    assert_eq!(spare.data(), "ok");
+}
+
+#[test]
+fn test_commit_exceeding_the_limit_fails() {
+    let mut string = String::from("abc");
+    let mut spare = SpareString::from(&mut string, NonZeroUsize::new(5));
+    let mut reader = ChunkedReader::new(b"def", 64);
+    let error = spare.read_from(&mut reader, nz(64)).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::OutOfMemory);
+    assert_eq!(spare.data(), "abc");
+    let mut reader = ChunkedReader::new(b"de", 64);
+    assert_eq!(spare.read_from(&mut reader, nz(64)).unwrap(), 2);
+    assert_eq!(spare.data(), "abcde");
+}
+
+#[test]
+fn test_reader_error_keeps_the_pending_bytes() {
+    let bytes = "€".as_bytes();
+    let mut string = String::new();
+    let mut spare = SpareString::from(&mut string, None).with_incremental(true);
+    let mut reader = ChunkedReader::new(&bytes[..1], 64);
+    reader.error = Some(ErrorKind::ConnectionReset);
+    spare.read_from(&mut reader, nz(64)).unwrap();
+    let error = spare.read_from(&mut reader, nz(64)).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::ConnectionReset);
+    assert_eq!(spare.pending(), &bytes[..1]);
+    let mut reader = ChunkedReader::new(&bytes[1..], 64);
+    spare.read_from(&mut reader, nz(64)).unwrap();
+    assert_eq!(spare.data(), "€");
+}
+
+#[test]
+#[should_panic(expected = "No spare buffer allocated!")]
+fn test_reader_error_discards_the_spare_buffer() {
+    let mut string = String::new();
+    let mut spare = SpareString::from(&mut string, None);
+    let mut reader = ChunkedReader::new(b"", 64);
+    reader.error = Some(ErrorKind::ConnectionReset);
+    assert!(spare.read_from(&mut reader, nz(64)).is_err());
+    let _ = spare.commit(0);
+}
+
+#[test]
+fn test_read_lines_with_split_sequences() {
+    let text = "zwölf\nüber€\nend";
+    let mut reader = BufReader::with_capacity(1, ChunkedReader::new(text.as_bytes(), 1));
+    let mut string = String::new();
+    let mut spare = SpareString::from(&mut string, None);
+    assert_eq!(spare.read_line_into(&mut reader).unwrap(), "zwölf\n".len());
+    assert_eq!(spare.data(), "zwölf\n");
+    assert_eq!(spare.read_line_into(&mut reader).unwrap(), "über€\n".len());
+    assert_eq!(spare.read_line_into(&mut reader).unwrap(), 3);
+    assert_eq!(spare.read_line_into(&mut reader).unwrap(), 0);
+    assert_eq!(string, text);
+}
+
+#[test]
+fn test_read_line_with_incomplete_sequence_at_the_end_fails() {
+    let mut reader = BufReader::new(ChunkedReader::new(b"abc\xE2\x82", 64));
+    let mut string = String::new();
+    let mut spare = SpareString::from(&mut string, None);
+    let error = spare.read_line_into(&mut reader).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    assert_eq!(spare.data(), "abc");
+    assert!(!spare.incremental());
+}
+
+#[cfg(not(feature = "safe-only"))]
+#[test]
+fn test_allocate_and_commit() {
+    let mut string = String::new();
+    let mut spare = SpareString::from(&mut string, None).with_incremental(true);
+    let bytes = "😀".as_bytes();
+    spare.allocate_spare(nz(4))[..3].copy_from_slice(&bytes[..3]);
+    spare.commit(3).unwrap();
+    assert_eq!(spare.pending().len(), 3);
+    let buffer = spare.allocate_spare(nz(4));
+    assert!(buffer.len() >= 4);
+    buffer[0] = bytes[3];
+    spare.commit(1).unwrap();
+    assert_eq!(spare.data(), "😀");
+}
+
+#[test]
+#[should_panic(expected = "No spare buffer allocated!")]
+fn test_commit_without_allocation_panics() {
+    let mut string = String::new();
+    let mut spare = SpareString::from(&mut string, None);
+    let _ = spare.commit(0);
+}
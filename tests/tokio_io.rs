@@ -0,0 +1,41 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+#![cfg(feature = "tokio")]
+
+use std::io::ErrorKind;
+use std::num::NonZeroUsize;
+use std::pin::Pin;
+use std::task::{Context, Poll, Waker};
+
+use spare_buffer::SpareBuffer;
+use tokio::io::AsyncWrite;
+
+#[test]
+fn test_poll_write_commits_right_away() {
+    let mut vec = b"ab".to_vec();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let mut cx = Context::from_waker(Waker::noop());
+    assert!(matches!(Pin::new(&mut buffer).poll_write(&mut cx, b"cd"), Poll::Ready(Ok(2))));
+    assert!(!buffer.discard_spare());
+    assert!(matches!(Pin::new(&mut buffer).poll_flush(&mut cx), Poll::Ready(Ok(()))));
+    assert!(matches!(Pin::new(&mut buffer).poll_shutdown(&mut cx), Poll::Ready(Ok(()))));
+    assert_eq!(buffer.data(), b"abcd");
+}
+
+#[test]
+fn test_poll_write_is_shortened() {
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, NonZeroUsize::new(8)).with_max_allocation(NonZeroUsize::new(3));
+    let mut cx = Context::from_waker(Waker::noop());
+    assert!(matches!(Pin::new(&mut buffer).poll_write(&mut cx, b"abcde"), Poll::Ready(Ok(3))));
+    assert!(matches!(Pin::new(&mut buffer).poll_write(&mut cx, b"defgh"), Poll::Ready(Ok(3))));
+    assert!(matches!(Pin::new(&mut buffer).poll_write(&mut cx, b"ghi"), Poll::Ready(Ok(2))));
+    match Pin::new(&mut buffer).poll_write(&mut cx, b"i") {
+        Poll::Ready(Err(error)) => assert_eq!(error.kind(), ErrorKind::OutOfMemory),
+        _ => panic!("The write beyond the limit did not fail!"),
+    }
+    assert!(matches!(Pin::new(&mut buffer).poll_write(&mut cx, b""), Poll::Ready(Ok(0))));
+    assert_eq!(buffer.data(), b"abcdefgh");
+}
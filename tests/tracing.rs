@@ -0,0 +1,110 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+#![cfg(all(feature = "tracing", not(feature = "safe-only")))]
+
+use std::fmt::Debug;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+
+use spare_buffer::{SpareBuffer, SpareVec};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{subscriber, Event, Metadata, Subscriber};
+
+mod common;
+use common::nz;
+
+/// Records the messages of all events, and the names of all spans, with the
+/// target `spare_buffer`.
+#[derive(Clone, Default)]
+struct Recorder(Arc<Mutex<Vec<String>>>);
+
+impl Recorder {
+    fn take(&self) -> Vec<String> {
+        std::mem::take(&mut self.0.lock().unwrap())
+    }
+}
+
+impl Visit for Recorder {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if field.name() == "message" {
+            self.0.lock().unwrap().push(format!("{:?}", value));
+        }
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.target() == "spare_buffer"
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        self.0.lock().unwrap().push(format!("span {}", span.metadata().name()));
+        Id::from_u64(1)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        event.record(&mut self.clone());
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+/// Runs `f` with a [`Recorder`] as the default subscriber, and returns the
+/// recorded messages.
+fn record<F: FnOnce()>(f: F) -> Vec<String> {
+    let recorder = Recorder::default();
+    subscriber::with_default(recorder.clone(), f);
+    recorder.take()
+}
+
+#[test]
+fn test_allocations_reallocations_and_commits_are_traced() {
+    let messages = record(|| {
+        let mut vec = Vec::new();
+        let mut buffer = SpareBuffer::from(&mut vec, None);
+        buffer.allocate_spare(nz(4))[..2].copy_from_slice(b"ab");
+        buffer.commit(2).unwrap();
+    });
+    assert!(messages.iter().any(|message| message.starts_with("Reallocated the storage")));
+    assert!(messages.iter().any(|message| message.starts_with("Allocated a spare buffer of 4 elements")));
+    assert_eq!(messages.last().unwrap(), "Committed 2 elements, length is 2");
+}
+
+#[test]
+fn test_limit_violations_are_traced() {
+    let messages = record(|| {
+        let mut vec: Vec<u8> = Vec::new();
+        let mut buffer = SpareBuffer::from(&mut vec, NonZeroUsize::new(2));
+        buffer.allocate_spare(nz(4));
+        assert!(buffer.commit(4).is_err());
+    });
+    assert!(messages.iter().any(|message| message.starts_with("Commit of 4 elements exceeds the headroom")));
+}
+
+#[test]
+fn test_chunked_reads_are_spanned() {
+    let messages = record(|| {
+        let mut vec = Vec::new();
+        let mut buffer = SpareBuffer::from(&mut vec, None);
+        buffer.read_all_from(&mut &b"abc"[..]).unwrap();
+    });
+    assert_eq!(messages[0], "span read_chunks");
+}
+
+#[test]
+fn test_outstanding_spare_buffer_is_reported_on_drop() {
+    let messages = record(|| {
+        let mut vec = SpareVec::<u8>::owned(Vec::new(), None);
+        vec.allocate_spare(nz(4));
+    });
+    assert_eq!(messages.last().unwrap(), "A `SpareVec` was dropped with a spare buffer that was neither committed nor discarded!");
+}
@@ -0,0 +1,73 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::mem::MaybeUninit;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use spare_buffer::SpareBuffer;
+
+mod common;
+use common::nz;
+
+#[test]
+fn test_commit_written() {
+    let mut vec: Vec<u16> = vec![1];
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let count = buffer.commit_written(nz(4), |spare| spare[..2].write_copy_of_slice(&[2, 3])).unwrap();
+    assert_eq!(count, 2);
+    assert_eq!(buffer.data(), &[1, 2, 3]);
+}
+
+#[test]
+#[should_panic(expected = "Spare buffer was allocated uninitialized, use commit_written()!")]
+fn test_commit_of_uninit_allocation_panics() {
+    let mut vec: Vec<u8> = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    buffer.allocate_spare_uninit(nz(4))[0] = MaybeUninit::uninit();
+    let _ = buffer.commit(1);
+}
+
+#[test]
+#[should_panic(expected = "Spare buffer was allocated uninitialized, use commit_written()!")]
+fn test_commit_all_of_uninit_allocation_panics() {
+    let mut vec: Vec<u8> = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    buffer.allocate_spare_uninit(nz(4));
+    let _ = buffer.commit_all();
+}
+
+#[test]
+fn test_rejected_commit_invalidates_the_spare_buffer() {
+    let mut vec: Vec<u8> = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    buffer.allocate_spare_uninit(nz(4));
+    assert!(catch_unwind(AssertUnwindSafe(|| buffer.commit_partial(2))).is_err());
+    assert!(!buffer.discard_spare());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_uninit_allocation_can_be_discarded() {
+    let mut vec: Vec<u8> = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    buffer.allocate_spare_uninit(nz(4));
+    buffer.commit(0).unwrap();
+    buffer.allocate_spare_uninit(nz(4));
+    assert!(buffer.discard_spare());
+    buffer.commit_written(nz(1), |spare| spare[..1].write_copy_of_slice(b"x")).unwrap();
+    assert_eq!(buffer.data(), b"x");
+}
+
+#[cfg(feature = "allocator-api2")]
+#[test]
+fn test_allocator_commit_of_uninit_allocation_panics() {
+    use spare_buffer::SpareBufferIn;
+    let mut vec = allocator_api2::vec::Vec::<u8>::new();
+    let mut buffer = SpareBufferIn::from(&mut vec, None);
+    buffer.allocate_spare_uninit(nz(4))[0].write(1);
+    assert!(catch_unwind(AssertUnwindSafe(|| buffer.commit(1))).is_err());
+    let count = buffer.commit_written(nz(4), |spare| spare[..3].write_copy_of_slice(b"abc")).unwrap();
+    assert_eq!(count, 3);
+    assert_eq!(buffer.data(), b"abc");
+}
@@ -0,0 +1,57 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+#![cfg(all(target_os = "linux", not(feature = "safe-only")))]
+
+//! The kernel is *simulated*, by writing through the prepared address, so
+//! that the tests do **not** depend on io_uring being available.
+
+use std::io::ErrorKind;
+use std::ptr::copy_nonoverlapping;
+
+use spare_buffer::SpareBuffer;
+
+mod common;
+use common::nz;
+
+#[test]
+fn test_completion_commits_the_result() {
+    let mut vec = b"ab".to_vec();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let (address, length) = buffer.prepare_read(nz(8)).unwrap();
+    assert_eq!(length, 8);
+    unsafe {
+        copy_nonoverlapping(b"cde".as_ptr(), address, 3);
+    }
+    assert_eq!(buffer.commit_completion(3).unwrap(), 3);
+    assert_eq!(buffer.data(), b"abcde");
+}
+
+#[test]
+fn test_negative_result_is_an_os_error() {
+    let mut vec = b"ab".to_vec();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    buffer.prepare_read(nz(8)).unwrap();
+    let error = buffer.commit_completion(-11).unwrap_err();
+    assert_eq!(error.raw_os_error(), Some(11));
+    assert_eq!(error.kind(), ErrorKind::WouldBlock);
+    assert!(!buffer.discard_spare());
+    assert_eq!(buffer.data(), b"ab");
+}
+
+#[test]
+fn test_prepare_read_respects_the_max_allocation() {
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None).with_max_allocation(Some(nz(4)));
+    let error = buffer.prepare_read(nz(8)).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::OutOfMemory);
+}
+
+#[test]
+#[should_panic(expected = "No spare buffer allocated!")]
+fn test_completion_without_preparation_panics() {
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let _ = buffer.commit_completion(0);
+}
@@ -0,0 +1,82 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::{IoSliceMut, Read, Result as IoResult, ErrorKind};
+use std::num::NonZeroUsize;
+
+use spare_buffer::SpareBuffer;
+
+mod common;
+use common::{nz, FailingReader};
+
+/// Fills *all* of the given slices, in order, as far as the data goes.
+struct VectoredReader<'a> {
+    data: &'a [u8],
+    slices: Vec<usize>,
+}
+
+impl<'a> VectoredReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, slices: Vec::new() }
+    }
+}
+
+impl Read for VectoredReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
+        self.data.read(buf)
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> IoResult<usize> {
+        self.slices = bufs.iter().map(|buf| buf.len()).collect();
+        let mut total = 0;
+        for buf in bufs {
+            total += self.data.read(buf)?;
+        }
+        Ok(total)
+    }
+}
+
+#[test]
+fn test_read_into_adjacent_chunks() {
+    let mut vec = b"ab".to_vec();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let mut reader = VectoredReader::new(b"cdefghij");
+    assert_eq!(buffer.read_vectored_from(&mut reader, nz(3), nz(2)).unwrap(), 6);
+    assert_eq!(reader.slices, [3, 3]);
+    assert_eq!(buffer.data(), b"abcdefgh");
+}
+
+#[test]
+fn test_length_is_clamped_to_the_limit() {
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, NonZeroUsize::new(5));
+    let mut reader = VectoredReader::new(b"abcdefgh");
+    assert_eq!(buffer.read_vectored_from(&mut reader, nz(2), nz(4)).unwrap(), 5);
+    assert_eq!(reader.slices, [2, 2, 1]);
+    assert_eq!(buffer.read_vectored_from(&mut reader, nz(2), nz(4)).unwrap(), 0);
+    assert_eq!(buffer.data(), b"abcde");
+}
+
+#[test]
+fn test_reader_error_commits_nothing() {
+    let mut vec = b"ab".to_vec();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let error = buffer.read_vectored_from(&mut FailingReader(ErrorKind::BrokenPipe), nz(2), nz(2)).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::BrokenPipe);
+    assert!(!buffer.discard_spare());
+    assert_eq!(buffer.data(), b"ab");
+}
+
+#[cfg(not(feature = "safe-only"))]
+#[test]
+fn test_allocate_spare_vectored() {
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let mut slices = buffer.allocate_spare_vectored(nz(2), nz(3));
+    assert_eq!(slices.iter().map(|slice| slice.len()).collect::<Vec<_>>(), [2, 2, 2]);
+    let count = VectoredReader::new(b"abcde").read_vectored(&mut slices).unwrap();
+    drop(slices);
+    buffer.commit(count).unwrap();
+    assert_eq!(buffer.data(), b"abcde");
+}
@@ -0,0 +1,54 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::io::{Error as IoError, ErrorKind};
+
+use spare_buffer::{SpareBuffer, VerifyError};
+
+#[test]
+fn test_matching_length() {
+    let mut vec = b"payload".to_vec();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    buffer.consume(3);
+    buffer.finish_verified(7).unwrap();
+}
+
+#[test]
+fn test_truncated_data_is_rejected() {
+    let mut vec = b"pay".to_vec();
+    let buffer = SpareBuffer::from(&mut vec, None);
+    let error = buffer.finish_verified(7).unwrap_err();
+    assert_eq!(error, VerifyError::LengthMismatch { expected: 7, actual: 3 });
+    assert_eq!(IoError::from(error).kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_overlong_data_is_rejected() {
+    let mut vec = b"payload!".to_vec();
+    let buffer = SpareBuffer::from(&mut vec, None);
+    assert!(matches!(buffer.finish_verified(7), Err(VerifyError::LengthMismatch { expected: 7, actual: 8 })));
+}
+
+#[cfg(feature = "crc32fast")]
+#[test]
+fn test_checksum_is_verified() {
+    use std::io::Write;
+
+    let mut vec = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None).with_crc32();
+    buffer.write_all(b"123456789").unwrap();
+    buffer.finish_verified_crc32(9, 0xCBF43926).unwrap();
+    let error = buffer.finish_verified_crc32(9, 0xDEADBEEF).unwrap_err();
+    assert_eq!(error, VerifyError::ChecksumMismatch { expected: 0xDEADBEEF, actual: 0xCBF43926 });
+    assert!(matches!(buffer.finish_verified_crc32(8, 0xCBF43926), Err(VerifyError::LengthMismatch { .. })));
+}
+
+#[cfg(feature = "crc32fast")]
+#[test]
+#[should_panic(expected = "CRC-32 checksum not enabled!")]
+fn test_checksum_not_enabled_panics() {
+    let mut vec = Vec::new();
+    let buffer = SpareBuffer::<u8>::from(&mut vec, None);
+    let _ = buffer.finish_verified_crc32(0, 0);
+}
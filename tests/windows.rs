@@ -0,0 +1,71 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+#![cfg(all(windows, not(feature = "safe-only")))]
+
+use std::fs::OpenOptions;
+use std::io::Error as IoError;
+use std::os::windows::fs::OpenOptionsExt;
+use std::os::windows::io::AsRawHandle;
+
+use spare_buffer::SpareBuffer;
+use windows_sys::Win32::Foundation::{ERROR_IO_PENDING, HANDLE};
+use windows_sys::Win32::Storage::FileSystem::{ReadFile, FILE_FLAG_OVERLAPPED};
+use windows_sys::Win32::System::IO::{GetOverlappedResult, OVERLAPPED};
+
+mod common;
+use common::nz;
+
+#[test]
+fn test_overlapped_read() {
+    let path = std::env::temp_dir().join(format!("spare_buffer_overlapped_{}", std::process::id()));
+    std::fs::write(&path, b"0123456789").unwrap();
+    let file = OpenOptions::new().read(true).custom_flags(FILE_FLAG_OVERLAPPED).open(&path).unwrap();
+    let handle = file.as_raw_handle() as HANDLE;
+
+    let mut vec = b"data:".to_vec();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    for (offset, expected) in [(4u64, 6usize), (10, 0)] {
+        let read = buffer.prepare_overlapped(nz(16), offset).unwrap();
+        let (ptr, length) = read.buffer();
+        let overlapped = read.overlapped_ptr() as *mut OVERLAPPED;
+        let result = unsafe {
+            if ReadFile(handle, ptr, length, std::ptr::null_mut(), overlapped) == 0 && IoError::last_os_error().raw_os_error() != Some(ERROR_IO_PENDING as i32) {
+                Err(IoError::last_os_error())
+            } else {
+                let mut transferred = 0u32;
+                match GetOverlappedResult(handle, overlapped, &mut transferred, 1) {
+                    0 => Err(IoError::last_os_error()),
+                    _ => Ok(transferred),
+                }
+            }
+        };
+        assert_eq!(buffer.commit_overlapped(read, result).unwrap(), expected);
+    }
+    assert_eq!(buffer.data(), b"data:456789");
+    assert!(!buffer.discard_spare());
+
+    drop(file);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_failed_overlapped_read_discards_the_spare_buffer() {
+    let mut vec: Vec<u8> = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let read = buffer.prepare_overlapped(nz(8), 0).unwrap();
+    let error = buffer.commit_overlapped(read, Err(IoError::from_raw_os_error(5))).unwrap_err();
+    assert_eq!(error.raw_os_error(), Some(5));
+    assert!(!buffer.discard_spare());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Read size exceeds the spare buffer!")]
+fn test_overlapped_read_exceeding_the_buffer_panics() {
+    let mut vec: Vec<u8> = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let read = buffer.prepare_overlapped(nz(8), 0).unwrap();
+    let _ = buffer.commit_overlapped(read, Ok(9));
+}
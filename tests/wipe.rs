@@ -0,0 +1,76 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use spare_buffer::{SpareBuffer, SpareCapacity};
+
+mod common;
+use common::nz;
+
+/// A fixed-capacity backend over memory that outlives it, so that the
+/// storage can be inspected after the **`SpareVec`** has been dropped.
+struct External {
+    ptr: *mut u8,
+    capacity: usize,
+    length: usize,
+}
+
+unsafe impl SpareCapacity<u8> for External {
+    fn len(&self) -> usize {
+        self.length
+    }
+
+    fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        assert!(additional <= self.capacity - self.length, "The external storage is full!");
+    }
+
+    fn as_ptr(&self) -> *const u8 {
+        self.ptr
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut u8 {
+        self.ptr
+    }
+
+    unsafe fn set_len(&mut self, new_len: usize) {
+        self.length = new_len;
+    }
+}
+
+#[test]
+fn test_wipe_zeroes_the_whole_storage() {
+    let mut vec = Vec::with_capacity(16);
+    vec.extend_from_slice(b"secret");
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    buffer.fill_from(&mut &b"more"[..], nz(4)).unwrap();
+    buffer.wipe();
+    assert!(!buffer.discard_spare());
+    assert!(buffer.is_empty());
+    assert!(vec.is_empty());
+    assert!(vec.spare_capacity_mut()[..16].iter().all(|byte| unsafe { byte.assume_init() } == 0));
+}
+
+#[test]
+fn test_wipe_on_drop() {
+    let mut memory = [0xAAu8; 16];
+    let backend = External { ptr: memory.as_mut_ptr(), capacity: memory.len(), length: 0 };
+    let mut buffer = SpareBuffer::owned(backend, None).with_wipe_on_drop();
+    buffer.fill_from(&mut &b"secret"[..], nz(4)).unwrap();
+    assert_eq!(buffer.data(), b"secret");
+    drop(buffer);
+    assert_eq!(memory, [0u8; 16]);
+}
+
+#[test]
+fn test_no_wipe_without_opt_in() {
+    let mut memory = [0u8; 16];
+    let backend = External { ptr: memory.as_mut_ptr(), capacity: memory.len(), length: 0 };
+    let mut buffer = SpareBuffer::owned(backend, None);
+    buffer.fill_from(&mut &b"public"[..], nz(4)).unwrap();
+    drop(buffer);
+    assert_eq!(&memory[..6], b"public");
+}
@@ -0,0 +1,79 @@
+/*
+ * Spare Buffer
+ * This is free and unencumbered software released into the public domain.
+ */
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use spare_buffer::SpareBuffer;
+
+mod common;
+use common::nz;
+
+#[test]
+fn test_commit_written_elements() {
+    let mut vec: Vec<u8> = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let mut writer = buffer.writer(nz(8));
+    assert_eq!(writer.write(b"ab"), 2);
+    assert_eq!(writer.written(), b"ab");
+    assert_eq!(writer.commit().unwrap(), 2);
+    assert_eq!(buffer.data(), b"ab");
+}
+
+#[test]
+#[should_panic(expected = "No spare buffer allocated!")]
+fn test_dropped_writer_discards_the_spare_buffer() {
+    let mut vec: Vec<u8> = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let mut writer = buffer.writer(nz(8));
+    writer.write(b"ab");
+    drop(writer);
+    let _ = buffer.commit(8);
+}
+
+#[test]
+fn test_dropped_empty_writer_discards_the_spare_buffer() {
+    let mut vec: Vec<u8> = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    drop(buffer.writer(nz(8)));
+    assert!(!buffer.discard_spare());
+}
+
+#[test]
+fn test_dropped_auto_writer_commits() {
+    let mut vec: Vec<u8> = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let mut writer = buffer.auto_writer(nz(8));
+    writer.write(b"abc");
+    drop(writer);
+    assert!(!buffer.discard_spare());
+    assert_eq!(buffer.data(), b"abc");
+}
+
+#[test]
+fn test_panic_discards_the_spare_buffer() {
+    let mut vec: Vec<u8> = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        let mut writer = buffer.auto_writer(nz(8));
+        writer.write(b"abc");
+        panic!("Injected panic!");
+    }));
+    assert!(result.is_err());
+    assert!(!buffer.discard_spare());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_writer_stops_at_its_length() {
+    let mut vec: Vec<u8> = Vec::new();
+    let mut buffer = SpareBuffer::from(&mut vec, None);
+    let mut writer = buffer.writer(nz(3));
+    assert_eq!(writer.write(b"abcd"), 3);
+    assert!(writer.is_full());
+    assert!(!writer.push(b'e'));
+    assert_eq!(writer.peek_filled(2), Some(&b"ab"[..]));
+    assert_eq!(writer.peek_filled(4), None);
+    writer.discard();
+    assert!(buffer.is_empty());
+}